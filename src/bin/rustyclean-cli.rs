@@ -0,0 +1,6 @@
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    // Kommandozeilenvariante ohne GUI, z.B. für Skripte oder SSH-Sitzungen
+    rustyclean::cli::main()
+}
@@ -0,0 +1,310 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::plan::classify_io_error;
+use super::progress::CleaningMonitor;
+use super::temp_files::CleaningError;
+use super::walker::{run_workers, worker_count, WorkQueue};
+
+/// Optionen der Speicherplatzanalyse
+#[derive(Debug, Clone)]
+pub struct UsageOptions {
+    /// Anzahl der größten Dateien und Ordner in der Auswertung
+    pub top_n: usize,
+    /// Nicht in andere Dateisysteme wechseln (z.B. `/proc` oder eingehängte Laufwerke)
+    pub same_filesystem: bool,
+    /// Anzahl paralleler Worker (0 = automatisch)
+    pub worker_threads: usize,
+}
+
+impl Default for UsageOptions {
+    fn default() -> Self {
+        Self {
+            top_n: 20,
+            same_filesystem: true,
+            worker_threads: 0,
+        }
+    }
+}
+
+/// Verzeichnis mit aufsummierter Größe
+#[derive(Debug, Clone)]
+pub struct UsageNode {
+    pub name: String,
+    pub path: PathBuf,
+    /// Gesamtgröße einschließlich aller Unterverzeichnisse
+    pub size: u64,
+    /// Größe der Dateien direkt in diesem Verzeichnis
+    pub file_size: u64,
+    /// Anzahl Dateien einschließlich aller Unterverzeichnisse
+    pub file_count: usize,
+    /// Größtes zuerst
+    pub children: Vec<UsageNode>,
+}
+
+impl UsageNode {
+    /// Sucht den Knoten eines Verzeichnisses unterhalb (oder gleich) diesem
+    pub fn find(&self, path: &Path) -> Option<&UsageNode> {
+        if path == self.path {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .find(|child| path.starts_with(&child.path))
+            .and_then(|child| child.find(path))
+    }
+
+    /// Alle Knoten des Teilbaums (Tiefensuche, dieser zuerst)
+    pub fn descendants(&self) -> Vec<&UsageNode> {
+        let mut nodes = vec![self];
+        let mut index = 0;
+        while index < nodes.len() {
+            let node = nodes[index];
+            nodes.extend(node.children.iter());
+            index += 1;
+        }
+        nodes
+    }
+}
+
+/// Eine der größten gefundenen Dateien
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LargeFile {
+    pub size: u64,
+    pub path: PathBuf,
+}
+
+/// Ergebnis einer Speicherplatzanalyse
+#[derive(Debug, Clone)]
+pub struct DiskUsage {
+    pub root: UsageNode,
+    /// Größte zuerst, höchstens `top_n`
+    pub largest_files: Vec<LargeFile>,
+    pub top_n: usize,
+    pub errors: Vec<CleaningError>,
+    pub scan_time: Duration,
+    /// Analyse wurde abgebrochen; die Größen sind unvollständig
+    pub cancelled: bool,
+}
+
+impl DiskUsage {
+    /// Ordner, die selbst (ohne Unterverzeichnisse) die meisten Daten enthalten, größte zuerst
+    ///
+    /// Nach der Gesamtgröße sortiert stünden nur die Vorfahren der großen Ordner oben.
+    pub fn largest_dirs(&self) -> Vec<&UsageNode> {
+        let mut nodes = self.root.descendants();
+        nodes.sort_by_key(|node| Reverse(node.file_size));
+        nodes.truncate(self.top_n);
+        nodes
+    }
+}
+
+/// Analysiert den Speicherverbrauch unterhalb von `root`
+///
+/// Es wird nur gelesen; die Sperrliste der Bereinigung gilt hier nicht. Symlinks werden
+/// nicht verfolgt, Größen sind die Dateilängen.
+pub fn analyze(root: &Path, options: &UsageOptions) -> DiskUsage {
+    analyze_with_monitor(root, options, &CleaningMonitor::default())
+}
+
+/// Wie [`analyze`], meldet aber Fortschritt und bricht auf Anforderung ab
+pub fn analyze_with_monitor(root: &Path, options: &UsageOptions, monitor: &CleaningMonitor) -> DiskUsage {
+    let start_time = SystemTime::now();
+    monitor.start_location(&root.display().to_string());
+
+    let root_device = fs::metadata(root).ok().and_then(|metadata| device(&metadata));
+    let queue = WorkQueue::new([root.to_path_buf()]);
+    let results = run_workers(&queue, worker_count(options.worker_threads), |dir, queue, local: &mut WorkerUsage| {
+        if monitor.is_cancelled() {
+            queue.close();
+            return;
+        }
+        scan_directory(dir, root_device.filter(|_| options.same_filesystem), options.top_n, monitor, queue, local);
+    });
+
+    let mut records = HashMap::new();
+    let mut largest = BinaryHeap::new();
+    let mut errors = Vec::new();
+    for result in results {
+        records.extend(result.dirs.into_iter().map(|record| (record.path.clone(), record)));
+        for file in result.largest {
+            push_bounded(&mut largest, file.0, options.top_n);
+        }
+        errors.extend(result.errors);
+    }
+
+    let mut children: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+    for path in records.keys() {
+        if path != root {
+            if let Some(parent) = path.parent() {
+                children.entry(parent.to_path_buf()).or_default().push(path.clone());
+            }
+        }
+    }
+
+    let mut largest_files: Vec<LargeFile> = largest.into_iter().map(|Reverse(file)| file).collect();
+    largest_files.sort_by(|a, b| b.cmp(a));
+    monitor.finish();
+
+    DiskUsage {
+        root: build_node(root, &records, &mut children),
+        largest_files,
+        top_n: options.top_n,
+        errors,
+        scan_time: start_time.elapsed().unwrap_or_default(),
+        cancelled: monitor.is_cancelled(),
+    }
+}
+
+/// Dateien direkt in einem Verzeichnis
+struct DirRecord {
+    path: PathBuf,
+    file_size: u64,
+    file_count: usize,
+}
+
+#[derive(Default)]
+struct WorkerUsage {
+    dirs: Vec<DirRecord>,
+    largest: BinaryHeap<Reverse<LargeFile>>,
+    errors: Vec<CleaningError>,
+}
+
+fn scan_directory(
+    dir: PathBuf,
+    root_device: Option<u64>,
+    top_n: usize,
+    monitor: &CleaningMonitor,
+    queue: &WorkQueue<PathBuf>,
+    local: &mut WorkerUsage,
+) {
+    let mut record = DirRecord { path: dir, file_size: 0, file_count: 0 };
+    let entries = match fs::read_dir(&record.path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            local.errors.push(classify_io_error(&record.path, &e));
+            local.dirs.push(record);
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_symlink() {
+            continue;
+        }
+        let path = entry.path();
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                local.errors.push(classify_io_error(&path, &e));
+                continue;
+            }
+        };
+
+        if file_type.is_dir() {
+            if root_device.is_none() || device(&metadata) == root_device {
+                queue.push(path);
+            }
+        } else {
+            monitor.file_scanned(&path);
+            record.file_size += metadata.len();
+            record.file_count += 1;
+            push_bounded(&mut local.largest, LargeFile { size: metadata.len(), path }, top_n);
+        }
+    }
+    local.dirs.push(record);
+}
+
+/// Behält nur die `limit` größten Dateien (Min-Heap)
+fn push_bounded(heap: &mut BinaryHeap<Reverse<LargeFile>>, file: LargeFile, limit: usize) {
+    if limit == 0 {
+        return;
+    }
+    if heap.len() < limit {
+        heap.push(Reverse(file));
+    } else if heap.peek().is_some_and(|Reverse(smallest)| file > *smallest) {
+        heap.pop();
+        heap.push(Reverse(file));
+    }
+}
+
+fn build_node(path: &Path, records: &HashMap<PathBuf, DirRecord>, children: &mut HashMap<PathBuf, Vec<PathBuf>>) -> UsageNode {
+    let (file_size, own_files) = records.get(path).map(|r| (r.file_size, r.file_count)).unwrap_or_default();
+    let mut nodes: Vec<UsageNode> = children
+        .remove(path)
+        .unwrap_or_default()
+        .iter()
+        .map(|child| build_node(child, records, children))
+        .collect();
+    nodes.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+    UsageNode {
+        name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string()),
+        path: path.to_path_buf(),
+        size: file_size + nodes.iter().map(|node| node.size).sum::<u64>(),
+        file_size,
+        file_count: own_files + nodes.iter().map(|node| node.file_count).sum::<usize>(),
+        children: nodes,
+    }
+}
+
+#[cfg(unix)]
+fn device(metadata: &fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.dev())
+}
+
+#[cfg(not(unix))]
+fn device(_metadata: &fs::Metadata) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sizes_are_aggregated_per_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for (path, size) in [
+            ("a.bin", 100),
+            ("videos/film.mkv", 5000),
+            ("videos/alt/clip.mkv", 800),
+            ("videos/alt/clip2.mkv", 700),
+            ("docs/notiz.txt", 10),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, vec![0u8; size]).unwrap();
+        }
+        fs::create_dir_all(root.join("leer")).unwrap();
+
+        let usage = analyze(root, &UsageOptions { top_n: 2, ..Default::default() });
+
+        assert_eq!(usage.root.size, 6610);
+        assert_eq!(usage.root.file_count, 5);
+        assert_eq!(usage.root.file_size, 100);
+        let names: Vec<_> = usage.root.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["videos", "docs", "leer"]);
+
+        let alt = usage.root.find(&root.join("videos/alt")).unwrap();
+        assert_eq!(alt.size, 1500);
+        assert_eq!(usage.root.children[0].size, 6500);
+
+        let largest: Vec<_> = usage.largest_files.iter().map(|f| f.size).collect();
+        assert_eq!(largest, vec![5000, 800]);
+        let dirs: Vec<_> = usage.largest_dirs().iter().map(|d| d.name.clone()).collect();
+        assert_eq!(dirs, vec!["videos", "alt"]);
+    }
+}
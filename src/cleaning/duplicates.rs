@@ -0,0 +1,524 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::patterns::PatternSet;
+use super::plan::{classify_io_error, execute_with_monitor, CleaningPlan, MatchReason, PlannedFile};
+use super::progress::CleaningMonitor;
+use super::protection::ProtectedPaths;
+use super::temp_files::{CleaningError, CleaningOptions, CleaningSummary};
+use super::walker::{run_workers, worker_count, WorkQueue};
+
+/// Name des Orts, unter dem Duplikate in Plan und Zusammenfassung erscheinen
+pub const DUPLICATES_LOCATION: &str = "Duplikate";
+
+/// Anzahl Bytes am Dateianfang, die vor dem vollständigen Vergleich gehasht werden
+const PARTIAL_HASH_BYTES: u64 = 16 * 1024;
+
+/// Regel, welche Datei einer Gruppe behalten wird
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum KeeperRule {
+    /// Die zuletzt geänderte Datei
+    #[default]
+    Newest,
+    /// Eine Datei unter dem ersten passenden Verzeichnis der Liste (dort die neueste),
+    /// ohne Treffer die neueste
+    PreferPaths(Vec<PathBuf>),
+}
+
+/// Optionen der Duplikatsuche
+#[derive(Debug, Clone)]
+pub struct DuplicateOptions {
+    /// Zu durchsuchende Verzeichnisse (rekursiv)
+    pub roots: Vec<PathBuf>,
+    /// Kleinere Dateien werden ignoriert (Standard 1, also keine leeren Dateien)
+    pub min_file_size: u64,
+    /// Ausgeschlossene Dateien relativ zur jeweiligen Wurzel, siehe [`PatternSet`]
+    pub excluded_patterns: Vec<String>,
+    pub keeper: KeeperRule,
+    /// Anzahl paralleler Worker für Suche und Hashing (0 = automatisch)
+    pub worker_threads: usize,
+}
+
+impl Default for DuplicateOptions {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            min_file_size: 1,
+            excluded_patterns: Vec::new(),
+            keeper: KeeperRule::default(),
+            worker_threads: 0,
+        }
+    }
+}
+
+/// Eine Datei innerhalb einer Duplikatgruppe
+#[derive(Debug, Clone)]
+pub struct DuplicateFile {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+}
+
+/// Dateien mit identischem Inhalt
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    /// Größe jeder einzelnen Datei
+    pub size: u64,
+    /// BLAKE3-Hash des Inhalts (hexadezimal)
+    pub hash: String,
+    /// Nach Pfad sortiert
+    pub files: Vec<DuplicateFile>,
+    /// Index der vorgeschlagenen zu behaltenden Datei in `files`
+    pub keeper: usize,
+}
+
+impl DuplicateGroup {
+    pub fn keeper(&self) -> &DuplicateFile {
+        &self.files[self.keeper]
+    }
+
+    /// Alle Dateien außer der zu behaltenden
+    pub fn redundant(&self) -> impl Iterator<Item = &DuplicateFile> {
+        self.files.iter().enumerate().filter(move |(i, _)| *i != self.keeper).map(|(_, file)| file)
+    }
+
+    /// Speicher, der durch Entfernen der überzähligen Kopien frei würde
+    pub fn wasted_bytes(&self) -> u64 {
+        self.size * (self.files.len() as u64 - 1)
+    }
+}
+
+/// Ergebnis einer Duplikatsuche
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateReport {
+    /// Größte Verschwendung zuerst
+    pub groups: Vec<DuplicateGroup>,
+    pub errors: Vec<CleaningError>,
+    pub files_scanned: usize,
+    pub scan_time: Duration,
+    /// Suche wurde abgebrochen; `groups` ist dann leer
+    pub cancelled: bool,
+}
+
+impl DuplicateReport {
+    /// Anzahl überzähliger Kopien über alle Gruppen
+    pub fn redundant_files(&self) -> usize {
+        self.groups.iter().map(|group| group.files.len() - 1).sum()
+    }
+
+    pub fn wasted_bytes(&self) -> u64 {
+        self.groups.iter().map(DuplicateGroup::wasted_bytes).sum()
+    }
+
+    /// Plan, der alle Dateien außer den zu behaltenden entfernt
+    ///
+    /// Der Ort hat keine Wurzeln, damit beim Ausführen keine Verzeichnisse entfernt werden.
+    pub fn to_plan(&self) -> CleaningPlan {
+        let mut plan = CleaningPlan::new();
+        plan.location_mut(DUPLICATES_LOCATION);
+        plan.scan_time = self.scan_time;
+        plan.cancelled = self.cancelled;
+        for error in &self.errors {
+            plan.add_error(DUPLICATES_LOCATION, error.clone());
+        }
+
+        for group in &self.groups {
+            for file in group.redundant() {
+                plan.push(PlannedFile {
+                    location: DUPLICATES_LOCATION.to_string(),
+                    path: file.path.clone(),
+                    size: group.size,
+                    modified: file.modified,
+                    reason: MatchReason::Duplicate(group.keeper().path.clone()),
+                    companion: None,
+                });
+            }
+        }
+        plan
+    }
+}
+
+/// Sucht Dateien mit identischem Inhalt unter `options.roots`
+pub fn find_duplicates(options: &DuplicateOptions) -> DuplicateReport {
+    find_duplicates_with_monitor(options, &CleaningMonitor::default())
+}
+
+/// Wie [`find_duplicates`], meldet aber Fortschritt und bricht auf Anforderung ab
+///
+/// Verglichen wird in drei Stufen: Größe, Hash der ersten 16 KiB und Hash des gesamten Inhalts.
+/// Nur Dateien, die in einer Stufe noch Partner haben, werden in der nächsten gelesen.
+pub fn find_duplicates_with_monitor(options: &DuplicateOptions, monitor: &CleaningMonitor) -> DuplicateReport {
+    let start_time = SystemTime::now();
+    let mut report = DuplicateReport::default();
+    monitor.start_location(DUPLICATES_LOCATION);
+
+    let excluded = match PatternSet::new(&options.excluded_patterns) {
+        Ok(excluded) => excluded,
+        Err(error) => {
+            report.errors.push(error.into());
+            return report;
+        }
+    };
+
+    let candidates = collect_candidates(options, &excluded, monitor, &mut report);
+
+    let mut by_size: HashMap<u64, Vec<Candidate>> = HashMap::new();
+    for candidate in candidates {
+        by_size.entry(candidate.size).or_default().push(candidate);
+    }
+    let groups: Vec<_> = by_size.into_values().filter(|files| files.len() > 1).collect();
+
+    // Bei kleinen Dateien deckt der Teil-Hash bereits den ganzen Inhalt ab
+    let (mut finished, partial): (Vec<_>, Vec<_>) = refine(groups, Some(PARTIAL_HASH_BYTES), options, monitor, &mut report.errors)
+        .into_iter()
+        .partition(|files| files[0].size <= PARTIAL_HASH_BYTES);
+    finished.extend(refine(partial, None, options, monitor, &mut report.errors));
+
+    if monitor.is_cancelled() {
+        report.cancelled = true;
+    } else {
+        report.groups = finished.into_iter().map(|files| into_group(files, &options.keeper)).collect();
+        report.groups.sort_by(|a, b| {
+            b.wasted_bytes().cmp(&a.wasted_bytes()).then_with(|| a.files[0].path.cmp(&b.files[0].path))
+        });
+    }
+
+    if let Ok(elapsed) = start_time.elapsed() {
+        report.scan_time = elapsed;
+    }
+    monitor.finish();
+    report
+}
+
+/// Entfernt die überzähligen Kopien über die normale Lösch-, Quarantäne- bzw. Papierkorb-Pipeline
+///
+/// Kopien, deren zu behaltende Datei inzwischen fehlt, werden übersprungen, damit nie
+/// alle Exemplare eines Inhalts verschwinden.
+pub fn clean_duplicates(report: &DuplicateReport, options: &CleaningOptions, monitor: &CleaningMonitor) -> CleaningSummary {
+    let mut plan = report.to_plan();
+    let before = plan.file_count();
+    plan.retain(|entry| match &entry.reason {
+        MatchReason::Duplicate(keeper) => keeper.is_file(),
+        _ => true,
+    });
+    let orphaned = before - plan.file_count();
+    plan.add_skipped(DUPLICATES_LOCATION, orphaned);
+
+    execute_with_monitor(&plan, options, monitor)
+}
+
+#[derive(Debug)]
+struct Candidate {
+    path: PathBuf,
+    size: u64,
+    modified: Option<SystemTime>,
+    /// Hash der zuletzt durchlaufenen Stufe
+    hash: Option<blake3::Hash>,
+}
+
+/// Zu durchsuchendes Verzeichnis samt Pfad relativ zur Wurzel
+struct SearchDir {
+    path: PathBuf,
+    relative: PathBuf,
+}
+
+#[derive(Default)]
+struct WorkerSearch {
+    candidates: Vec<(Option<(u64, u64)>, Candidate)>,
+    scanned: usize,
+    errors: Vec<CleaningError>,
+}
+
+#[derive(Default)]
+struct WorkerHash {
+    hashed: Vec<(usize, Candidate)>,
+    errors: Vec<CleaningError>,
+}
+
+/// Sammelt alle regulären Dateien ab der Mindestgröße; Hardlinks auf dieselbe Datei zählen einmal
+fn collect_candidates(
+    options: &DuplicateOptions,
+    excluded: &PatternSet,
+    monitor: &CleaningMonitor,
+    report: &mut DuplicateReport,
+) -> Vec<Candidate> {
+    let protection = ProtectedPaths::global();
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for root in &options.roots {
+        if !root.is_dir() {
+            report.errors.push(CleaningError::DirectoryNotFound(root.clone()));
+            continue;
+        }
+        let checked = protection.check_dir(root).and_then(|()| match fs::canonicalize(root) {
+            Ok(resolved) => protection.check_dir(&resolved),
+            Err(_) => Ok(()),
+        });
+        if let Err(error) = checked {
+            report.errors.push(error);
+            continue;
+        }
+        // Verschachtelte oder doppelte Wurzeln nur einmal durchsuchen
+        if roots.iter().any(|existing| root.starts_with(existing)) {
+            continue;
+        }
+        roots.retain(|existing| !existing.starts_with(root));
+        roots.push(root.clone());
+    }
+
+    let queue = WorkQueue::new(roots.into_iter().map(|path| SearchDir { path, relative: PathBuf::new() }));
+    let results = run_workers(&queue, worker_count(options.worker_threads), |dir, queue, local: &mut WorkerSearch| {
+        if monitor.is_cancelled() {
+            queue.close();
+            return;
+        }
+        search_directory(&dir, options, excluded, protection, monitor, queue, local);
+    });
+
+    let mut seen_inodes = HashSet::new();
+    let mut candidates = Vec::new();
+    for result in results {
+        report.files_scanned += result.scanned;
+        report.errors.extend(result.errors);
+        for (inode, candidate) in result.candidates {
+            if inode.is_none_or(|inode| seen_inodes.insert(inode)) {
+                candidates.push(candidate);
+            }
+        }
+    }
+    candidates
+}
+
+fn search_directory(
+    dir: &SearchDir,
+    options: &DuplicateOptions,
+    excluded: &PatternSet,
+    protection: &ProtectedPaths,
+    monitor: &CleaningMonitor,
+    queue: &WorkQueue<SearchDir>,
+    local: &mut WorkerSearch,
+) {
+    let entries = match fs::read_dir(&dir.path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            local.errors.push(classify_io_error(&dir.path, &e));
+            return;
+        }
+    };
+
+    for entry in entries {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        let relative = dir.relative.join(entry.file_name());
+
+        // Versteckte Dateien und Verzeichnisse (z.B. `.git`) sowie Symlinks auslassen
+        if entry.file_name().to_string_lossy().starts_with('.') || file_type.is_symlink() {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            match protection.check_dir(&path) {
+                Ok(()) => queue.push(SearchDir { path, relative }),
+                Err(error) => local.errors.push(error),
+            }
+        } else if file_type.is_file() {
+            local.scanned += 1;
+            monitor.file_scanned(&path);
+            if excluded.is_match(&relative) || protection.check(&path).is_err() {
+                continue;
+            }
+            match entry.metadata() {
+                Ok(metadata) if metadata.len() >= options.min_file_size.max(1) => local.candidates.push((
+                    inode(&metadata),
+                    Candidate {
+                        path,
+                        size: metadata.len(),
+                        modified: metadata.modified().ok(),
+                        hash: None,
+                    },
+                )),
+                Ok(_) => {}
+                Err(e) => local.errors.push(classify_io_error(&path, &e)),
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn inode(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn inode(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Teilt jede Gruppe nach dem Hash der ersten `limit` Bytes (bzw. des ganzen Inhalts) weiter auf
+/// und verwirft Dateien ohne Partner
+fn refine(
+    groups: Vec<Vec<Candidate>>,
+    limit: Option<u64>,
+    options: &DuplicateOptions,
+    monitor: &CleaningMonitor,
+    errors: &mut Vec<CleaningError>,
+) -> Vec<Vec<Candidate>> {
+    let queue = WorkQueue::new(
+        groups
+            .into_iter()
+            .enumerate()
+            .flat_map(|(group, files)| files.into_iter().map(move |candidate| (group, candidate))),
+    );
+    let results = run_workers(&queue, worker_count(options.worker_threads), |(group, mut candidate), queue, local: &mut WorkerHash| {
+        if monitor.is_cancelled() {
+            queue.close();
+            return;
+        }
+        match hash_file(&candidate.path, limit) {
+            Ok(hash) => {
+                candidate.hash = Some(hash);
+                local.hashed.push((group, candidate));
+            }
+            Err(e) => local.errors.push(classify_io_error(&candidate.path, &e)),
+        }
+    });
+
+    let mut refined: BTreeMap<(usize, [u8; 32]), Vec<Candidate>> = BTreeMap::new();
+    for result in results {
+        errors.extend(result.errors);
+        for (group, candidate) in result.hashed {
+            let hash = candidate.hash.map(|hash| *hash.as_bytes()).unwrap_or_default();
+            refined.entry((group, hash)).or_default().push(candidate);
+        }
+    }
+    refined.into_values().filter(|files| files.len() > 1).collect()
+}
+
+fn hash_file(path: &Path, limit: Option<u64>) -> io::Result<blake3::Hash> {
+    let file = File::open(path)?;
+    let mut hasher = blake3::Hasher::new();
+    match limit {
+        Some(limit) => io::copy(&mut file.take(limit), &mut hasher)?,
+        None => io::copy(&mut io::BufReader::new(file), &mut hasher)?,
+    };
+    Ok(hasher.finalize())
+}
+
+fn into_group(mut files: Vec<Candidate>, rule: &KeeperRule) -> DuplicateGroup {
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    let size = files[0].size;
+    let hash = files[0].hash.map(|hash| hash.to_hex().to_string()).unwrap_or_default();
+    let files: Vec<_> = files
+        .into_iter()
+        .map(|candidate| DuplicateFile {
+            path: candidate.path,
+            modified: candidate.modified,
+        })
+        .collect();
+    let keeper = suggest_keeper(&files, rule);
+    DuplicateGroup { size, hash, files, keeper }
+}
+
+/// Index der zu behaltenden Datei; bei gleichem Änderungsdatum gewinnt der kleinere Pfad
+fn suggest_keeper(files: &[DuplicateFile], rule: &KeeperRule) -> usize {
+    let newest = |indices: &mut dyn Iterator<Item = usize>| {
+        indices.fold(None, |best: Option<usize>, i| match best {
+            Some(b) if files[b].modified >= files[i].modified => Some(b),
+            _ => Some(i),
+        })
+    };
+
+    if let KeeperRule::PreferPaths(preferred) = rule {
+        for dir in preferred {
+            if let Some(index) = newest(&mut (0..files.len()).filter(|&i| files[i].path.starts_with(dir))) {
+                return index;
+            }
+        }
+    }
+    newest(&mut (0..files.len())).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cleaning::DeletionMode;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &[u8], age_secs: u64) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+        let file = File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(age_secs)).unwrap();
+    }
+
+    #[test]
+    fn test_groups_by_size_partial_and_full_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        write(&root.join("downloads/bild.png"), b"gleicher inhalt", 300);
+        write(&root.join("downloads/bild (1).png"), b"gleicher inhalt", 100);
+        write(&root.join("archiv/bild.png"), b"gleicher inhalt", 200);
+        // Gleiche Größe, anderer Inhalt
+        write(&root.join("downloads/anders.png"), b"anderer inhalt!", 0);
+        // Gleicher Anfang über den Teil-Hash hinaus, aber anderes Ende
+        let mut large = vec![7u8; PARTIAL_HASH_BYTES as usize + 10];
+        write(&root.join("build/a.bin"), &large, 0);
+        *large.last_mut().unwrap() = 8;
+        write(&root.join("build/b.bin"), &large, 0);
+        write(&root.join("downloads/leer.txt"), b"", 0);
+        write(&root.join("archiv/leer.txt"), b"", 0);
+
+        let options = DuplicateOptions { roots: vec![root.to_path_buf()], ..Default::default() };
+        let report = find_duplicates(&options);
+
+        assert_eq!(report.groups.len(), 1);
+        let group = &report.groups[0];
+        assert_eq!(group.files.len(), 3);
+        assert_eq!(group.wasted_bytes(), 2 * 15);
+        assert_eq!(group.keeper().path, root.join("downloads/bild (1).png"));
+
+        let preferred = DuplicateOptions {
+            keeper: KeeperRule::PreferPaths(vec![root.join("archiv")]),
+            ..options
+        };
+        let report = find_duplicates(&preferred);
+        assert_eq!(report.groups[0].keeper().path, root.join("archiv/bild.png"));
+        assert_eq!(report.redundant_files(), 2);
+    }
+
+    #[test]
+    fn test_clean_duplicates_keeps_one_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        write(&root.join("a/kopie.zip"), b"archiv", 50);
+        write(&root.join("b/kopie.zip"), b"archiv", 10);
+        write(&root.join("c/x.zip"), b"anders", 0);
+        write(&root.join("c/y.zip"), b"anders", 5);
+
+        let report = find_duplicates(&DuplicateOptions { roots: vec![root.to_path_buf()], ..Default::default() });
+        assert_eq!(report.groups.len(), 2);
+
+        // Fehlt die zu behaltende Datei inzwischen, bleibt die Kopie unangetastet
+        fs::remove_file(root.join("c/x.zip")).unwrap();
+
+        let options = CleaningOptions {
+            deletion_mode: DeletionMode::Delete,
+            ..Default::default()
+        };
+        let summary = clean_duplicates(&report, &options, &CleaningMonitor::default());
+
+        assert_eq!(summary.deleted_files, 1);
+        assert_eq!(summary.skipped_files, 1);
+        assert!(!root.join("a/kopie.zip").exists());
+        assert!(root.join("b/kopie.zip").exists());
+        assert!(root.join("c/y.zip").exists());
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::temp_files::CleaningSummary;
+
+/// Dateiname des Verlaufs im Datenverzeichnis der Anwendung
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+/// Ergebnis eines Ortes innerhalb eines Laufs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocationRecord {
+    pub name: String,
+    pub files: usize,
+    pub bytes_freed: u64,
+    pub errors: usize,
+}
+
+/// Ein Bereinigungslauf im Verlauf
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Ende des Laufs
+    pub timestamp: DateTime<Utc>,
+    /// Verwendetes Profil (`None` bei freier Zielauswahl)
+    pub profile: Option<String>,
+    pub deleted_files: usize,
+    pub bytes_freed: u64,
+    pub errors: usize,
+    pub duration: Duration,
+    pub cancelled: bool,
+    /// Nach Namen sortiert
+    pub locations: Vec<LocationRecord>,
+}
+
+impl HistoryEntry {
+    pub fn from_summary(summary: &CleaningSummary, profile: Option<&str>, timestamp: DateTime<Utc>) -> Self {
+        let mut locations: Vec<_> = summary
+            .cleaned_locations
+            .values()
+            .map(|location| LocationRecord {
+                name: location.location_name.clone(),
+                files: location.deleted_files,
+                bytes_freed: location.total_size,
+                errors: location.errors,
+            })
+            .collect();
+        locations.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            timestamp,
+            profile: profile.map(String::from),
+            deleted_files: summary.deleted_files,
+            bytes_freed: summary.total_size,
+            errors: summary.errors.len(),
+            duration: summary.processing_time,
+            cancelled: summary.cancelled,
+            locations,
+        }
+    }
+}
+
+/// Verlauf aller Läufe als JSON Lines (eine Zeile pro Lauf, nur angehängt)
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Verlauf im Datenverzeichnis der Anwendung
+    pub fn open_default() -> io::Result<Self> {
+        super::targets::app_data_dir()
+            .map(|data| Self::new(data.join(HISTORY_FILE)))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Kein Datenverzeichnis ermittelbar"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Hängt einen Lauf an; legt Datei und Verzeichnis bei Bedarf an
+    pub fn append(&self, entry: &HistoryEntry) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut line = serde_json::to_string(entry).map_err(io::Error::other)?;
+        line.push('\n');
+
+        // Eine Zeile in einem Aufruf schreiben, damit parallele Prozesse sich nicht vermischen
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(line.as_bytes())
+    }
+
+    /// Alle Läufe in der gespeicherten Reihenfolge; unlesbare Zeilen werden übersprungen
+    pub fn load(&self) -> io::Result<Vec<HistoryEntry>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if let Ok(entry) = serde_json::from_str(&line) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Läufe mit `from <= timestamp < to`
+    pub fn query(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> io::Result<Vec<HistoryEntry>> {
+        let mut entries = self.load()?;
+        entries.retain(|entry| entry.timestamp >= from && entry.timestamp < to);
+        Ok(entries)
+    }
+}
+
+/// Freigegebener Speicher je Kalenderwoche (Montag in lokaler Zeit), aufsteigend
+pub fn weekly_totals(entries: &[HistoryEntry]) -> Vec<(NaiveDate, u64)> {
+    let mut weeks: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+    for entry in entries {
+        *weeks.entry(week_start(entry.timestamp)).or_default() += entry.bytes_freed;
+    }
+    weeks.into_iter().collect()
+}
+
+/// Freigegebener Speicher je Ort, größter zuerst
+pub fn location_totals(entries: &[HistoryEntry]) -> Vec<(String, u64)> {
+    let mut locations: BTreeMap<&str, u64> = BTreeMap::new();
+    for location in entries.iter().flat_map(|entry| &entry.locations) {
+        *locations.entry(&location.name).or_default() += location.bytes_freed;
+    }
+
+    let mut totals: Vec<_> = locations.into_iter().map(|(name, bytes)| (name.to_string(), bytes)).collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    totals
+}
+
+/// Montag der Woche, in die der Zeitpunkt in lokaler Zeit fällt
+pub fn week_start(timestamp: DateTime<Utc>) -> NaiveDate {
+    let date = timestamp.with_timezone(&Local).date_naive();
+    date - Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn entry(day: u32, hour: u32, locations: &[(&str, u64)]) -> HistoryEntry {
+        let mut summary = CleaningSummary::new();
+        for (name, bytes) in locations {
+            summary.add_location_data(name, 1, *bytes, 0, 0);
+            summary.total_size += bytes;
+        }
+        let timestamp = Local.with_ymd_and_hms(2025, 3, day, hour, 0, 0).unwrap().with_timezone(&Utc);
+        HistoryEntry::from_summary(&summary, Some("quick"), timestamp)
+    }
+
+    #[test]
+    fn test_append_load_and_query() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = HistoryStore::new(temp_dir.path().join("daten").join(HISTORY_FILE));
+        assert!(store.load().unwrap().is_empty());
+
+        for day in [3, 5, 12] {
+            store.append(&entry(day, 12, &[("npm Cache", 100)])).unwrap();
+        }
+        // Beschädigte Zeilen dürfen den Verlauf nicht unlesbar machen
+        fs::OpenOptions::new().append(true).open(store.path()).unwrap().write_all(b"{kaputt\n").unwrap();
+
+        let all = store.load().unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].profile.as_deref(), Some("quick"));
+
+        let from = Local.with_ymd_and_hms(2025, 3, 4, 0, 0, 0).unwrap().with_timezone(&Utc);
+        let to = Local.with_ymd_and_hms(2025, 3, 12, 0, 0, 0).unwrap().with_timezone(&Utc);
+        let range = store.query(from, to).unwrap();
+        assert_eq!(range.len(), 1);
+        assert_eq!(range[0].timestamp, entry(5, 12, &[]).timestamp);
+    }
+
+    #[test]
+    fn test_weekly_and_location_totals() {
+        // 3. März 2025 ist ein Montag
+        let entries = vec![
+            entry(3, 8, &[("npm Cache", 100), ("Papierkorb", 50)]),
+            entry(9, 22, &[("npm Cache", 10)]),
+            entry(10, 1, &[("Papierkorb", 500)]),
+        ];
+
+        let monday = |day| NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        assert_eq!(weekly_totals(&entries), vec![(monday(3), 160), (monday(10), 500)]);
+        assert_eq!(
+            location_totals(&entries),
+            vec![("Papierkorb".to_string(), 550), ("npm Cache".to_string(), 110)]
+        );
+    }
+}
@@ -1,3 +1,25 @@
+pub mod disk_usage;
+pub mod duplicates;
+pub mod history;
+pub mod patterns;
+pub mod plan;
+pub mod progress;
+pub mod protection;
+pub mod quarantine;
+pub mod report;
+pub mod targets;
 pub mod temp_files;
+pub mod trash;
+mod walker;
 
-pub use temp_files::*; 
\ No newline at end of file
+pub use disk_usage::{DiskUsage, LargeFile, UsageNode, UsageOptions};
+pub use duplicates::{clean_duplicates, find_duplicates, find_duplicates_with_monitor, DuplicateGroup, DuplicateOptions, DuplicateReport, KeeperRule};
+pub use history::{HistoryEntry, HistoryStore};
+pub use patterns::{PatternError, PatternSet};
+pub use plan::*;
+pub use progress::*;
+pub use protection::ProtectedPaths;
+pub use quarantine::{restore, QuarantineStore};
+pub use targets::{CleanTarget, PathTarget, TargetCategory, TargetRegistry};
+pub use temp_files::*;
+pub use trash::Trash;
@@ -0,0 +1,188 @@
+use std::path::{Component, Path};
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use regex::RegexSet;
+
+/// Präfix für reguläre Ausdrücke in Musterlisten
+pub const REGEX_PREFIX: &str = "regex:";
+
+/// Ungültiges Glob- oder Regex-Muster
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    pub pattern: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Ungültiges Muster \"{}\": {}", self.pattern, self.message)
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// Einmal übersetzte Liste von Mustern
+///
+/// - Glob ohne `/` (`*.log`, `data_?`, `[a-f]*.tmp`) prüft nur den Dateinamen.
+/// - Glob mit `/` (`**/Cache/index`, `Code Cache/js/*.tmp`) prüft den Pfad relativ zum
+///   durchsuchten Wurzelverzeichnis; `*` und `?` überspringen dabei kein `/`, `**` schon.
+/// - `regex:<Ausdruck>` sucht im relativen Pfad (Trenner immer `/`).
+///
+/// Globs unterscheiden nicht zwischen Groß- und Kleinschreibung, reguläre Ausdrücke nur mit `(?i)`.
+#[derive(Debug, Clone)]
+pub struct PatternSet {
+    names: GlobSet,
+    paths: GlobSet,
+    regexes: RegexSet,
+}
+
+impl Default for PatternSet {
+    fn default() -> Self {
+        Self::new::<&str>(&[]).expect("leere Musterliste ist gültig")
+    }
+}
+
+impl PatternSet {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Result<Self, PatternError> {
+        let mut names = GlobSetBuilder::new();
+        let mut paths = GlobSetBuilder::new();
+        let mut regexes = Vec::new();
+
+        for pattern in patterns {
+            match Rule::parse(pattern.as_ref())? {
+                Rule::Name(glob) => {
+                    names.add(glob);
+                }
+                Rule::Path(glob) => {
+                    paths.add(glob);
+                }
+                Rule::Regex(expression) => regexes.push(expression),
+            }
+        }
+
+        let build_error = |e: globset::Error| PatternError {
+            pattern: e.glob().unwrap_or_default().to_string(),
+            message: e.kind().to_string(),
+        };
+        Ok(Self {
+            names: names.build().map_err(build_error)?,
+            paths: paths.build().map_err(build_error)?,
+            regexes: RegexSet::new(&regexes).map_err(|e| PatternError {
+                pattern: regexes.join(", "),
+                message: e.to_string(),
+            })?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty() && self.paths.is_empty() && self.regexes.is_empty()
+    }
+
+    /// Prüft einen Pfad relativ zum Wurzelverzeichnis des Ziels
+    pub fn is_match(&self, relative: &Path) -> bool {
+        if let Some(name) = relative.file_name() {
+            if self.names.is_match(Path::new(name)) {
+                return true;
+            }
+        }
+        if self.paths.is_empty() && self.regexes.is_empty() {
+            return false;
+        }
+
+        let relative = slash_path(relative);
+        self.paths.is_match(&relative) || self.regexes.is_match(&relative)
+    }
+}
+
+/// Prüft ein einzelnes Muster, z.B. beim Einlesen von Konfiguration oder Kommandozeile
+pub fn validate(pattern: &str) -> Result<(), PatternError> {
+    Rule::parse(pattern).map(|_| ())
+}
+
+enum Rule {
+    Name(globset::Glob),
+    Path(globset::Glob),
+    Regex(String),
+}
+
+impl Rule {
+    fn parse(pattern: &str) -> Result<Self, PatternError> {
+        let error = |message: String| PatternError {
+            pattern: pattern.to_string(),
+            message,
+        };
+
+        if let Some(expression) = pattern.strip_prefix(REGEX_PREFIX) {
+            // Einzeln prüfen, damit die Fehlermeldung das betroffene Muster nennt
+            regex::Regex::new(expression).map_err(|e| error(e.to_string()))?;
+            return Ok(Rule::Regex(expression.to_string()));
+        }
+
+        let glob = GlobBuilder::new(pattern.trim_start_matches('/'))
+            .case_insensitive(true)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| error(e.kind().to_string()))?;
+        Ok(if pattern.contains('/') { Rule::Path(glob) } else { Rule::Name(glob) })
+    }
+}
+
+/// Relativer Pfad mit `/` als Trenner, unabhängig von der Plattform
+fn slash_path(path: &Path) -> String {
+    let mut result = String::new();
+    for component in path.components() {
+        if let Component::Normal(part) = component {
+            if !result.is_empty() {
+                result.push('/');
+            }
+            result.push_str(&part.to_string_lossy());
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, relative: &str) -> bool {
+        PatternSet::new(&[pattern]).unwrap().is_match(Path::new(relative))
+    }
+
+    #[test]
+    fn test_name_globs() {
+        assert!(matches("*.txt", "test.txt"));
+        assert!(matches("thumbcache_*.db", "thumbcache_1024.db"));
+        assert!(!matches("*.txt", "test.log"));
+        assert!(matches("test.txt", "TEST.TXT"));
+        assert!(matches("*.txt", "tief/im/baum/test.txt"));
+        assert!(matches("data_?", "Cache/data_3"));
+        assert!(!matches("data_?", "data_12"));
+        assert!(matches("[a-c]*.tmp", "b1.tmp"));
+        assert!(!matches("[a-c]*.tmp", "d1.tmp"));
+        assert!(matches("*.{log,tmp}", "x.tmp"));
+    }
+
+    #[test]
+    fn test_path_globs_are_relative_to_root() {
+        assert!(matches("**/Cache/index", "Default/Cache/index"));
+        assert!(matches("**/Cache/index", "Cache/index"));
+        assert!(!matches("**/Cache/index", "Default/Cache/index2"));
+        assert!(matches("Code Cache/js/*.tmp", "Code Cache/js/a.tmp"));
+        assert!(!matches("Code Cache/js/*.tmp", "Code Cache/js/sub/a.tmp"));
+        assert!(!matches("Code Cache/js/*.tmp", "Profil/Code Cache/js/a.tmp"));
+        assert!(matches("Code Cache/**/*.tmp", "Code Cache/js/sub/a.tmp"));
+    }
+
+    #[test]
+    fn test_regex_patterns() {
+        assert!(matches(r"regex:^Default/.*\.ldb$", "Default/x/000003.ldb"));
+        assert!(!matches(r"regex:^Default/.*\.ldb$", "Other/000003.ldb"));
+        assert!(matches(r"regex:(?i)journal", "db-JOURNAL"));
+
+        let error = PatternSet::new(&["*.tmp", "regex:(offen"]).unwrap_err();
+        assert_eq!(error.pattern, "regex:(offen");
+        assert!(validate("[a-").is_err());
+        assert!(PatternSet::default().is_empty());
+    }
+}
@@ -0,0 +1,629 @@
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+#[cfg(windows)]
+use windows::Win32::Foundation::{ERROR_SHARING_VIOLATION, ERROR_LOCK_VIOLATION};
+
+use super::progress::CleaningMonitor;
+use super::protection::ProtectedPaths;
+use super::quarantine::{QuarantineRun, QuarantineStore, MAX_RETENTION_DAYS};
+use super::trash::Trash;
+use super::temp_files::{CleaningError, CleaningOptions, CleaningSummary, DeletionMode, FileOutcome, FileRecord};
+use super::walker::{run_workers, worker_count, WorkQueue};
+
+/// Grund, warum eine Datei in den Bereinigungsplan aufgenommen wurde
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchReason {
+    /// Datei ist älter als die angegebene Anzahl Tage
+    OlderThan(u64),
+    /// Datei hat eine der gewünschten Dateiendungen
+    Extension(String),
+    /// Inhaltsgleiche Kopie der angegebenen, zu behaltenden Datei
+    Duplicate(PathBuf),
+}
+
+impl std::fmt::Display for MatchReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            MatchReason::OlderThan(days) => write!(f, "Älter als {} Tag(e)", days),
+            MatchReason::Extension(ext) => write!(f, "Dateiendung .{}", ext),
+            MatchReason::Duplicate(keeper) => write!(f, "Duplikat von {}", keeper.display()),
+        }
+    }
+}
+
+/// Eine einzelne zur Löschung vorgesehene Datei
+///
+/// Einträge mit Begleitdatei (Papierkorb) können auch ganze Verzeichnisse sein; `size` ist dann
+/// deren Gesamtgröße.
+#[derive(Debug, Clone)]
+pub struct PlannedFile {
+    pub location: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub reason: MatchReason,
+    /// Datei, die nur zusammen mit dem Eintrag und erst nach ihm entfernt wird (z.B. `.trashinfo`)
+    pub companion: Option<PathBuf>,
+}
+
+/// Scan-Ergebnis eines Bereinigungsortes
+#[derive(Debug, Clone)]
+pub struct PlannedLocation {
+    pub name: String,
+    /// Durchsuchte Wurzelverzeichnisse (für das Entfernen leerer Verzeichnisse)
+    pub roots: Vec<PathBuf>,
+    pub skipped_files: usize,
+    pub errors: usize,
+}
+
+/// Vollständiger Bereinigungsplan: alles, was gelöscht würde, bevor etwas gelöscht wird
+#[derive(Debug, Clone, Default)]
+pub struct CleaningPlan {
+    pub entries: Vec<PlannedFile>,
+    pub locations: Vec<PlannedLocation>,
+    pub errors: Vec<CleaningError>,
+    pub scan_time: Duration,
+    /// Scan wurde abgebrochen; der Plan ist unvollständig
+    pub cancelled: bool,
+    /// Bereits erfasste Pfade, damit überlappende Ziele keine Datei doppelt aufnehmen
+    claimed: HashSet<PathBuf>,
+}
+
+impl CleaningPlan {
+    /// Erstellt einen leeren Plan
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Anzahl der geplanten Dateien
+    pub fn file_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Gesamtgröße aller geplanten Dateien in Bytes
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|e| e.size).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Geplante Dateien eines bestimmten Ortes
+    pub fn entries_for<'a>(&'a self, location: &'a str) -> impl Iterator<Item = &'a PlannedFile> + 'a {
+        self.entries.iter().filter(move |e| e.location == location)
+    }
+
+    /// Fügt eine Datei hinzu, sofern sie noch nicht erfasst wurde
+    pub fn push(&mut self, entry: PlannedFile) -> bool {
+        if !self.claimed.insert(entry.path.clone()) {
+            return false;
+        }
+        self.entries.push(entry);
+        true
+    }
+
+    /// Prüft, ob ein Pfad bereits beim Scan erfasst wurde
+    pub fn contains(&self, path: &Path) -> bool {
+        self.claimed.contains(path)
+    }
+
+    /// Entfernt alle Einträge, für die `keep` false liefert
+    pub fn retain<F: FnMut(&PlannedFile) -> bool>(&mut self, keep: F) {
+        self.entries.retain(keep);
+    }
+
+    /// Liefert (oder legt an) den Scan-Eintrag für einen Ort
+    pub(crate) fn location_mut(&mut self, name: &str) -> &mut PlannedLocation {
+        let index = match self.locations.iter().position(|l| l.name == name) {
+            Some(index) => index,
+            None => {
+                self.locations.push(PlannedLocation {
+                    name: name.to_string(),
+                    roots: Vec::new(),
+                    skipped_files: 0,
+                    errors: 0,
+                });
+                self.locations.len() - 1
+            }
+        };
+        &mut self.locations[index]
+    }
+
+    /// Zählt übersprungene Dateien für einen Ort
+    pub(crate) fn add_skipped(&mut self, location: &str, count: usize) {
+        self.location_mut(location).skipped_files += count;
+    }
+
+    /// Fügt einen Scan-Fehler für einen Ort hinzu
+    pub(crate) fn add_error(&mut self, location: &str, error: CleaningError) {
+        self.location_mut(location).errors += 1;
+        self.errors.push(error);
+    }
+
+    /// Zusammenfassung ohne Löschung (Dry Run): zählt jede geplante Datei als gelöscht
+    pub fn to_summary(&self) -> CleaningSummary {
+        let mut summary = CleaningSummary::new();
+        summary.errors = self.errors.clone();
+        summary.processing_time = self.scan_time;
+
+        for location in &self.locations {
+            let (files, size) = self
+                .entries_for(&location.name)
+                .fold((0, 0), |(n, s), e| (n + 1, s + e.size));
+
+            summary.deleted_files += files;
+            summary.total_size += size;
+            summary.skipped_files += location.skipped_files;
+
+            if files > 0 || location.errors > 0 || location.skipped_files > 0 {
+                summary.add_location_data(&location.name, files, size, location.errors, location.skipped_files);
+            }
+        }
+
+        summary
+    }
+
+    /// Zusammenfassung eines während des Scans abgebrochenen Laufs: nichts gelöscht
+    pub fn to_cancelled_summary(&self) -> CleaningSummary {
+        let mut summary = CleaningSummary::new();
+        summary.errors = self.errors.clone();
+        summary.processing_time = self.scan_time;
+        summary.skipped_files = self.locations.iter().map(|l| l.skipped_files).sum();
+        summary.cancelled = true;
+        summary
+    }
+}
+
+/// Führt einen Bereinigungsplan aus und löscht genau die enthaltenen Einträge
+pub fn execute(plan: &CleaningPlan, options: &CleaningOptions) -> CleaningSummary {
+    execute_with_monitor(plan, options, &CleaningMonitor::default())
+}
+
+/// Wie [`execute`], meldet aber Fortschritt und bricht auf Anforderung ab
+///
+/// Bei einem Abbruch enthält die Zusammenfassung die bis dahin gelöschten Dateien
+/// und `cancelled` ist gesetzt.
+pub fn execute_with_monitor(plan: &CleaningPlan, options: &CleaningOptions, monitor: &CleaningMonitor) -> CleaningSummary {
+    if options.dry_run {
+        monitor.finish();
+        let mut summary = plan.to_summary();
+        if options.log_files {
+            for entry in &plan.entries {
+                log_file(&mut summary, options, entry, FileOutcome::Planned, None);
+            }
+        }
+        return summary;
+    }
+
+    let start_time = SystemTime::now();
+    let mut summary = CleaningSummary::new();
+    summary.errors = plan.errors.clone();
+
+    let disposal = match Disposal::for_options(options, &mut summary) {
+        Ok(disposal) => disposal,
+        Err(e) => {
+            // Ohne Quarantäne bzw. Papierkorb wird nichts angefasst
+            summary.add_error(e);
+            monitor.finish();
+            return summary;
+        }
+    };
+
+    monitor.start_deleting(plan.file_count());
+
+    for location in &plan.locations {
+        if summary.cancelled {
+            break;
+        }
+        monitor.start_location(&location.name);
+        let files_before = summary.deleted_files;
+        let size_before = summary.total_size;
+        let errors_before = summary.errors.len();
+        summary.skipped_files += location.skipped_files;
+
+        let mut emptied_dirs = BTreeSet::new();
+        let queue = WorkQueue::new(plan.entries_for(&location.name));
+        let results = run_workers(&queue, worker_count(options.worker_threads), |entry, queue, local: &mut WorkerDeletion| {
+            if monitor.is_cancelled() {
+                local.cancelled = true;
+                queue.close();
+                return;
+            }
+            if delete_planned_file(entry, &mut local.summary, options, &disposal) {
+                monitor.file_deleted(&entry.path, entry.size);
+                if let Some(parent) = entry.path.parent() {
+                    local.emptied_dirs.insert(parent.to_path_buf());
+                }
+            }
+        });
+
+        for result in results {
+            summary.deleted_files += result.summary.deleted_files;
+            summary.total_size += result.summary.total_size;
+            summary.skipped_files += result.summary.skipped_files;
+            summary.quarantined_files += result.summary.quarantined_files;
+            summary.trashed_files += result.summary.trashed_files;
+            summary.trashed_size += result.summary.trashed_size;
+            summary.errors.extend(result.summary.errors);
+            summary.files.extend(result.summary.files);
+            summary.cancelled |= result.cancelled;
+            emptied_dirs.extend(result.emptied_dirs);
+        }
+
+        if options.remove_empty_dirs {
+            remove_emptied_directories(&location.roots, &emptied_dirs, &mut summary, options);
+        }
+
+        let files_cleaned = summary.deleted_files - files_before;
+        let size_cleaned = summary.total_size - size_before;
+        let errors_count = summary.errors.len() - errors_before + location.errors;
+        let skipped_count = location.skipped_files;
+
+        if files_cleaned > 0 || errors_count > 0 || skipped_count > 0 {
+            summary.add_location_data(&location.name, files_cleaned, size_cleaned, errors_count, skipped_count);
+        }
+
+        if options.verbose {
+            println!("{}: {} Dateien gelöscht, {} übersprungen, {} Fehler",
+                location.name, files_cleaned, skipped_count, errors_count);
+        }
+    }
+
+    if let Disposal::Quarantine(run) = disposal {
+        if summary.quarantined_files > 0 {
+            summary.quarantine_run_id = Some(run.id().to_string());
+        }
+        if let Err(e) = run.finish() {
+            summary.add_error(e);
+        }
+    }
+
+    summary.processing_time = plan.scan_time + start_time.elapsed().unwrap_or_default();
+    monitor.finish();
+    summary
+}
+
+/// Wohin bereinigte Dateien gehen
+enum Disposal {
+    Delete,
+    Quarantine(QuarantineRun),
+    Trash(Trash),
+}
+
+impl Disposal {
+    fn for_options(options: &CleaningOptions, summary: &mut CleaningSummary) -> Result<Self, CleaningError> {
+        match options.deletion_mode {
+            DeletionMode::Delete => Ok(Disposal::Delete),
+            DeletionMode::Trash => Trash::home().map(Disposal::Trash),
+            DeletionMode::Quarantine => {
+                // Abgelaufene Läufe aufräumen, bevor ein neuer angelegt wird
+                let store = QuarantineStore::for_options(options)?;
+                if let Err(e) = store.purge_expired() {
+                    summary.add_error(e);
+                }
+                let days = options.quarantine_retention_days.min(MAX_RETENTION_DAYS);
+                let retention = Duration::from_secs(days * 24 * 60 * 60);
+                store.begin_run(retention).map(Disposal::Quarantine)
+            }
+        }
+    }
+}
+
+/// Ergebnisse eines Lösch-Workers, die nach dem Durchlauf zusammengeführt werden
+#[derive(Default)]
+struct WorkerDeletion {
+    summary: CleaningSummary,
+    emptied_dirs: BTreeSet<PathBuf>,
+    cancelled: bool,
+}
+
+/// Löscht eine geplante Datei (bzw. verschiebt sie), sofern sie sich seit dem Scan nicht verändert hat
+fn delete_planned_file(
+    entry: &PlannedFile,
+    summary: &mut CleaningSummary,
+    options: &CleaningOptions,
+    disposal: &Disposal
+) -> bool {
+    // Pläne können auch von außen stammen, daher hier erneut gegen die Sperrliste prüfen
+    if let Err(error) = ProtectedPaths::global().check(&entry.path) {
+        log_file(summary, options, entry, FileOutcome::Failed, Some(&error));
+        summary.add_error(error);
+        summary.skipped_files += 1;
+        return false;
+    }
+
+    let metadata = match fs::symlink_metadata(&entry.path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            // Bereits von jemand anderem entfernt
+            summary.skipped_files += 1;
+            log_file(summary, options, entry, FileOutcome::Skipped, None);
+            return false;
+        }
+        Err(e) => {
+            let error = CleaningError::IoError(entry.path.clone(), e.to_string());
+            log_file(summary, options, entry, FileOutcome::Failed, Some(&error));
+            summary.add_error(error);
+            return false;
+        }
+    };
+
+    let unchanged = if metadata.is_dir() {
+        entry.companion.is_some()
+    } else {
+        metadata.is_file() && metadata.len() == entry.size
+    };
+    if !unchanged || metadata.modified().ok() != entry.modified {
+        if options.verbose {
+            println!("Seit dem Scan verändert, übersprungen: {}", entry.path.display());
+        }
+        summary.skipped_files += 1;
+        log_file(summary, options, entry, FileOutcome::Skipped, None);
+        return false;
+    }
+
+    let result = match disposal {
+        Disposal::Quarantine(run) => run.store(entry).map(|_| FileOutcome::Quarantined),
+        // Einträge, die schon im Papierkorb liegen (Ziel "Papierkorb"), werden endgültig gelöscht
+        Disposal::Trash(trash) if !trash.contains(&entry.path) => trash.put(&entry.path).map(|_| FileOutcome::Trashed),
+        _ if metadata.is_dir() => fs::remove_dir_all(&entry.path).map(|_| FileOutcome::Deleted),
+        _ => fs::remove_file(&entry.path).map(|_| FileOutcome::Deleted),
+    };
+
+    match result {
+        Ok(outcome) => {
+            if let Some(companion) = &entry.companion {
+                if let Err(e) = dispose_companion(entry, companion, disposal) {
+                    summary.add_error(classify_io_error(companion, &e));
+                }
+            }
+            summary.deleted_files += 1;
+            summary.total_size += entry.size;
+            match outcome {
+                FileOutcome::Quarantined => summary.quarantined_files += 1,
+                FileOutcome::Trashed => {
+                    summary.trashed_files += 1;
+                    summary.trashed_size += entry.size;
+                }
+                _ => {}
+            }
+            if options.verbose {
+                println!("{}: {}", outcome, entry.path.display());
+            }
+            log_file(summary, options, entry, outcome, None);
+            true
+        },
+        Err(e) => {
+            let error = classify_io_error(&entry.path, &e);
+            log_file(summary, options, entry, FileOutcome::Failed, Some(&error));
+            summary.add_error(error);
+            summary.skipped_files += 1;
+            false
+        }
+    }
+}
+
+/// Entfernt die Begleitdatei eines bereits entfernten Eintrags auf dieselbe Weise wie den Eintrag
+///
+/// In der Quarantäne landet sie als eigener Eintrag, damit beim Wiederherstellen beide zurückkommen.
+fn dispose_companion(entry: &PlannedFile, companion: &Path, disposal: &Disposal) -> io::Result<()> {
+    let metadata = match fs::symlink_metadata(companion) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+
+    match disposal {
+        Disposal::Quarantine(run) => run.store(&PlannedFile {
+            location: entry.location.clone(),
+            path: companion.to_path_buf(),
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+            reason: entry.reason.clone(),
+            companion: None,
+        }),
+        Disposal::Trash(trash) if !trash.contains(companion) => trash.put(companion).map(|_| ()),
+        _ => fs::remove_file(companion),
+    }
+}
+
+/// Hält das Ergebnis einer Datei im Detailprotokoll fest, sofern gewünscht
+fn log_file(
+    summary: &mut CleaningSummary,
+    options: &CleaningOptions,
+    entry: &PlannedFile,
+    outcome: FileOutcome,
+    error: Option<&CleaningError>
+) {
+    if options.log_files {
+        summary.files.push(FileRecord {
+            location: entry.location.clone(),
+            path: entry.path.clone(),
+            size: entry.size,
+            outcome,
+            error: error.map(|e| e.to_string()),
+        });
+    }
+}
+
+/// Ordnet einen IO-Fehler der passenden Fehlerart zu
+pub(crate) fn classify_io_error(path: &Path, error: &io::Error) -> CleaningError {
+    if is_file_in_use_error(error) {
+        CleaningError::FileInUse(path.to_path_buf())
+    } else if error.kind() == io::ErrorKind::PermissionDenied {
+        CleaningError::PermissionDenied(path.to_path_buf())
+    } else {
+        CleaningError::IoError(path.to_path_buf(), error.to_string())
+    }
+}
+
+/// Entfernt Verzeichnisse, die durch diesen Lauf leer geworden sind
+///
+/// Ausgehend von den Elternverzeichnissen gelöschter Dateien wird nach oben gegangen,
+/// bis ein nicht-leeres Verzeichnis oder ein Wurzelverzeichnis erreicht ist. Bereits vorher
+/// leere oder fremde Verzeichnisse (z.B. in `/tmp`) bleiben unberührt.
+fn remove_emptied_directories(
+    roots: &[PathBuf],
+    emptied_dirs: &BTreeSet<PathBuf>,
+    summary: &mut CleaningSummary,
+    options: &CleaningOptions
+) {
+    // Tiefste Verzeichnisse zuerst, damit Eltern danach leer sein können
+    for dir in emptied_dirs.iter().rev() {
+        let Some(root) = roots.iter().find(|root| dir.starts_with(root)) else {
+            continue;
+        };
+
+        let mut current = dir.as_path();
+        while current != root.as_path() && current.starts_with(root) {
+            if !is_directory_empty(current).unwrap_or(false) || ProtectedPaths::global().check(current).is_err() {
+                break;
+            }
+            match fs::remove_dir(current) {
+                Ok(()) => {
+                    summary.empty_dirs_removed += 1;
+                    if options.verbose {
+                        println!("Leeres Verzeichnis entfernt: {}", current.display());
+                    }
+                },
+                Err(e) => {
+                    summary.add_error(CleaningError::IoError(current.to_path_buf(), e.to_string()));
+                    break;
+                }
+            }
+            current = match current.parent() {
+                Some(parent) => parent,
+                None => break,
+            };
+        }
+    }
+}
+
+/// Prüft, ob ein Verzeichnis leer ist
+fn is_directory_empty(dir: &Path) -> io::Result<bool> {
+    Ok(fs::read_dir(dir)?.next().is_none())
+}
+
+/// Verbesserte Prüfung für "Datei in Benutzung" Fehler mit Windows-Konstanten
+#[cfg(windows)]
+fn is_file_in_use_error(error: &io::Error) -> bool {
+    match error.raw_os_error() {
+        Some(err) if err == ERROR_SHARING_VIOLATION.0 as i32 => true,
+        Some(err) if err == ERROR_LOCK_VIOLATION.0 as i32 => true,
+        _ => error.kind() == io::ErrorKind::PermissionDenied,
+    }
+}
+
+#[cfg(not(windows))]
+fn is_file_in_use_error(error: &io::Error) -> bool {
+    error.kind() == io::ErrorKind::PermissionDenied
+}
+
+/// Plan über frisch angelegte Dateien `names` in `dir`, alle unter dem Ort "Test"
+#[cfg(test)]
+pub(crate) fn plan_for(dir: &Path, names: &[&str]) -> CleaningPlan {
+    use std::io::Write;
+
+    let mut plan = CleaningPlan::new();
+    plan.location_mut("Test").roots.push(dir.to_path_buf());
+    for name in names {
+        let path = dir.join(name);
+        let mut file = fs::File::create(&path).unwrap();
+        writeln!(file, "Inhalt von {}", name).unwrap();
+        let metadata = fs::metadata(&path).unwrap();
+        plan.push(PlannedFile {
+            location: "Test".to_string(),
+            path,
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+            reason: MatchReason::OlderThan(0),
+            companion: None,
+        });
+    }
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_execute_deletes_only_planned_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut plan = plan_for(temp_dir.path(), &["a.tmp", "b.tmp"]);
+        let untouched = temp_dir.path().join("c.tmp");
+        File::create(&untouched).unwrap();
+
+        plan.retain(|e| !e.path.ends_with("b.tmp"));
+        let summary = execute(&plan, &CleaningOptions::default());
+
+        assert_eq!(summary.deleted_files, 1);
+        assert!(!temp_dir.path().join("a.tmp").exists());
+        assert!(temp_dir.path().join("b.tmp").exists());
+        assert!(untouched.exists());
+    }
+
+    #[test]
+    fn test_execute_skips_changed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let plan = plan_for(temp_dir.path(), &["changed.tmp"]);
+        let mut file = fs::OpenOptions::new().append(true).open(&plan.entries[0].path).unwrap();
+        writeln!(file, "mehr Inhalt").unwrap();
+
+        let summary = execute(&plan, &CleaningOptions::default());
+        assert_eq!(summary.deleted_files, 0);
+        assert_eq!(summary.skipped_files, 1);
+        assert!(plan.entries[0].path.exists());
+    }
+
+    #[test]
+    fn test_dry_run_execute_keeps_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let plan = plan_for(temp_dir.path(), &["a.tmp"]);
+        let options = CleaningOptions { dry_run: true, ..CleaningOptions::default() };
+
+        let summary = execute(&plan, &options);
+        assert_eq!(summary.deleted_files, 1);
+        assert_eq!(summary.total_size, plan.total_size());
+        assert!(plan.entries[0].path.exists());
+    }
+
+    #[test]
+    fn test_execute_removes_only_emptied_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("a").join("b");
+        let untouched = temp_dir.path().join("leer");
+        fs::create_dir_all(&nested).unwrap();
+        fs::create_dir_all(&untouched).unwrap();
+
+        let mut plan = plan_for(&nested, &["x.tmp"]);
+        plan.locations[0].roots = vec![temp_dir.path().to_path_buf()];
+
+        let summary = execute(&plan, &CleaningOptions::default());
+        assert_eq!(summary.empty_dirs_removed, 2);
+        assert!(!temp_dir.path().join("a").exists());
+        assert!(untouched.exists());
+        assert!(temp_dir.path().exists());
+    }
+
+    #[test]
+    fn test_cancelled_execute_returns_partial_summary() {
+        let temp_dir = TempDir::new().unwrap();
+        let plan = plan_for(temp_dir.path(), &["a.tmp", "b.tmp"]);
+        let token = crate::cleaning::CancellationToken::new();
+        token.cancel();
+
+        let summary = execute_with_monitor(&plan, &CleaningOptions::default(), &CleaningMonitor::new(token));
+        assert!(summary.cancelled);
+        assert_eq!(summary.deleted_files, 0);
+        assert!(plan.entries.iter().all(|e| e.path.exists()));
+    }
+}
@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Mindestabstand zwischen zwei Fortschrittsmeldungen
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Token zum Abbrechen einer laufenden Bereinigung (günstig klonbar, thread-sicher)
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fordert den Abbruch an; laufende Operationen beenden sich beim nächsten Prüfpunkt
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Aktuelle Phase einer Bereinigung
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleaningPhase {
+    Scanning,
+    Deleting,
+    Finished,
+}
+
+/// Momentaufnahme des Fortschritts
+#[derive(Debug, Clone)]
+pub struct CleaningProgress {
+    pub phase: CleaningPhase,
+    pub location: String,
+    pub current_path: PathBuf,
+    pub files_scanned: usize,
+    /// Anzahl geplanter Dateien (bekannt ab der Löschphase)
+    pub files_planned: usize,
+    pub files_deleted: usize,
+    pub bytes_freed: u64,
+}
+
+impl CleaningProgress {
+    /// Anteil der erledigten Löschungen (0.0 - 1.0); während des Scans 0.0
+    pub fn fraction(&self) -> f32 {
+        match self.phase {
+            CleaningPhase::Scanning => 0.0,
+            CleaningPhase::Finished => 1.0,
+            CleaningPhase::Deleting if self.files_planned == 0 => 1.0,
+            CleaningPhase::Deleting => self.files_deleted as f32 / self.files_planned as f32,
+        }
+    }
+}
+
+type ProgressCallback = Box<dyn Fn(CleaningProgress) + Send + Sync>;
+
+/// Verbindet eine laufende Bereinigung mit ihrem Beobachter: Fortschritt nach außen, Abbruch nach innen
+pub struct CleaningMonitor {
+    cancel: CancellationToken,
+    callback: Option<ProgressCallback>,
+    files_scanned: AtomicUsize,
+    files_planned: AtomicUsize,
+    files_deleted: AtomicUsize,
+    bytes_freed: AtomicU64,
+    /// Phase, Ort und Zeitpunkt der letzten Meldung
+    state: Mutex<(CleaningPhase, String, Option<Instant>)>,
+}
+
+impl Default for CleaningMonitor {
+    fn default() -> Self {
+        Self::new(CancellationToken::new())
+    }
+}
+
+impl CleaningMonitor {
+    /// Monitor ohne Fortschrittsmeldungen, nur mit Abbruchmöglichkeit
+    pub fn new(cancel: CancellationToken) -> Self {
+        Self {
+            cancel,
+            callback: None,
+            files_scanned: AtomicUsize::new(0),
+            files_planned: AtomicUsize::new(0),
+            files_deleted: AtomicUsize::new(0),
+            bytes_freed: AtomicU64::new(0),
+            state: Mutex::new((CleaningPhase::Scanning, String::new(), None)),
+        }
+    }
+
+    /// Ruft `callback` mit gedrosselten Fortschrittsmeldungen auf
+    pub fn with_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(CleaningProgress) + Send + Sync + 'static,
+    {
+        self.callback = Some(Box::new(callback));
+        self
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    pub(crate) fn start_location(&self, location: &str) {
+        if let Ok(mut state) = self.state.lock() {
+            state.1 = location.to_string();
+        }
+    }
+
+    pub(crate) fn start_deleting(&self, files_planned: usize) {
+        self.files_planned.store(files_planned, Ordering::Relaxed);
+        self.set_phase(CleaningPhase::Deleting, Path::new(""));
+    }
+
+    pub(crate) fn finish(&self) {
+        self.set_phase(CleaningPhase::Finished, Path::new(""));
+    }
+
+    pub(crate) fn file_scanned(&self, path: &Path) {
+        self.files_scanned.fetch_add(1, Ordering::Relaxed);
+        self.emit(path, false);
+    }
+
+    pub(crate) fn file_deleted(&self, path: &Path, size: u64) {
+        self.files_deleted.fetch_add(1, Ordering::Relaxed);
+        self.bytes_freed.fetch_add(size, Ordering::Relaxed);
+        self.emit(path, false);
+    }
+
+    fn set_phase(&self, phase: CleaningPhase, path: &Path) {
+        if let Ok(mut state) = self.state.lock() {
+            state.0 = phase;
+        }
+        self.emit(path, true);
+    }
+
+    fn emit(&self, path: &Path, force: bool) {
+        let Some(callback) = &self.callback else {
+            return;
+        };
+
+        let (phase, location) = {
+            let Ok(mut state) = self.state.lock() else {
+                return;
+            };
+            let now = Instant::now();
+            if !force && state.2.is_some_and(|last| now.duration_since(last) < PROGRESS_INTERVAL) {
+                return;
+            }
+            state.2 = Some(now);
+            (state.0, state.1.clone())
+        };
+
+        callback(CleaningProgress {
+            phase,
+            location,
+            current_path: path.to_path_buf(),
+            files_scanned: self.files_scanned.load(Ordering::Relaxed),
+            files_planned: self.files_planned.load(Ordering::Relaxed),
+            files_deleted: self.files_deleted.load(Ordering::Relaxed),
+            bytes_freed: self.bytes_freed.load(Ordering::Relaxed),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_is_throttled_but_phase_changes_are_not() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let monitor = CleaningMonitor::default()
+            .with_callback(move |progress| sink.lock().unwrap().push(progress));
+
+        for _ in 0..1000 {
+            monitor.file_scanned(Path::new("datei.tmp"));
+        }
+        monitor.start_deleting(10);
+        monitor.finish();
+
+        let events = events.lock().unwrap();
+        assert!(events.len() < 10);
+        assert_eq!(events.last().unwrap().phase, CleaningPhase::Finished);
+        assert_eq!(events.last().unwrap().files_scanned, 1000);
+        assert_eq!(events.last().unwrap().files_planned, 10);
+    }
+
+    #[test]
+    fn test_cancellation_token_is_shared() {
+        let token = CancellationToken::new();
+        let monitor = CleaningMonitor::new(token.clone());
+        assert!(!monitor.is_cancelled());
+        token.cancel();
+        assert!(monitor.is_cancelled());
+    }
+}
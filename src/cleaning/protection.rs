@@ -0,0 +1,178 @@
+use std::path::{Component, Path, PathBuf};
+
+use once_cell::sync::Lazy;
+
+use super::targets::{app_config_dir, home_dir};
+use super::temp_files::CleaningError;
+
+/// Unterhalb des Home-Verzeichnisses nie angefasste Ordner (englisch und deutsch)
+const PROTECTED_HOME_DIRS: &[&str] = &["Documents", "Dokumente", "Desktop", "Schreibtisch"];
+
+#[cfg(unix)]
+const SYSTEM_PREFIXES: &[&str] = &["/bin", "/boot", "/dev", "/etc", "/lib", "/lib32", "/lib64", "/proc", "/sbin", "/sys", "/usr"];
+#[cfg(unix)]
+const SYSTEM_EXACT: &[&str] = &["/", "/home", "/opt", "/root", "/var"];
+
+#[cfg(windows)]
+const SYSTEM_PREFIXES: &[&str] = &[
+    "C:\\Windows\\System32",
+    "C:\\Windows\\SysWOW64",
+    "C:\\Windows\\WinSxS",
+    "C:\\Program Files",
+    "C:\\Program Files (x86)",
+    "C:\\ProgramData\\Microsoft",
+];
+#[cfg(windows)]
+const SYSTEM_EXACT: &[&str] = &["C:\\", "C:\\Windows", "C:\\Users", "C:\\ProgramData"];
+
+static GLOBAL: Lazy<ProtectedPaths> = Lazy::new(ProtectedPaths::system_defaults);
+
+/// Globale Sperrliste von Pfaden, die kein Ziel betreten oder löschen darf
+///
+/// - `exact`: nur der Pfad selbst ist gesperrt (z.B. das Home-Verzeichnis), Unterordner wie
+///   `~/.cache` bleiben erlaubt.
+/// - `prefixes`: der Pfad und alles darunter ist gesperrt (z.B. `~/Documents`, `/usr`).
+/// - Git-Arbeitskopien: Verzeichnisse mit `.git` werden nicht betreten und nichts in `.git` wird gelöscht.
+///
+/// Übergeordnete Verzeichnisse eines gesperrten Pfads sind ebenfalls gesperrt, damit ein Ziel
+/// mit der Wurzel `/home` nicht über Umwege in ein Home-Verzeichnis gelangt.
+#[derive(Debug, Clone, Default)]
+pub struct ProtectedPaths {
+    exact: Vec<PathBuf>,
+    prefixes: Vec<PathBuf>,
+    git_worktrees: bool,
+}
+
+impl ProtectedPaths {
+    /// Leere Liste (nichts gesperrt)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sperrliste, die der Bereinigung immer zugrunde liegt
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// Home-Verzeichnis, Dokumente, Systemverzeichnisse, Konfiguration und Git-Arbeitskopien
+    pub fn system_defaults() -> Self {
+        let mut paths = Self::new().with_git_worktrees();
+        for path in SYSTEM_EXACT {
+            paths = paths.with_exact(path);
+        }
+        for path in SYSTEM_PREFIXES {
+            paths = paths.with_prefix(path);
+        }
+        if let Some(home) = home_dir() {
+            for dir in PROTECTED_HOME_DIRS {
+                paths = paths.with_prefix(home.join(dir));
+            }
+            paths = paths.with_exact(home);
+        }
+        if let Some(config) = app_config_dir() {
+            paths = paths.with_prefix(config);
+        }
+        paths
+    }
+
+    pub fn with_exact(mut self, path: impl Into<PathBuf>) -> Self {
+        self.exact.push(normalize(&path.into()));
+        self
+    }
+
+    pub fn with_prefix(mut self, path: impl Into<PathBuf>) -> Self {
+        self.prefixes.push(normalize(&path.into()));
+        self
+    }
+
+    pub fn with_git_worktrees(mut self) -> Self {
+        self.git_worktrees = true;
+        self
+    }
+
+    /// Prüft ein Verzeichnis, bevor es betreten oder entfernt wird
+    pub fn check_dir(&self, dir: &Path) -> Result<(), CleaningError> {
+        self.check(dir)?;
+        if self.git_worktrees && dir.join(".git").symlink_metadata().is_ok() {
+            return Err(CleaningError::ProtectedPath(dir.to_path_buf()));
+        }
+        Ok(())
+    }
+
+    /// Prüft eine Datei bzw. einen Pfad ohne Zugriff auf das Dateisystem
+    pub fn check(&self, path: &Path) -> Result<(), CleaningError> {
+        if self.is_protected(path) {
+            Err(CleaningError::ProtectedPath(path.to_path_buf()))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn is_protected(&self, path: &Path) -> bool {
+        let path = normalize(path);
+        let inside_git = self.git_worktrees && path.components().any(|c| c.as_os_str() == ".git");
+
+        inside_git
+            || self.prefixes.iter().any(|prefix| path.starts_with(prefix))
+            // Gesperrter Pfad selbst oder eines seiner Elternverzeichnisse
+            || self.exact.iter().chain(&self.prefixes).any(|protected| protected.starts_with(&path))
+    }
+}
+
+/// Lexikalisch bereinigter Pfad (`.` und `..` aufgelöst, unter Windows ohne Groß-/Kleinschreibung)
+fn normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                result.pop();
+            }
+            other => result.push(other.as_os_str()),
+        }
+    }
+    if cfg!(windows) {
+        PathBuf::from(result.to_string_lossy().to_lowercase())
+    } else {
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_exact_and_prefix_rules() {
+        let home = Path::new("/home/nutzer");
+        let paths = ProtectedPaths::new()
+            .with_exact(home)
+            .with_prefix(home.join("Documents"));
+
+        assert!(paths.check(home).is_err());
+        assert!(paths.check(Path::new("/home")).is_err());
+        assert!(paths.check(Path::new("/")).is_err());
+        assert!(paths.check(&home.join("Documents/steuer.pdf")).is_err());
+        assert!(paths.check(&home.join(".cache/../Documents/a")).is_err());
+        assert!(paths.check(&home.join(".cache/thumbnails/a.png")).is_ok());
+        assert!(paths.check(&home.join("Documents2/a")).is_ok());
+
+        assert!(matches!(paths.check(home), Err(CleaningError::ProtectedPath(p)) if p == home));
+    }
+
+    #[test]
+    fn test_git_worktrees_are_not_entered() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().join("projekt");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        std::fs::create_dir_all(repo.join("target")).unwrap();
+
+        let paths = ProtectedPaths::new().with_git_worktrees();
+        assert!(paths.check_dir(&repo).is_err());
+        assert!(paths.check(&repo.join(".git/index")).is_err());
+        // Explizit ausgewählte Build-Verzeichnisse innerhalb eines Projekts bleiben erlaubt
+        assert!(paths.check_dir(&repo.join("target")).is_ok());
+        assert!(ProtectedPaths::new().check_dir(&repo).is_ok());
+    }
+}
@@ -0,0 +1,535 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::plan::{classify_io_error, PlannedFile};
+use super::temp_files::{CleaningError, CleaningOptions};
+
+const MANIFEST_FILE: &str = "manifest.json";
+/// Während eines Laufs wird jede Datei vor dem Verschieben als eine JSON-Zeile angehängt,
+/// damit sie auch nach einem Absturz vor `finish` wiederhergestellt werden kann
+const JOURNAL_FILE: &str = "entries.jsonl";
+const FILES_DIR: &str = "files";
+
+/// Längste erlaubte Aufbewahrungsdauer in Tagen (100 Jahre); größere Werte werden begrenzt
+pub const MAX_RETENTION_DAYS: u64 = 36_500;
+
+/// Eine in die Quarantäne verschobene Datei
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    /// Ursprünglicher Pfad, an den die Datei wiederhergestellt wird
+    pub original_path: PathBuf,
+    /// Dateiname innerhalb des Quarantäne-Laufs
+    pub stored_name: String,
+    pub location: String,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// Zeile im Journal eines laufenden Quarantäne-Vorgangs
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum JournalRecord {
+    /// Wird vor dem Verschieben geschrieben
+    Stored(QuarantineEntry),
+    /// Verschieben gescheitert; der Eintrag mit diesem `stored_name` gilt nicht
+    Failed(String),
+}
+
+/// Manifest eines Quarantäne-Laufs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineManifest {
+    pub run_id: String,
+    pub created: SystemTime,
+    /// Ab diesem Zeitpunkt darf der Lauf endgültig gelöscht werden
+    pub expires: SystemTime,
+    pub entries: Vec<QuarantineEntry>,
+}
+
+impl QuarantineManifest {
+    /// Gesamtgröße aller Dateien des Laufs in Bytes
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|e| e.size).sum()
+    }
+
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        now >= self.expires
+    }
+}
+
+/// Ergebnis einer Wiederherstellung
+#[derive(Debug, Clone, Default)]
+pub struct RestoreSummary {
+    pub restored_files: usize,
+    pub restored_size: u64,
+    /// Ursprungspfade, an denen inzwischen wieder eine Datei liegt; diese bleiben in der Quarantäne
+    pub conflicts: Vec<PathBuf>,
+    pub errors: Vec<CleaningError>,
+}
+
+/// Quarantäne-Verzeichnis mit einem Unterverzeichnis pro Lauf
+#[derive(Debug, Clone)]
+pub struct QuarantineStore {
+    root: PathBuf,
+}
+
+impl QuarantineStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Standardort: `quarantine` im Datenverzeichnis der Anwendung
+    pub fn default_dir() -> Option<PathBuf> {
+        super::targets::app_data_dir().map(|data| data.join("quarantine"))
+    }
+
+    /// Quarantäne laut Optionen (`quarantine_dir` oder Standardort)
+    pub fn for_options(options: &CleaningOptions) -> Result<Self, CleaningError> {
+        options
+            .quarantine_dir
+            .clone()
+            .or_else(Self::default_dir)
+            .map(Self::new)
+            .ok_or_else(|| CleaningError::InvalidPath("Kein Quarantäne-Verzeichnis ermittelbar".to_string()))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Alle vorhandenen Läufe, älteste zuerst
+    pub fn runs(&self) -> Vec<QuarantineManifest> {
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+
+        let mut runs: Vec<QuarantineManifest> = entries
+            .flatten()
+            .filter_map(|entry| read_manifest(&entry.path()).ok())
+            .collect();
+        runs.sort_by_key(|run| run.created);
+        runs
+    }
+
+    /// Manifest eines bestimmten Laufs
+    pub fn manifest(&self, run_id: &str) -> Result<QuarantineManifest, CleaningError> {
+        read_manifest(&self.run_dir(run_id)?)
+    }
+
+    /// Legt einen neuen Lauf an, der nach `retention` verfällt
+    pub fn begin_run(&self, retention: Duration) -> Result<QuarantineRun, CleaningError> {
+        let created = SystemTime::now();
+        let stamp = created.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        fs::create_dir_all(&self.root).map_err(|e| classify_io_error(&self.root, &e))?;
+
+        // Eindeutige Lauf-ID, auch wenn mehrere Läufe in derselben Millisekunde starten
+        let mut suffix = 0;
+        let (id, dir) = loop {
+            let id = match suffix {
+                0 => format!("{}-{:03}", stamp.as_secs(), stamp.subsec_millis()),
+                n => format!("{}-{:03}-{}", stamp.as_secs(), stamp.subsec_millis(), n),
+            };
+            let dir = self.root.join(&id);
+            match fs::create_dir(&dir) {
+                Ok(()) => break (id, dir),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => suffix += 1,
+                Err(e) => return Err(classify_io_error(&dir, &e)),
+            }
+        };
+
+        let files_dir = dir.join(FILES_DIR);
+        fs::create_dir(&files_dir).map_err(|e| classify_io_error(&files_dir, &e))?;
+
+        let journal_path = dir.join(JOURNAL_FILE);
+        let journal = File::options()
+            .create(true)
+            .append(true)
+            .open(&journal_path)
+            .map_err(|e| classify_io_error(&journal_path, &e))?;
+
+        let run = QuarantineRun {
+            id,
+            dir,
+            created,
+            expires: created + retention,
+            journal: Mutex::new(Journal { file: journal, entries: Vec::new() }),
+            next_index: AtomicUsize::new(0),
+        };
+        // Leeres Manifest sofort schreiben, damit der Lauf auch nach einem Absturz auffindbar ist
+        run.write_manifest()?;
+        Ok(run)
+    }
+
+    /// Verschiebt alle Dateien eines Laufs an ihren ursprünglichen Ort zurück
+    ///
+    /// Existiert am Ursprungspfad bereits wieder eine Datei, wird nichts überschrieben;
+    /// solche Einträge bleiben im Manifest. Ein vollständig wiederhergestellter Lauf wird entfernt.
+    pub fn restore(&self, run_id: &str) -> Result<RestoreSummary, CleaningError> {
+        let dir = self.run_dir(run_id)?;
+        let mut manifest = read_manifest(&dir)?;
+        let mut summary = RestoreSummary::default();
+        let mut remaining = Vec::new();
+
+        for entry in manifest.entries.drain(..) {
+            if fs::symlink_metadata(&entry.original_path).is_ok() {
+                summary.conflicts.push(entry.original_path.clone());
+                remaining.push(entry);
+                continue;
+            }
+
+            let stored = dir.join(FILES_DIR).join(&entry.stored_name);
+            let result = match entry.original_path.parent() {
+                Some(parent) => fs::create_dir_all(parent).and_then(|_| move_file(&stored, &entry.original_path)),
+                None => move_file(&stored, &entry.original_path),
+            };
+
+            match result {
+                Ok(()) => {
+                    summary.restored_files += 1;
+                    summary.restored_size += entry.size;
+                }
+                Err(e) => {
+                    summary.errors.push(classify_io_error(&entry.original_path, &e));
+                    remaining.push(entry);
+                }
+            }
+        }
+
+        if remaining.is_empty() {
+            fs::remove_dir_all(&dir).map_err(|e| classify_io_error(&dir, &e))?;
+        } else {
+            manifest.entries = remaining;
+            write_manifest(&dir, &manifest)?;
+            remove_journal(&dir)?;
+        }
+
+        Ok(summary)
+    }
+
+    /// Löscht alle abgelaufenen Läufe endgültig und liefert deren Anzahl
+    pub fn purge_expired(&self) -> Result<usize, CleaningError> {
+        let now = SystemTime::now();
+        let mut purged = 0;
+
+        for run in self.runs().into_iter().filter(|run| run.is_expired(now)) {
+            let dir = self.run_dir(&run.run_id)?;
+            fs::remove_dir_all(&dir).map_err(|e| classify_io_error(&dir, &e))?;
+            purged += 1;
+        }
+
+        Ok(purged)
+    }
+
+    fn run_dir(&self, run_id: &str) -> Result<PathBuf, CleaningError> {
+        // Lauf-IDs sind einfache Namen; alles andere könnte aus dem Quarantäne-Verzeichnis herausführen
+        let valid = !run_id.is_empty() && run_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        if !valid {
+            return Err(CleaningError::InvalidPath(format!("Ungültige Quarantäne-ID: {}", run_id)));
+        }
+
+        let dir = self.root.join(run_id);
+        if !dir.is_dir() {
+            return Err(CleaningError::DirectoryNotFound(dir));
+        }
+        Ok(dir)
+    }
+}
+
+/// Ein laufender Quarantäne-Vorgang; Dateien können von mehreren Workern gleichzeitig abgelegt werden
+pub struct QuarantineRun {
+    id: String,
+    dir: PathBuf,
+    created: SystemTime,
+    expires: SystemTime,
+    journal: Mutex<Journal>,
+    next_index: AtomicUsize,
+}
+
+/// Offenes Journal eines Laufs und die bisher abgelegten Einträge
+struct Journal {
+    file: File,
+    entries: Vec<QuarantineEntry>,
+}
+
+impl QuarantineRun {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Verschiebt eine geplante Datei in die Quarantäne
+    pub(crate) fn store(&self, file: &PlannedFile) -> io::Result<()> {
+        let index = self.next_index.fetch_add(1, Ordering::Relaxed);
+        let file_name = file.path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+        let stored_name = format!("{:06}-{}", index, file_name);
+
+        let stored_path = self.dir.join(FILES_DIR).join(&stored_name);
+        let entry = QuarantineEntry {
+            original_path: file.path.clone(),
+            stored_name,
+            location: file.location.clone(),
+            size: file.size,
+            modified: file.modified,
+        };
+
+        // Erst festhalten, dann verschieben: So liegt nie eine Datei ohne Eintrag in der Quarantäne
+        self.append(&JournalRecord::Stored(entry.clone()))?;
+        if let Err(e) = move_file(&file.path, &stored_path) {
+            // Scheitert auch das, übergeht read_manifest den Eintrag, weil die Datei fehlt
+            let _ = self.append(&JournalRecord::Failed(entry.stored_name));
+            return Err(e);
+        }
+
+        self.lock_journal()?.entries.push(entry);
+        Ok(())
+    }
+
+    /// Hängt eine Zeile an das Journal an, in einem Schreibvorgang
+    fn append(&self, record: &JournalRecord) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record).map_err(io::Error::other)?;
+        line.push(b'\n');
+        self.lock_journal()?.file.write_all(&line)
+    }
+
+    fn lock_journal(&self) -> io::Result<std::sync::MutexGuard<'_, Journal>> {
+        self.journal
+            .lock()
+            .map_err(|_| io::Error::other("Quarantäne-Journal nach einem Fehler in einem anderen Worker gesperrt"))
+    }
+
+    /// Schreibt das endgültige Manifest; ein Lauf ohne Dateien wird wieder entfernt
+    pub(crate) fn finish(self) -> Result<(), CleaningError> {
+        let journal = self.journal.into_inner().map_err(|_| {
+            CleaningError::IoError(self.dir.join(JOURNAL_FILE), "Quarantäne-Journal nach einem Fehler gesperrt".to_string())
+        })?;
+        if journal.entries.is_empty() {
+            return fs::remove_dir_all(&self.dir).map_err(|e| classify_io_error(&self.dir, &e));
+        }
+
+        let manifest = QuarantineManifest {
+            run_id: self.id,
+            created: self.created,
+            expires: self.expires,
+            entries: journal.entries,
+        };
+        write_manifest(&self.dir, &manifest)?;
+        drop(journal.file);
+        remove_journal(&self.dir)
+    }
+
+    fn write_manifest(&self) -> Result<(), CleaningError> {
+        let manifest = QuarantineManifest {
+            run_id: self.id.clone(),
+            created: self.created,
+            expires: self.expires,
+            entries: Vec::new(),
+        };
+        write_manifest(&self.dir, &manifest)
+    }
+}
+
+/// Stellt einen Quarantäne-Lauf aus dem Quarantäne-Verzeichnis der Optionen wieder her
+pub fn restore(run_id: &str, options: &CleaningOptions) -> Result<RestoreSummary, CleaningError> {
+    QuarantineStore::for_options(options)?.restore(run_id)
+}
+
+/// Liest das Manifest eines Laufs samt der Einträge, die bisher nur im Journal stehen
+///
+/// Das ist nach einem Absturz vor `finish` der Fall; eine abgebrochene letzte Zeile wird übergangen,
+/// ebenso Einträge, deren Datei nie in der Quarantäne angekommen ist.
+fn read_manifest(dir: &Path) -> Result<QuarantineManifest, CleaningError> {
+    let path = dir.join(MANIFEST_FILE);
+    let file = File::open(&path).map_err(|e| classify_io_error(&path, &e))?;
+    let mut manifest: QuarantineManifest = serde_json::from_reader(io::BufReader::new(file))
+        .map_err(|e| CleaningError::IoError(path.clone(), e.to_string()))?;
+
+    let journal_path = dir.join(JOURNAL_FILE);
+    match File::open(&journal_path) {
+        Ok(journal) => {
+            let mut stored = Vec::new();
+            for line in io::BufReader::new(journal).lines() {
+                let line = line.map_err(|e| classify_io_error(&journal_path, &e))?;
+                match serde_json::from_str::<JournalRecord>(&line) {
+                    Ok(JournalRecord::Stored(entry)) => stored.push(entry),
+                    Ok(JournalRecord::Failed(name)) => stored.retain(|e| e.stored_name != name),
+                    Err(_) => {}
+                }
+            }
+            for entry in stored {
+                let arrived = fs::symlink_metadata(dir.join(FILES_DIR).join(&entry.stored_name)).is_ok();
+                // Absturz zwischen Manifest und Entfernen des Journals: nichts doppelt aufführen
+                if arrived && !manifest.entries.iter().any(|e| e.stored_name == entry.stored_name) {
+                    manifest.entries.push(entry);
+                }
+            }
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(classify_io_error(&journal_path, &e)),
+    }
+    Ok(manifest)
+}
+
+/// Entfernt das Journal, nachdem dessen Einträge im Manifest stehen
+fn remove_journal(dir: &Path) -> Result<(), CleaningError> {
+    let path = dir.join(JOURNAL_FILE);
+    match fs::remove_file(&path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(classify_io_error(&path, &e)),
+        _ => Ok(()),
+    }
+}
+
+/// Schreibt das Manifest über eine temporäre Datei, damit nie ein halbes Manifest entsteht
+fn write_manifest(dir: &Path, manifest: &QuarantineManifest) -> Result<(), CleaningError> {
+    let path = dir.join(MANIFEST_FILE);
+    let temp_path = dir.join(format!("{}.tmp", MANIFEST_FILE));
+
+    let json = serde_json::to_vec_pretty(manifest)
+        .map_err(|e| CleaningError::IoError(path.clone(), e.to_string()))?;
+    fs::write(&temp_path, json).map_err(|e| classify_io_error(&temp_path, &e))?;
+    fs::rename(&temp_path, &path).map_err(|e| classify_io_error(&path, &e))
+}
+
+/// Verschiebt eine Datei; über Dateisystemgrenzen hinweg wird kopiert und das Original gelöscht
+pub(crate) fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    let modified = fs::metadata(from)?.modified().ok();
+    if let Err(e) = fs::copy(from, to) {
+        let _ = fs::remove_file(to);
+        return Err(e);
+    }
+    if let Some(modified) = modified {
+        File::options().write(true).open(to)?.set_modified(modified)?;
+    }
+    if let Err(e) = fs::remove_file(from) {
+        // Original bleibt erhalten, also auch keine Kopie zurücklassen
+        let _ = fs::remove_file(to);
+        return Err(e);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cleaning::plan::plan_for;
+    use crate::cleaning::{execute, DeletionMode};
+    use tempfile::TempDir;
+
+    fn quarantine_options(dir: &Path, retention_days: u64) -> CleaningOptions {
+        CleaningOptions {
+            deletion_mode: DeletionMode::Quarantine,
+            quarantine_dir: Some(dir.to_path_buf()),
+            quarantine_retention_days: retention_days,
+            ..CleaningOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_quarantine_and_restore_roundtrip() {
+        let files = TempDir::new().unwrap();
+        let quarantine = TempDir::new().unwrap();
+        let nested = files.path().join("unter");
+        fs::create_dir(&nested).unwrap();
+        let plan = plan_for(&nested, &["a.tmp", "b.tmp"]);
+        let original_mtime = plan.entries[0].modified;
+
+        let summary = execute(&plan, &quarantine_options(quarantine.path(), 7));
+        assert_eq!(summary.quarantined_files, 2);
+        assert!(!nested.join("a.tmp").exists());
+
+        let run_id = summary.quarantine_run_id.expect("Lauf-ID fehlt");
+        let store = QuarantineStore::new(quarantine.path());
+        let manifest = store.manifest(&run_id).unwrap();
+        assert_eq!(manifest.entries.len(), 2);
+        assert_eq!(manifest.total_size(), plan.total_size());
+
+        let restored = store.restore(&run_id).unwrap();
+        assert_eq!(restored.restored_files, 2);
+        assert!(restored.conflicts.is_empty());
+        assert_eq!(fs::read_to_string(nested.join("a.tmp")).unwrap(), "Inhalt von a.tmp\n");
+        assert_eq!(fs::metadata(nested.join("a.tmp")).unwrap().modified().ok(), original_mtime);
+        assert!(store.runs().is_empty());
+    }
+
+    #[test]
+    fn test_restore_keeps_conflicting_entries() {
+        let files = TempDir::new().unwrap();
+        let quarantine = TempDir::new().unwrap();
+        let plan = plan_for(files.path(), &["a.tmp"]);
+
+        let summary = execute(&plan, &quarantine_options(quarantine.path(), 7));
+        let run_id = summary.quarantine_run_id.unwrap();
+        fs::write(files.path().join("a.tmp"), "neu").unwrap();
+
+        let store = QuarantineStore::new(quarantine.path());
+        let restored = store.restore(&run_id).unwrap();
+        assert_eq!(restored.restored_files, 0);
+        assert_eq!(restored.conflicts, vec![files.path().join("a.tmp")]);
+        assert_eq!(fs::read_to_string(files.path().join("a.tmp")).unwrap(), "neu");
+        assert_eq!(store.manifest(&run_id).unwrap().entries.len(), 1);
+    }
+
+    #[test]
+    fn test_entries_survive_crash_before_finish() {
+        let files = TempDir::new().unwrap();
+        let quarantine = TempDir::new().unwrap();
+        let plan = plan_for(files.path(), &["a.tmp", "b.tmp"]);
+
+        let store = QuarantineStore::new(quarantine.path());
+        let run = store.begin_run(Duration::from_secs(3600)).unwrap();
+        let run_id = run.id().to_string();
+        for entry in &plan.entries {
+            run.store(entry).unwrap();
+        }
+        // Absturz: finish wird nie aufgerufen
+        std::mem::forget(run);
+
+        assert_eq!(store.manifest(&run_id).unwrap().entries.len(), 2);
+        let restored = store.restore(&run_id).unwrap();
+        assert_eq!(restored.restored_files, 2);
+        assert_eq!(fs::read_to_string(files.path().join("b.tmp")).unwrap(), "Inhalt von b.tmp\n");
+    }
+
+    #[test]
+    fn test_failed_moves_leave_no_entry() {
+        let files = TempDir::new().unwrap();
+        let quarantine = TempDir::new().unwrap();
+        let plan = plan_for(files.path(), &["a.tmp", "b.tmp"]);
+        fs::remove_file(&plan.entries[1].path).unwrap();
+
+        let store = QuarantineStore::new(quarantine.path());
+        let run = store.begin_run(Duration::from_secs(3600)).unwrap();
+        let run_id = run.id().to_string();
+        run.store(&plan.entries[0]).unwrap();
+        assert!(run.store(&plan.entries[1]).is_err());
+        // Absturz nach dem Journal-Eintrag, aber vor dem Verschieben
+        let mut journal = File::options().append(true).open(quarantine.path().join(&run_id).join(JOURNAL_FILE)).unwrap();
+        let pending = QuarantineEntry { stored_name: "000099-c.tmp".to_string(), ..store.manifest(&run_id).unwrap().entries[0].clone() };
+        writeln!(journal, "{}", serde_json::to_string(&JournalRecord::Stored(pending)).unwrap()).unwrap();
+        std::mem::forget(run);
+
+        let manifest = store.manifest(&run_id).unwrap();
+        assert_eq!(manifest.entries.len(), 1);
+        assert_eq!(manifest.entries[0].original_path, plan.entries[0].path);
+    }
+
+    #[test]
+    fn test_purge_expired_runs() {
+        let quarantine = TempDir::new().unwrap();
+        let store = QuarantineStore::new(quarantine.path());
+        store.begin_run(Duration::ZERO).unwrap();
+        store.begin_run(Duration::from_secs(3600)).unwrap();
+
+        assert_eq!(store.purge_expired().unwrap(), 1);
+        assert_eq!(store.runs().len(), 1);
+        assert!(store.restore("../etc").is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::temp_files::CleaningSummary;
+
+/// Vollständige Zusammenfassung als JSON (inkl. Orte, typisierter Fehler und Detailprotokoll)
+pub fn to_json(summary: &CleaningSummary) -> Result<String, serde_json::Error> {
+    serde_json::to_string_pretty(summary)
+}
+
+/// Liest einen zuvor exportierten JSON-Bericht
+pub fn from_json(json: &str) -> Result<CleaningSummary, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+/// Eine Zeile pro Bereinigungsort, nach Namen sortiert
+pub fn locations_csv(summary: &CleaningSummary) -> String {
+    let mut csv = String::from("location,deleted_files,total_size,errors,skipped_files\n");
+
+    let mut locations: Vec<_> = summary.cleaned_locations.values().collect();
+    locations.sort_by(|a, b| a.location_name.cmp(&b.location_name));
+
+    for location in locations {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            csv_field(&location.location_name),
+            location.deleted_files,
+            location.total_size,
+            location.errors,
+            location.skipped_files
+        ));
+    }
+
+    csv
+}
+
+/// Eine Zeile pro Datei aus dem Detailprotokoll (`CleaningOptions::log_files`)
+pub fn files_csv(summary: &CleaningSummary) -> String {
+    let mut csv = String::from("location,path,size,outcome,error\n");
+
+    for record in &summary.files {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            csv_field(&record.location),
+            csv_field(&record.path.to_string_lossy()),
+            record.size,
+            record.outcome.as_str(),
+            csv_field(record.error.as_deref().unwrap_or(""))
+        ));
+    }
+
+    csv
+}
+
+/// Speichert den Bericht als `<name>.json` und `<name>.csv` im Verzeichnis `dir`
+///
+/// Enthält die Zusammenfassung ein Detailprotokoll, wird zusätzlich `<name>-dateien.csv` geschrieben.
+/// Liefert die Pfade aller geschriebenen Dateien.
+pub fn save_report(summary: &CleaningSummary, dir: &Path, name: &str) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+
+    let json = to_json(summary).map_err(io::Error::other)?;
+    let json_path = dir.join(format!("{}.json", name));
+    fs::write(&json_path, json)?;
+    written.push(json_path);
+
+    let csv_path = dir.join(format!("{}.csv", name));
+    fs::write(&csv_path, locations_csv(summary))?;
+    written.push(csv_path);
+
+    if !summary.files.is_empty() {
+        let files_path = dir.join(format!("{}-dateien.csv", name));
+        fs::write(&files_path, files_csv(summary))?;
+        written.push(files_path);
+    }
+
+    Ok(written)
+}
+
+/// Standardverzeichnis für Berichte: `reports` im Datenverzeichnis der Anwendung
+pub fn default_report_dir() -> Option<PathBuf> {
+    super::targets::app_data_dir().map(|data| data.join("reports"))
+}
+
+/// Dateiname ohne Endung für einen Bericht zum aktuellen Zeitpunkt
+pub fn report_name() -> String {
+    format!("bericht-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"))
+}
+
+/// Maskiert ein CSV-Feld nach RFC 4180
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cleaning::{CleaningError, FileOutcome, FileRecord};
+    use tempfile::TempDir;
+
+    fn sample_summary() -> CleaningSummary {
+        let mut summary = CleaningSummary::new();
+        summary.deleted_files = 3;
+        summary.total_size = 300;
+        summary.add_location_data("Zweiter, Ort", 1, 100, 1, 0);
+        summary.add_location_data("Erster Ort", 2, 200, 0, 4);
+        summary.add_error(CleaningError::IoError(PathBuf::from("/tmp/x"), "kaputt".to_string()));
+        summary.files.push(FileRecord {
+            location: "Erster Ort".to_string(),
+            path: PathBuf::from("/tmp/\"a\".tmp"),
+            size: 200,
+            outcome: FileOutcome::Deleted,
+            error: None,
+        });
+        summary
+    }
+
+    #[test]
+    fn test_json_roundtrip_keeps_typed_errors() {
+        let summary = sample_summary();
+        let json = to_json(&summary).unwrap();
+        assert!(json.contains("\"kind\": \"IoError\""));
+        // Orte erscheinen sortiert
+        assert!(json.find("Erster Ort").unwrap() < json.find("Zweiter, Ort").unwrap());
+
+        let parsed = from_json(&json).unwrap();
+        assert_eq!(parsed.deleted_files, 3);
+        assert_eq!(parsed.cleaned_locations.len(), 2);
+        assert!(matches!(&parsed.errors[0], CleaningError::IoError(path, msg) if path == Path::new("/tmp/x") && msg == "kaputt"));
+        assert_eq!(parsed.files[0].outcome, FileOutcome::Deleted);
+    }
+
+    #[test]
+    fn test_csv_outcome_matches_json() {
+        use FileOutcome::*;
+        for outcome in [Planned, Deleted, Quarantined, Trashed, Skipped, Failed] {
+            assert_eq!(serde_json::to_string(&outcome).unwrap(), format!("\"{}\"", outcome.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let summary = sample_summary();
+        let csv = locations_csv(&summary);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "location,deleted_files,total_size,errors,skipped_files");
+        assert_eq!(lines[1], "Erster Ort,2,200,0,4");
+        assert_eq!(lines[2], "\"Zweiter, Ort\",1,100,1,0");
+
+        assert_eq!(files_csv(&summary).lines().nth(1), Some("Erster Ort,\"/tmp/\"\"a\"\".tmp\",200,deleted,"));
+
+        let dir = TempDir::new().unwrap();
+        let written = save_report(&summary, dir.path(), "bericht").unwrap();
+        assert_eq!(written.len(), 3);
+        assert!(written.iter().all(|path| path.exists()));
+    }
+}
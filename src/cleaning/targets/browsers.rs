@@ -0,0 +1,182 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use super::{CleanTarget, CleaningOptions, TargetCategory};
+
+/// Browser-Cache: Profilverzeichnisse × Cache-Unterverzeichnisse
+pub struct BrowserTarget {
+    id: String,
+    name: String,
+    base_paths: Vec<String>,
+    cache_subdirs: Vec<String>,
+    protected: Vec<String>,
+}
+
+impl BrowserTarget {
+    /// Erstellt ein Browser-Ziel; `base_paths` darf `%LOCALAPPDATA%`, `%APPDATA%` und `%USERPROFILE%` enthalten
+    pub fn new(id: &str, name: &str, base_paths: Vec<String>, cache_subdirs: &[&str]) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            base_paths,
+            cache_subdirs: cache_subdirs.iter().map(|s| s.to_string()).collect(),
+            protected: super::DEFAULT_PROTECTED_PATTERNS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    /// Ersetzt die Liste der geschützten Dateien
+    pub fn with_protected(mut self, patterns: &[&str]) -> Self {
+        self.protected = patterns.iter().map(|p| p.to_string()).collect();
+        self
+    }
+}
+
+impl CleanTarget for BrowserTarget {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> TargetCategory {
+        TargetCategory::Browser
+    }
+
+    fn root_paths(&self, _options: &CleaningOptions) -> Vec<PathBuf> {
+        let mut roots = Vec::new();
+        for base_path_template in &self.base_paths {
+            if let Some(base_path) = expand_environment_path(base_path_template) {
+                for cache_subdir in &self.cache_subdirs {
+                    roots.push(base_path.join(cache_subdir));
+                }
+            }
+        }
+        roots
+    }
+
+    fn protected_patterns(&self) -> Vec<String> {
+        self.protected.clone()
+    }
+}
+
+/// Browser-Cache-Informationen mit erweiterten Pfaden
+pub(super) fn builtin_targets() -> Vec<BrowserTarget> {
+    vec![
+        BrowserTarget::new(
+            "chrome-cache",
+            "Chrome Cache",
+            vec![
+                "%LOCALAPPDATA%\\Google\\Chrome\\User Data\\Default".to_string(),
+                "%LOCALAPPDATA%\\Google\\Chrome\\User Data\\Profile 1".to_string(),
+                "%LOCALAPPDATA%\\Google\\Chrome\\User Data\\Profile 2".to_string(),
+            ],
+            &["Cache", "GPUCache", "Code Cache", "Service Worker\\CacheStorage"],
+        )
+        .with_protected(&["index", "data_*"]),
+        BrowserTarget::new(
+            "firefox-cache",
+            "Firefox Cache",
+            get_firefox_profile_paths(),
+            &["cache2", "startupCache", "OfflineCache", "thumbnails"],
+        )
+        .with_protected(&["places.sqlite", "cookies.sqlite", "*.sqlite-wal", "*.sqlite-shm"]),
+        BrowserTarget::new(
+            "edge-cache",
+            "Edge Cache",
+            vec![
+                "%LOCALAPPDATA%\\Microsoft\\Edge\\User Data\\Default".to_string(),
+                "%LOCALAPPDATA%\\Microsoft\\Edge\\User Data\\Profile 1".to_string(),
+            ],
+            &["Cache", "GPUCache", "Code Cache", "Service Worker\\CacheStorage"],
+        )
+        .with_protected(&["index", "data_*"]),
+        BrowserTarget::new(
+            "brave-cache",
+            "Brave Cache",
+            vec![
+                "%LOCALAPPDATA%\\BraveSoftware\\Brave-Browser\\User Data\\Default".to_string(),
+                "%LOCALAPPDATA%\\BraveSoftware\\Brave-Browser\\User Data\\Profile 1".to_string(),
+            ],
+            &["Cache", "GPUCache", "Code Cache"],
+        )
+        .with_protected(&["index", "data_*"]),
+        BrowserTarget::new(
+            "opera-cache",
+            "Opera Cache",
+            vec![
+                "%APPDATA%\\Opera Software\\Opera Stable".to_string(),
+                "%APPDATA%\\Opera Software\\Opera GX Stable".to_string(),
+            ],
+            &["Cache", "GPUCache", "Code Cache"],
+        ),
+        BrowserTarget::new(
+            "vivaldi-cache",
+            "Vivaldi Cache",
+            vec![
+                "%LOCALAPPDATA%\\Vivaldi\\User Data\\Default".to_string(),
+            ],
+            &["Cache", "GPUCache", "Code Cache"],
+        ),
+    ]
+}
+
+/// Firefox-Profile dynamisch ermitteln
+fn get_firefox_profile_paths() -> Vec<String> {
+    let mut paths = Vec::new();
+
+    if let Ok(appdata) = env::var("APPDATA") {
+        let profiles_dir = PathBuf::from(appdata).join("Mozilla\\Firefox\\Profiles");
+        if let Ok(entries) = fs::read_dir(&profiles_dir) {
+            for entry in entries.flatten() {
+                if entry.path().is_dir() {
+                    paths.push(entry.path().to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+
+    if paths.is_empty() {
+        paths.push("%APPDATA%\\Mozilla\\Firefox\\Profiles\\*".to_string());
+    }
+
+    paths
+}
+
+/// Umgebungsvariablen in Pfaden expandieren
+fn expand_environment_path(path_template: &str) -> Option<PathBuf> {
+    let mut expanded = path_template.to_string();
+
+    // Ersetze Umgebungsvariablen
+    if let Ok(localappdata) = env::var("LOCALAPPDATA") {
+        expanded = expanded.replace("%LOCALAPPDATA%", &localappdata);
+    }
+    if let Ok(appdata) = env::var("APPDATA") {
+        expanded = expanded.replace("%APPDATA%", &appdata);
+    }
+    if let Ok(userprofile) = env::var("USERPROFILE") {
+        expanded = expanded.replace("%USERPROFILE%", &userprofile);
+    }
+
+    // Nicht aufgelöste Variablen ergeben keinen gültigen Pfad
+    if expanded.contains('%') {
+        return None;
+    }
+
+    // Wildcard-Unterstützung für Profile
+    if expanded.contains('*') {
+        let parent = PathBuf::from(&expanded.replace("\\*", ""));
+        if let Ok(entries) = fs::read_dir(&parent) {
+            for entry in entries.flatten() {
+                if entry.path().is_dir() {
+                    return Some(entry.path());
+                }
+            }
+        }
+        return None;
+    }
+
+    Some(PathBuf::from(expanded))
+}
@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use super::{CleanTarget, CleaningOptions, TargetCategory};
+
+/// Frei gewähltes Verzeichnis als Ziel, z.B. aus der Speicherplatzanalyse
+///
+/// Es gelten die Optionen des Aufrufers; die Sperrliste schützt wie bei allen Zielen.
+pub struct PathTarget {
+    id: String,
+    name: String,
+    root: PathBuf,
+}
+
+impl PathTarget {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        Self {
+            id: format!("pfad:{}", root.display()),
+            name: root.display().to_string(),
+            root,
+        }
+    }
+}
+
+impl CleanTarget for PathTarget {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> TargetCategory {
+        TargetCategory::User
+    }
+
+    fn root_paths(&self, _options: &CleaningOptions) -> Vec<PathBuf> {
+        vec![self.root.clone()]
+    }
+}
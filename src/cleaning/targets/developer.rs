@@ -0,0 +1,310 @@
+use std::env;
+use std::fs::{self, Metadata};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::{home_dir, CleanTarget, CleaningOptions, TargetCategory};
+
+/// Größte sinnvolle Angabe für `stale_project_days` (100 Jahre)
+pub const MAX_STALE_PROJECT_DAYS: u64 = 36_500;
+
+/// Maximale Tiefe bei der Suche nach Projekten unterhalb eines Projektverzeichnisses
+const PROJECT_SEARCH_DEPTH: usize = 4;
+
+/// Verzeichnisse, die bei der Projektsuche und Aktivitätsprüfung nicht betreten werden
+const IGNORED_PROJECT_DIRS: &[&str] = &["target", ".git", "node_modules", ".idea", ".vscode"];
+
+/// Build-Cache eines Entwicklerwerkzeugs
+pub(super) struct DeveloperTarget {
+    id: &'static str,
+    name: &'static str,
+    roots: fn(&CleaningOptions) -> Vec<PathBuf>,
+    protected: &'static [&'static str],
+    filter: Option<fn(&Path, &Metadata) -> bool>,
+}
+
+impl CleanTarget for DeveloperTarget {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn display_name(&self) -> &str {
+        self.name
+    }
+
+    fn category(&self) -> TargetCategory {
+        TargetCategory::Developer
+    }
+
+    fn root_paths(&self, options: &CleaningOptions) -> Vec<PathBuf> {
+        (self.roots)(options)
+    }
+
+    fn protected_patterns(&self) -> Vec<String> {
+        self.protected.iter().map(|p| p.to_string()).collect()
+    }
+
+    fn accepts(&self, path: &Path, metadata: &Metadata) -> bool {
+        self.filter.map(|filter| filter(path, metadata)).unwrap_or(true)
+    }
+}
+
+/// Alle eingebauten Entwickler-Caches
+pub(super) fn builtin_targets() -> Vec<DeveloperTarget> {
+    vec![
+        DeveloperTarget {
+            id: "cargo-registry-cache",
+            name: "Cargo Registry Cache",
+            roots: |_| cargo_home().map(|cargo| cargo.join("registry").join("cache")).into_iter().collect(),
+            protected: &[],
+            filter: None,
+        },
+        DeveloperTarget {
+            id: "cargo-target-dirs",
+            name: "Veraltete target-Verzeichnisse",
+            roots: stale_cargo_target_dirs,
+            protected: &[],
+            filter: None,
+        },
+        DeveloperTarget {
+            id: "npm-cache",
+            name: "npm Cache",
+            roots: |_| npm_cache_dir().into_iter().collect(),
+            protected: &[],
+            filter: None,
+        },
+        DeveloperTarget {
+            id: "pip-cache",
+            name: "pip Cache",
+            roots: |_| pip_cache_dir().into_iter().collect(),
+            protected: &[],
+            filter: None,
+        },
+        DeveloperTarget {
+            id: "gradle-cache",
+            name: "Gradle Cache",
+            roots: |_| home_dir().map(|home| home.join(".gradle").join("caches")).into_iter().collect(),
+            protected: &["*.lock", "gc.properties"],
+            filter: None,
+        },
+        DeveloperTarget {
+            id: "maven-snapshots",
+            name: "Maven Snapshots",
+            roots: |_| maven_repository().into_iter().collect(),
+            protected: &["maven-metadata*.xml"],
+            filter: Some(is_maven_snapshot_entry),
+        },
+        DeveloperTarget {
+            id: "go-build-cache",
+            name: "Go Build Cache",
+            roots: |_| go_build_cache_dir().into_iter().collect(),
+            protected: &["README", "trim.txt"],
+            filter: None,
+        },
+    ]
+}
+
+/// `$CARGO_HOME` bzw. `~/.cargo`
+fn cargo_home() -> Option<PathBuf> {
+    env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".cargo")))
+}
+
+fn npm_cache_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(|local| PathBuf::from(local).join("npm-cache").join("_cacache"))
+    } else {
+        home_dir().map(|home| home.join(".npm").join("_cacache"))
+    }
+}
+
+fn pip_cache_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(|local| PathBuf::from(local).join("pip").join("Cache"))
+    } else {
+        super::linux::xdg_cache_home().map(|cache| cache.join("pip"))
+    }
+}
+
+/// `$GOCACHE` bzw. der plattformübliche Standardpfad
+fn go_build_cache_dir() -> Option<PathBuf> {
+    match env::var_os("GOCACHE") {
+        // "off" deaktiviert den Cache
+        Some(value) if value == "off" => None,
+        Some(value) => Some(PathBuf::from(value)),
+        None if cfg!(windows) => env::var_os("LOCALAPPDATA").map(|local| PathBuf::from(local).join("go-build")),
+        None => super::linux::xdg_cache_home().map(|cache| cache.join("go-build")),
+    }
+}
+
+/// Lokales Maven-Repository `~/.m2/repository`
+fn maven_repository() -> Option<PathBuf> {
+    home_dir().map(|home| home.join(".m2").join("repository"))
+}
+
+/// Maven: nur Verzeichnisse/Dateien, die in einer `*-SNAPSHOT`-Version liegen
+///
+/// Verzeichnisse oberhalb einer Version (groupId/artifactId) werden betreten,
+/// Dateien nur gelöscht, wenn ein Pfadbestandteil unterhalb des Repositorys auf `-SNAPSHOT` endet.
+fn is_maven_snapshot_entry(path: &Path, metadata: &Metadata) -> bool {
+    metadata.is_dir() || maven_repository().is_some_and(|repository| is_snapshot_path(path, &repository))
+}
+
+/// Liegt `path` in einer Snapshot-Version von `repository`? Bestandteile oberhalb des
+/// Repositorys (z.B. ein Home-Verzeichnis namens `x-SNAPSHOT`) zählen nicht
+fn is_snapshot_path(path: &Path, repository: &Path) -> bool {
+    path.strip_prefix(repository).is_ok_and(|relative| {
+        relative
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().ends_with("-SNAPSHOT"))
+    })
+}
+
+/// `target/`-Verzeichnisse von Cargo-Projekten, die seit `stale_project_days` Tagen unverändert sind
+fn stale_cargo_target_dirs(options: &CleaningOptions) -> Vec<PathBuf> {
+    let max_age = Duration::from_secs(options.stale_project_days.saturating_mul(24 * 60 * 60));
+    let mut dirs = Vec::new();
+
+    for root in &options.project_roots {
+        for project in find_cargo_projects(root, PROJECT_SEARCH_DEPTH) {
+            let target = project.join("target");
+            if target.is_dir() && !project_touched_within(&project, max_age) {
+                dirs.push(target);
+            }
+        }
+    }
+
+    dirs
+}
+
+/// Sucht Verzeichnisse mit `Cargo.toml` und `target/` bis zur angegebenen Tiefe
+fn find_cargo_projects(dir: &Path, depth: usize) -> Vec<PathBuf> {
+    let mut projects = Vec::new();
+    if dir.join("Cargo.toml").is_file() && dir.join("target").is_dir() {
+        projects.push(dir.to_path_buf());
+    }
+
+    if depth == 0 {
+        return projects;
+    }
+
+    for subdir in list_project_subdirs(dir) {
+        projects.extend(find_cargo_projects(&subdir, depth - 1));
+    }
+
+    projects
+}
+
+/// Prüft, ob eine Datei im Projekt (außer Build-Ausgaben) jünger als `max_age` ist
+fn project_touched_within(project: &Path, max_age: Duration) -> bool {
+    let now = SystemTime::now();
+    let is_recent = |metadata: &Metadata| {
+        metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .map(|age| age < max_age)
+            // Zeitstempel in der Zukunft zählen als aktuell
+            .unwrap_or(true)
+    };
+
+    let mut pending = vec![project.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            // Im Zweifel gilt das Projekt als aktiv
+            Err(_) => return true,
+        };
+
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if !is_ignored_project_dir(&entry.path()) {
+                    pending.push(entry.path());
+                }
+            } else if entry.metadata().map(|m| is_recent(&m)).unwrap_or(true) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+fn list_project_subdirs(dir: &Path) -> Vec<PathBuf> {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+            .map(|entry| entry.path())
+            .filter(|path| !is_ignored_project_dir(path))
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn is_ignored_project_dir(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|name| IGNORED_PROJECT_DIRS.contains(&name))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use tempfile::TempDir;
+
+    fn set_mtime_days_ago(path: &Path, days: u64) {
+        let time = SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60);
+        File::options().write(true).open(path).unwrap().set_modified(time).unwrap();
+    }
+
+    fn create_project(root: &Path, name: &str, source_age_days: u64) -> PathBuf {
+        let project = root.join(name);
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::create_dir_all(project.join("target").join("debug")).unwrap();
+        File::create(project.join("Cargo.toml")).unwrap();
+        File::create(project.join("src").join("main.rs")).unwrap();
+        File::create(project.join("target").join("debug").join("app")).unwrap();
+        set_mtime_days_ago(&project.join("Cargo.toml"), source_age_days);
+        set_mtime_days_ago(&project.join("src").join("main.rs"), source_age_days);
+        project
+    }
+
+    #[test]
+    fn test_only_stale_target_dirs_are_selected() {
+        let temp_dir = TempDir::new().unwrap();
+        let stale = create_project(temp_dir.path(), "alt", 60);
+        create_project(&temp_dir.path().join("gruppe"), "aktiv", 0);
+
+        let options = CleaningOptions {
+            project_roots: vec![temp_dir.path().to_path_buf()],
+            stale_project_days: 30,
+            ..CleaningOptions::default()
+        };
+
+        assert_eq!(stale_cargo_target_dirs(&options), vec![stale.join("target")]);
+    }
+
+    #[test]
+    fn test_maven_snapshot_filter() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("lib.jar");
+        File::create(&file).unwrap();
+        let file_meta = fs::metadata(&file).unwrap();
+        let dir_meta = fs::metadata(temp_dir.path()).unwrap();
+
+        assert!(is_maven_snapshot_entry(Path::new("org/app"), &dir_meta));
+        assert!(!is_maven_snapshot_entry(&file, &file_meta));
+
+        let repository = Path::new("/home/build-SNAPSHOT/.m2/repository");
+        assert!(is_snapshot_path(&repository.join("org/app/1.0-SNAPSHOT/app.jar"), repository));
+        assert!(!is_snapshot_path(&repository.join("org/app/1.0/app.jar"), repository));
+        assert!(!is_snapshot_path(Path::new("/anderswo/1.0-SNAPSHOT/app.jar"), repository));
+    }
+}
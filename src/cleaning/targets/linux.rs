@@ -0,0 +1,331 @@
+use std::env;
+use std::fs::{self, Metadata};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::{home_dir, BrowserTarget, CleanTarget, CleaningError, CleaningOptions, TargetCategory};
+use crate::cleaning::plan::{classify_io_error, MatchReason, PlannedFile};
+use crate::cleaning::trash::{payload_size, Trash};
+
+/// Eingebautes Linux-Ziel mit Pfaden nach XDG-Basisverzeichnis-Spezifikation
+pub(super) struct LinuxTarget {
+    id: &'static str,
+    name: &'static str,
+    category: TargetCategory,
+    roots: fn() -> Vec<PathBuf>,
+    /// Nur Einträge des aktuellen Benutzers berücksichtigen (für gemeinsam genutzte Verzeichnisse)
+    owned_by_user: bool,
+}
+
+impl CleanTarget for LinuxTarget {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn display_name(&self) -> &str {
+        self.name
+    }
+
+    fn category(&self) -> TargetCategory {
+        self.category
+    }
+
+    fn root_paths(&self, _options: &CleaningOptions) -> Vec<PathBuf> {
+        (self.roots)()
+    }
+
+    fn accepts(&self, _path: &Path, metadata: &Metadata) -> bool {
+        !self.owned_by_user || is_owned_by_current_user(metadata)
+    }
+}
+
+/// Alle eingebauten Linux-Ziele; der allgemeine Benutzer-Cache steht am Ende
+pub(super) fn builtin_targets() -> Vec<LinuxTarget> {
+    vec![
+        LinuxTarget {
+            id: "linux-thumbnails",
+            name: "Miniaturansichten",
+            category: TargetCategory::User,
+            roots: || xdg_cache_home().map(|cache| cache.join("thumbnails")).into_iter().collect(),
+            owned_by_user: false,
+        },
+        LinuxTarget {
+            id: "linux-tmp",
+            name: "Temporäre Dateien (/tmp)",
+            category: TargetCategory::System,
+            roots: || vec![PathBuf::from("/tmp"), PathBuf::from("/var/tmp")],
+            owned_by_user: true,
+        },
+        LinuxTarget {
+            id: "xdg-cache",
+            name: "Benutzer-Cache",
+            category: TargetCategory::User,
+            roots: || xdg_cache_home().into_iter().collect(),
+            owned_by_user: false,
+        },
+    ]
+}
+
+/// Home-Papierkorb nach der freedesktop.org Trash-Spezifikation
+///
+/// Jeder Eintrag wird samt seiner `.trashinfo` geplant und nach `DeletionDate` gealtert, nicht nach
+/// der Änderungszeit: Die gelöschte Datei behält ihr ursprüngliches, oft altes Datum.
+pub(super) struct TrashTarget;
+
+impl CleanTarget for TrashTarget {
+    fn id(&self) -> &str {
+        "linux-trash"
+    }
+
+    fn display_name(&self) -> &str {
+        "Papierkorb"
+    }
+
+    fn category(&self) -> TargetCategory {
+        TargetCategory::User
+    }
+
+    fn root_paths(&self, _options: &CleaningOptions) -> Vec<PathBuf> {
+        xdg_data_home()
+            .map(|data| vec![data.join("Trash").join("files"), data.join("Trash").join("info")])
+            .unwrap_or_default()
+    }
+
+    fn planned_entries(&self, options: &CleaningOptions) -> Option<Result<Vec<PlannedFile>, CleaningError>> {
+        let trash = Trash::new(xdg_data_home()?.join("Trash"));
+        Some(trash_entries(&trash, self.display_name(), options))
+    }
+}
+
+/// Plant alle Papierkorb-Einträge, die vor mindestens `min_file_age_days` gelöscht wurden
+///
+/// Der Inhalt (Datei oder Verzeichnis) ist der Eintrag, die Info-Datei seine Begleitdatei und wird
+/// damit erst danach entfernt. Ist nur noch die Info-Datei übrig, wird sie allein geplant.
+fn trash_entries(trash: &Trash, location: &str, options: &CleaningOptions) -> Result<Vec<PlannedFile>, CleaningError> {
+    let items = trash.items().map_err(|e| classify_io_error(&trash.root().join("info"), &e))?;
+    let min_age = Duration::from_secs(options.min_file_age_days.saturating_mul(24 * 60 * 60));
+    let now = SystemTime::now();
+    let mut entries = Vec::new();
+
+    for item in items {
+        // Ohne lesbares Löschdatum nur, wenn ohnehin jedes Alter genügt
+        let old_enough = match item.deleted {
+            Some(deleted) => now.duration_since(deleted).is_ok_and(|age| age >= min_age),
+            None => options.min_file_age_days == 0,
+        };
+        if !old_enough {
+            continue;
+        }
+
+        let reason = MatchReason::OlderThan(options.min_file_age_days);
+        let entry = match fs::symlink_metadata(&item.payload) {
+            Ok(metadata) if metadata.is_file() || metadata.is_dir() => PlannedFile {
+                location: location.to_string(),
+                size: payload_size(&item.payload),
+                modified: metadata.modified().ok(),
+                path: item.payload,
+                reason,
+                companion: Some(item.info),
+            },
+            // Symlinks und Sonderdateien bleiben samt Info-Datei liegen
+            Ok(_) => continue,
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => continue,
+            Err(_) => {
+                let Ok(metadata) = fs::metadata(&item.info) else {
+                    continue;
+                };
+                PlannedFile {
+                    location: location.to_string(),
+                    path: item.info,
+                    size: metadata.len(),
+                    modified: metadata.modified().ok(),
+                    reason,
+                    companion: None,
+                }
+            }
+        };
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Browser-Caches unter Linux (Cache-Daten liegen in `$XDG_CACHE_HOME`, GPU-Caches im Profil)
+pub(super) fn builtin_browser_targets() -> Vec<BrowserTarget> {
+    vec![
+        BrowserTarget::new(
+            "chrome-cache",
+            "Chrome Cache",
+            chromium_profile_paths("google-chrome"),
+            &["Cache", "Code Cache", "GPUCache", "Service Worker/CacheStorage"],
+        )
+        .with_protected(&["index", "data_*"]),
+        BrowserTarget::new(
+            "chromium-cache",
+            "Chromium Cache",
+            chromium_profile_paths("chromium"),
+            &["Cache", "Code Cache", "GPUCache", "Service Worker/CacheStorage"],
+        )
+        .with_protected(&["index", "data_*"]),
+        BrowserTarget::new(
+            "firefox-cache",
+            "Firefox Cache",
+            firefox_profile_paths(),
+            &["cache2", "startupCache", "OfflineCache", "thumbnails"],
+        )
+        .with_protected(&["places.sqlite", "cookies.sqlite", "*.sqlite-wal", "*.sqlite-shm"]),
+        BrowserTarget::new(
+            "brave-cache",
+            "Brave Cache",
+            chromium_profile_paths("BraveSoftware/Brave-Browser"),
+            &["Cache", "Code Cache", "GPUCache", "Service Worker/CacheStorage"],
+        )
+        .with_protected(&["index", "data_*"]),
+        BrowserTarget::new(
+            "vivaldi-cache",
+            "Vivaldi Cache",
+            chromium_profile_paths("vivaldi"),
+            &["Cache", "Code Cache", "GPUCache"],
+        )
+        .with_protected(&["index", "data_*"]),
+    ]
+}
+
+/// Profilverzeichnisse eines Chromium-basierten Browsers in Cache- und Konfigurationsverzeichnis
+fn chromium_profile_paths(browser_dir: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    for base in xdg_cache_home().into_iter().chain(xdg_config_home()) {
+        for profile in list_subdirs(&base.join(browser_dir)) {
+            let name = profile.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if name == "Default" || name.starts_with("Profile ") {
+                paths.push(profile.to_string_lossy().to_string());
+            }
+        }
+    }
+    paths
+}
+
+/// Firefox-Profile im Cache-Verzeichnis dynamisch ermitteln
+fn firefox_profile_paths() -> Vec<String> {
+    xdg_cache_home()
+        .map(|cache| list_subdirs(&cache.join("mozilla").join("firefox")))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect()
+}
+
+/// Alle direkten Unterverzeichnisse (keine Symlinks)
+fn list_subdirs(dir: &Path) -> Vec<PathBuf> {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+            .map(|entry| entry.path())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// XDG-Variable, sofern gesetzt und absolut, sonst Standardpfad relativ zu `$HOME`
+fn xdg_dir(variable: &str, fallback: &str) -> Option<PathBuf> {
+    env::var_os(variable)
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| home_dir().map(|home| home.join(fallback)))
+}
+
+/// `$XDG_CACHE_HOME` bzw. `~/.cache`
+pub(crate) fn xdg_cache_home() -> Option<PathBuf> {
+    xdg_dir("XDG_CACHE_HOME", ".cache")
+}
+
+/// `$XDG_DATA_HOME` bzw. `~/.local/share`
+pub(crate) fn xdg_data_home() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", ".local/share")
+}
+
+/// `$XDG_CONFIG_HOME` bzw. `~/.config`
+pub(crate) fn xdg_config_home() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// Gehört die Datei dem Benutzer des laufenden Prozesses?
+///
+/// Die UID kommt direkt vom System statt aus `/proc`, das in Containern oder mit `hidepid` fehlen kann.
+#[cfg(unix)]
+fn is_owned_by_current_user(metadata: &Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    // SAFETY: getuid hat keine Vorbedingungen und kann nicht fehlschlagen
+    metadata.uid() == unsafe { libc::getuid() }
+}
+
+#[cfg(not(unix))]
+fn is_owned_by_current_user(_metadata: &Metadata) -> bool {
+    false
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::cleaning::{execute, CleaningPlan};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_tmp_target_only_accepts_own_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let own = fs::metadata(temp_dir.path()).unwrap();
+        let foreign = fs::metadata("/").unwrap();
+        let tmp = builtin_targets().into_iter().find(|t| t.id == "linux-tmp").unwrap();
+
+        assert!(tmp.accepts(temp_dir.path(), &own));
+        // Wurzelverzeichnis gehört root; bei Tests als root ist der Vergleich nicht aussagekräftig
+        if unsafe { libc::getuid() } != 0 {
+            assert!(!tmp.accepts(Path::new("/"), &foreign));
+        }
+    }
+
+    #[test]
+    fn test_trash_entries_age_by_deletion_date() {
+        let files = TempDir::new().unwrap();
+        let trash_dir = TempDir::new().unwrap();
+        let trash = Trash::new(trash_dir.path());
+
+        // Frisch gelöscht, die Datei selbst ist aber uralt
+        let recent = files.path().join("neu.txt");
+        fs::write(&recent, "neu").unwrap();
+        fs::File::options().write(true).open(&recent).unwrap().set_modified(SystemTime::UNIX_EPOCH).unwrap();
+        let recent = trash.put(&recent).unwrap();
+
+        // Vor Jahren gelöschtes Verzeichnis und eine verwaiste Info-Datei
+        let old = trash.root().join("files").join("alt");
+        fs::create_dir_all(old.join("unter")).unwrap();
+        fs::write(old.join("unter").join("a.txt"), "12345").unwrap();
+        let info = "[Trash Info]\nPath=/alt\nDeletionDate=2020-01-02T03:04:05\n";
+        fs::write(trash.root().join("info").join("alt.trashinfo"), info).unwrap();
+        fs::write(trash.root().join("info").join("weg.trashinfo"), info).unwrap();
+
+        let options = CleaningOptions { min_file_age_days: 30, ..CleaningOptions::default() };
+        let entries = trash_entries(&trash, "Papierkorb", &options).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, old);
+        assert_eq!(entries[0].size, 5);
+        assert_eq!(entries[0].companion, Some(trash.root().join("info").join("alt.trashinfo")));
+        assert_eq!(entries[1].path, trash.root().join("info").join("weg.trashinfo"));
+        assert_eq!(entries[1].companion, None);
+
+        let mut plan = CleaningPlan::new();
+        plan.location_mut("Papierkorb").roots.extend([trash.root().join("files"), trash.root().join("info")]);
+        for entry in entries {
+            plan.push(entry);
+        }
+        let summary = execute(&plan, &options);
+        assert_eq!(summary.deleted_files, 2);
+        assert!(summary.errors.is_empty());
+        assert!(!old.exists());
+        assert!(!trash.root().join("info").join("alt.trashinfo").exists());
+        assert!(!trash.root().join("info").join("weg.trashinfo").exists());
+        assert!(recent.exists());
+        assert!(trash.root().join("info").join("neu.txt.trashinfo").exists());
+    }
+}
@@ -0,0 +1,250 @@
+use std::env;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::plan::PlannedFile;
+use super::temp_files::{CleaningError, CleaningOptions};
+
+mod browsers;
+mod custom;
+mod developer;
+mod linux;
+mod windows;
+
+pub(crate) use linux::{xdg_config_home, xdg_data_home};
+
+pub use browsers::BrowserTarget;
+pub use custom::PathTarget;
+pub use developer::MAX_STALE_PROJECT_DAYS;
+
+/// Geschützte Dateien für Orte ohne eigene Liste
+pub const DEFAULT_PROTECTED_PATTERNS: &[&str] = &[
+    "desktop.ini", "thumbs.db", "ntuser.dat", "iconcache.db", "bootsect.bak", "pagefile.sys",
+];
+
+/// Kategorie eines Bereinigungsziels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetCategory {
+    System,
+    User,
+    Browser,
+    Developer,
+}
+
+impl std::fmt::Display for TargetCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TargetCategory::System => write!(f, "System"),
+            TargetCategory::User => write!(f, "Benutzer"),
+            TargetCategory::Browser => write!(f, "Browser"),
+            TargetCategory::Developer => write!(f, "Entwicklung"),
+        }
+    }
+}
+
+/// Ein Ort, der bereinigt werden kann
+///
+/// Eigene Ziele implementieren dieses Trait und werden über
+/// [`TargetRegistry::register`] bekannt gemacht.
+pub trait CleanTarget: Send + Sync {
+    /// Eindeutige, stabile Kennung (z.B. "chrome-cache")
+    fn id(&self) -> &str;
+
+    /// Anzeigename, unter dem der Ort in der Zusammenfassung erscheint
+    fn display_name(&self) -> &str;
+
+    fn category(&self) -> TargetCategory;
+
+    /// Wurzelverzeichnisse, die durchsucht werden (nicht existierende werden ignoriert)
+    fn root_paths(&self, options: &CleaningOptions) -> Vec<PathBuf>;
+
+    /// Dateinamen/Muster, die an diesem Ort nie gelöscht werden
+    fn protected_patterns(&self) -> Vec<String> {
+        DEFAULT_PROTECTED_PATTERNS.iter().map(|p| p.to_string()).collect()
+    }
+
+    /// Zusätzlicher Filter für Dateien und Verzeichnisse unterhalb der Wurzeln
+    ///
+    /// Abgelehnte Verzeichnisse werden nicht betreten, abgelehnte Dateien übersprungen.
+    fn accepts(&self, _path: &Path, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    /// Fertig geplante Einträge statt eines Scans der Wurzelverzeichnisse
+    ///
+    /// Für Orte, deren Dateien nur gemeinsam gelöscht werden dürfen (Papierkorb-Einträge samt
+    /// `.trashinfo`). Das Alter prüft das Ziel selbst; Sperrliste, Muster und `max_files` der Scan.
+    /// `None` (Standard) durchsucht `root_paths` wie gewohnt.
+    fn planned_entries(&self, _options: &CleaningOptions) -> Option<Result<Vec<PlannedFile>, CleaningError>> {
+        None
+    }
+
+    /// Optionen, die verwendet werden, wenn der Aufrufer keine vorgibt
+    fn default_options(&self) -> CleaningOptions {
+        CleaningOptions::default()
+    }
+}
+
+/// Sammlung aller bekannten Bereinigungsziele
+#[derive(Default)]
+pub struct TargetRegistry {
+    targets: Vec<Box<dyn CleanTarget>>,
+}
+
+impl TargetRegistry {
+    /// Erstellt eine leere Registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry mit allen eingebauten Zielen der aktuellen Plattform
+    ///
+    /// Spezifische Ziele stehen vor allgemeinen (z.B. Browser-Caches vor `$XDG_CACHE_HOME`),
+    /// damit eine Datei dem genauesten Ort zugeordnet wird.
+    pub fn with_builtin_targets() -> Self {
+        Self::builtin(cfg!(windows), cfg!(target_os = "linux"))
+    }
+
+    /// Eingebaute Ziele aller Plattformen, z.B. um Zielnamen in Konfigurationen zu prüfen
+    pub fn with_all_platform_targets() -> Self {
+        Self::builtin(true, true)
+    }
+
+    fn builtin(windows: bool, linux: bool) -> Self {
+        let mut registry = Self::new();
+        if windows {
+            for target in windows::builtin_targets() {
+                registry.register(target);
+            }
+            for target in browsers::builtin_targets() {
+                registry.register(target);
+            }
+        }
+        if linux {
+            for target in linux::builtin_browser_targets() {
+                registry.register(target);
+            }
+        }
+        for target in developer::builtin_targets() {
+            registry.register(target);
+        }
+        if linux {
+            for target in linux::builtin_targets() {
+                registry.register(target);
+            }
+            registry.register(linux::TrashTarget);
+        }
+        registry
+    }
+
+    /// Registriert ein Ziel; ein vorhandenes Ziel mit derselben Kennung wird ersetzt
+    pub fn register<T: CleanTarget + 'static>(&mut self, target: T) {
+        self.register_boxed(Box::new(target));
+    }
+
+    pub fn register_boxed(&mut self, target: Box<dyn CleanTarget>) {
+        match self.targets.iter().position(|t| t.id() == target.id()) {
+            Some(index) => self.targets[index] = target,
+            None => self.targets.push(target),
+        }
+    }
+
+    /// Sucht ein Ziel anhand seiner Kennung oder seines Anzeigenamens
+    pub fn get(&self, name: &str) -> Option<&dyn CleanTarget> {
+        self.targets
+            .iter()
+            .find(|t| t.id().eq_ignore_ascii_case(name) || t.display_name().eq_ignore_ascii_case(name))
+            .map(|t| t.as_ref())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn CleanTarget> {
+        self.targets.iter().map(|t| t.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Wählt Ziele anhand von Kennungen oder Anzeigenamen aus
+    pub fn select<S: AsRef<str>>(&self, names: &[S]) -> Result<Vec<&dyn CleanTarget>, String> {
+        names
+            .iter()
+            .map(|name| {
+                self.get(name.as_ref())
+                    .ok_or_else(|| format!("Unbekanntes Bereinigungsziel: {}", name.as_ref()))
+            })
+            .collect()
+    }
+}
+
+/// Home-Verzeichnis des Benutzers (`$HOME` bzw. `%USERPROFILE%`)
+pub(crate) fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|h| !h.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Datenverzeichnis der Anwendung (`%LOCALAPPDATA%\RustyClean` bzw. `$XDG_DATA_HOME/rustyclean`)
+pub(crate) fn app_data_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(|local| PathBuf::from(local).join("RustyClean"))
+    } else {
+        xdg_data_home().map(|data| data.join("rustyclean"))
+    }
+}
+
+/// Konfigurationsverzeichnis der Anwendung (`%APPDATA%\RustyClean` bzw. `$XDG_CONFIG_HOME/rustyclean`)
+pub(crate) fn app_config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        env::var_os("APPDATA").map(|appdata| PathBuf::from(appdata).join("RustyClean"))
+    } else {
+        xdg_config_home().map(|config| config.join("rustyclean"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestTarget(&'static str);
+
+    impl CleanTarget for TestTarget {
+        fn id(&self) -> &str {
+            self.0
+        }
+
+        fn display_name(&self) -> &str {
+            "Testziel"
+        }
+
+        fn category(&self) -> TargetCategory {
+            TargetCategory::User
+        }
+
+        fn root_paths(&self, _options: &CleaningOptions) -> Vec<PathBuf> {
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_register_and_select() {
+        let mut registry = TargetRegistry::with_builtin_targets();
+        let builtin = registry.len();
+        registry.register(TestTarget("test"));
+        registry.register(TestTarget("test"));
+
+        assert_eq!(registry.len(), builtin + 1);
+        assert!(registry.get("TEST").is_some());
+        assert!(registry.get("Testziel").is_some());
+        assert_eq!(registry.select(&["test", "Testziel"]).unwrap().len(), 2);
+        assert!(registry.select(&["gibt-es-nicht"]).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use std::env;
+use std::path::PathBuf;
+
+use super::{CleanTarget, CleaningOptions, TargetCategory};
+
+/// Eingebautes Windows-Ziel mit festen Pfaden aus Umgebungsvariablen
+pub(super) struct WindowsTarget {
+    id: &'static str,
+    name: &'static str,
+    category: TargetCategory,
+    roots: fn() -> Vec<PathBuf>,
+    protected: Option<&'static [&'static str]>,
+}
+
+impl CleanTarget for WindowsTarget {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn display_name(&self) -> &str {
+        self.name
+    }
+
+    fn category(&self) -> TargetCategory {
+        self.category
+    }
+
+    fn root_paths(&self, _options: &CleaningOptions) -> Vec<PathBuf> {
+        (self.roots)()
+    }
+
+    fn protected_patterns(&self) -> Vec<String> {
+        match self.protected {
+            Some(patterns) => patterns.iter().map(|p| p.to_string()).collect(),
+            None => super::DEFAULT_PROTECTED_PATTERNS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+/// Alle eingebauten Windows Temp-Verzeichnisse
+pub(super) fn builtin_targets() -> Vec<WindowsTarget> {
+    vec![
+        WindowsTarget {
+            id: "windows-temp",
+            name: "Windows Temp",
+            category: TargetCategory::System,
+            roots: || get_windows_temp_dir().into_iter().collect(),
+            protected: Some(&["desktop.ini", "thumbs.db", "*.log"]),
+        },
+        WindowsTarget {
+            id: "user-temp",
+            name: "Benutzer Temp",
+            category: TargetCategory::User,
+            roots: || get_user_temp_dir().into_iter().collect(),
+            protected: Some(&["desktop.ini", "some_app_config"]),
+        },
+        WindowsTarget {
+            id: "windows-prefetch",
+            name: "Windows Prefetch",
+            category: TargetCategory::System,
+            roots: || get_prefetch_dir().into_iter().collect(),
+            protected: Some(&["layout.ini", "pf.txt"]),
+        },
+        WindowsTarget {
+            id: "ie-cache",
+            name: "Internet Explorer Cache",
+            category: TargetCategory::Browser,
+            roots: || get_ie_cache_dir().into_iter().collect(),
+            protected: Some(&["index.dat", "*.dat"]),
+        },
+        WindowsTarget {
+            id: "windows-thumbnails",
+            name: "Windows Miniaturansichten",
+            category: TargetCategory::User,
+            roots: || get_thumbnails_cache_dir().into_iter().collect(),
+            protected: Some(&["thumbcache_*.db", "iconcache_*.db"]),
+        },
+        WindowsTarget {
+            id: "windows-update-cache",
+            name: "Windows Update Cache",
+            category: TargetCategory::System,
+            roots: || get_windows_update_dir().into_iter().collect(),
+            protected: Some(&["*.cab", "*.msu"]),
+        },
+        WindowsTarget {
+            id: "windows-temp-extra",
+            name: "Weitere Windows Temp",
+            category: TargetCategory::System,
+            roots: get_additional_windows_temp_dirs,
+            protected: None,
+        },
+        WindowsTarget {
+            id: "system-temp",
+            name: "System Temp",
+            category: TargetCategory::User,
+            roots: get_system_temp_dirs,
+            protected: None,
+        },
+    ]
+}
+
+/// Zusätzliche Windows Temp-Verzeichnisse
+fn get_additional_windows_temp_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    // Windows Installer Cache
+    if let Ok(windows) = env::var("SystemRoot") {
+        let windows_path = PathBuf::from(&windows);
+        dirs.push(windows_path.join("Installer\\$PatchCache$"));
+        dirs.push(windows_path.join("SoftwareDistribution\\DataStore"));
+        dirs.push(windows_path.join("Logs"));
+    }
+
+    // IIS Logs
+    if let Some(iis_logs) = env::var("SystemDrive").ok().map(|d| PathBuf::from(d).join("inetpub\\logs")) {
+        if iis_logs.exists() {
+            dirs.push(iis_logs);
+        }
+    }
+
+    dirs
+}
+
+/// System-spezifische Temp-Verzeichnisse
+fn get_system_temp_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    // Benutzer-spezifische Temp-Verzeichnisse
+    if let Ok(userprofile) = env::var("USERPROFILE") {
+        let user_path = PathBuf::from(userprofile);
+        dirs.push(user_path.join("AppData\\Local\\Temp"));
+        dirs.push(user_path.join("AppData\\Local\\Microsoft\\Windows\\INetCache"));
+        dirs.push(user_path.join("AppData\\Local\\Microsoft\\Windows\\WebCache"));
+        dirs.push(user_path.join("AppData\\Roaming\\Microsoft\\Windows\\Recent"));
+    }
+
+    dirs
+}
+
+/// Windows System-TEMP mit Umgebungsvariable
+fn get_windows_temp_dir() -> Option<PathBuf> {
+    env::var("SystemRoot")
+        .ok()
+        .map(|root| PathBuf::from(root).join("Temp"))
+}
+
+/// Benutzer-TEMP mit Umgebungsvariable
+fn get_user_temp_dir() -> Option<PathBuf> {
+    env::var("TEMP").ok().map(PathBuf::from)
+}
+
+/// Windows Prefetch-Verzeichnis
+fn get_prefetch_dir() -> Option<PathBuf> {
+    env::var("SystemRoot")
+        .ok()
+        .map(|root| PathBuf::from(root).join("Prefetch"))
+}
+
+/// Internet Explorer Cache
+fn get_ie_cache_dir() -> Option<PathBuf> {
+    env::var("LOCALAPPDATA")
+        .ok()
+        .map(|local_app_data| PathBuf::from(local_app_data).join("Microsoft\\Windows\\Temporary Internet Files"))
+}
+
+/// Windows Miniaturansichten Cache
+fn get_thumbnails_cache_dir() -> Option<PathBuf> {
+    env::var("LOCALAPPDATA")
+        .ok()
+        .map(|local_app_data| PathBuf::from(local_app_data).join("Microsoft\\Windows\\Explorer"))
+}
+
+/// Windows Update-Cache
+fn get_windows_update_dir() -> Option<PathBuf> {
+    env::var("SystemRoot")
+        .ok()
+        .map(|root| PathBuf::from(root).join("SoftwareDistribution\\Download"))
+}
@@ -1,22 +1,33 @@
-use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{SystemTime, Duration};
-use std::collections::HashMap;
-use std::ffi::OsStr;
+use std::collections::{BTreeMap, HashMap};
 
-#[cfg(windows)]
-use windows::Win32::Foundation::{ERROR_SHARING_VIOLATION, ERROR_LOCK_VIOLATION};
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::patterns::{PatternError, PatternSet};
+use super::plan::{execute, execute_with_monitor, CleaningPlan, MatchReason, PlannedFile};
+use super::progress::CleaningMonitor;
+use super::protection::ProtectedPaths;
+use super::targets::{CleanTarget, TargetRegistry};
+use super::walker::{run_workers, worker_count, WorkQueue};
+#[cfg(test)]
+use super::targets::TargetCategory;
 
 /// Verbessertes Error-Handling für Bereinigungsoperationen
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "detail")]
 pub enum CleaningError {
     DirectoryNotFound(PathBuf),
     PermissionDenied(PathBuf),
     FileInUse(PathBuf),
     IoError(PathBuf, String),
     InvalidPath(String),
+    /// Ungültiges Ausschluss-, Einschluss- oder Schutzmuster (Muster, Meldung)
+    InvalidPattern(String, String),
+    /// Pfad steht auf der Sperrliste ([`ProtectedPaths`]) und wurde weder betreten noch gelöscht
+    ProtectedPath(PathBuf),
 }
 
 impl Clone for CleaningError {
@@ -27,6 +38,8 @@ impl Clone for CleaningError {
             CleaningError::FileInUse(path) => CleaningError::FileInUse(path.clone()),
             CleaningError::IoError(path, err) => CleaningError::IoError(path.clone(), err.clone()),
             CleaningError::InvalidPath(msg) => CleaningError::InvalidPath(msg.clone()),
+            CleaningError::InvalidPattern(pattern, msg) => CleaningError::InvalidPattern(pattern.clone(), msg.clone()),
+            CleaningError::ProtectedPath(path) => CleaningError::ProtectedPath(path.clone()),
         }
     }
 }
@@ -44,26 +57,50 @@ impl std::fmt::Display for CleaningError {
                 write!(f, "IO-Fehler bei {}: {}", path.display(), err),
             CleaningError::InvalidPath(msg) => 
                 write!(f, "Ungültiger Pfad: {}", msg),
+            CleaningError::InvalidPattern(pattern, msg) =>
+                write!(f, "Ungültiges Muster \"{}\": {}", pattern, msg),
+            CleaningError::ProtectedPath(path) =>
+                write!(f, "Geschützter Pfad, nicht bereinigt: {}", path.display()),
         }
     }
 }
 
 impl std::error::Error for CleaningError {}
 
+impl From<PatternError> for CleaningError {
+    fn from(error: PatternError) -> Self {
+        CleaningError::InvalidPattern(error.pattern, error.message)
+    }
+}
+
 /// Ergebniszusammenfassung mit erweiterten Metriken
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CleaningSummary {
     pub deleted_files: usize,
     pub total_size: u64,
     pub errors: Vec<CleaningError>,
     pub skipped_files: usize,
+    #[serde(serialize_with = "serialize_sorted")]
     pub cleaned_locations: HashMap<String, LocationSummary>,
     pub processing_time: Duration,
     pub empty_dirs_removed: usize,
+    /// Lauf wurde vorzeitig abgebrochen; die Zahlen beschreiben nur den erledigten Teil
+    pub cancelled: bool,
+    /// Davon in die Quarantäne verschobene Dateien
+    pub quarantined_files: usize,
+    /// Davon in den Papierkorb verschobene Dateien
+    pub trashed_files: usize,
+    /// Größe der in den Papierkorb verschobenen Dateien in Bytes
+    pub trashed_size: u64,
+    /// Quarantäne-Lauf, aus dem die Dateien wiederhergestellt werden können
+    pub quarantine_run_id: Option<String>,
+    /// Ergebnis je Datei (nur mit `CleaningOptions::log_files`)
+    #[serde(default)]
+    pub files: Vec<FileRecord>,
 }
 
 /// Zusammenfassung für einen bestimmten Ort
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LocationSummary {
     pub location_name: String,
     pub deleted_files: usize,
@@ -72,6 +109,70 @@ pub struct LocationSummary {
     pub skipped_files: usize,
 }
 
+/// Was mit einer einzelnen Datei geschehen ist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileOutcome {
+    /// Nur geplant (Dry Run)
+    Planned,
+    Deleted,
+    Quarantined,
+    Trashed,
+    /// Seit dem Scan verändert oder bereits entfernt
+    Skipped,
+    Failed,
+}
+
+impl FileOutcome {
+    /// Sprachunabhängiger Bezeichner wie im JSON-Bericht, z.B. `quarantined`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileOutcome::Planned => "planned",
+            FileOutcome::Deleted => "deleted",
+            FileOutcome::Quarantined => "quarantined",
+            FileOutcome::Trashed => "trashed",
+            FileOutcome::Skipped => "skipped",
+            FileOutcome::Failed => "failed",
+        }
+    }
+}
+
+impl std::fmt::Display for FileOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let text = match self {
+            FileOutcome::Planned => "Geplant",
+            FileOutcome::Deleted => "Gelöscht",
+            FileOutcome::Quarantined => "In Quarantäne",
+            FileOutcome::Trashed => "Im Papierkorb",
+            FileOutcome::Skipped => "Übersprungen",
+            FileOutcome::Failed => "Fehler",
+        };
+        f.write_str(text)
+    }
+}
+
+/// Eintrag im Detailprotokoll einer Bereinigung
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileRecord {
+    pub location: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub outcome: FileOutcome,
+    /// Fehlermeldung bei `FileOutcome::Failed`
+    pub error: Option<String>,
+}
+
+/// Orte nach Namen sortiert ausgeben, damit Berichte verschiedener Läufe vergleichbar sind
+fn serialize_sorted<S: Serializer>(map: &HashMap<String, LocationSummary>, serializer: S) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+impl Default for CleaningSummary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CleaningSummary {
     /// Erstellt eine neue leere Zusammenfassung
     pub fn new() -> Self {
@@ -83,6 +184,12 @@ impl CleaningSummary {
             cleaned_locations: HashMap::new(),
             processing_time: Duration::new(0, 0),
             empty_dirs_removed: 0,
+            cancelled: false,
+            quarantined_files: 0,
+            trashed_files: 0,
+            trashed_size: 0,
+            files: Vec::new(),
+            quarantine_run_id: None,
         }
     }
 
@@ -105,6 +212,11 @@ impl CleaningSummary {
         self.errors.push(error);
     }
 
+    /// Endgültig gelöschte Dateien (ohne Quarantäne und Papierkorb)
+    pub fn hard_deleted_files(&self) -> usize {
+        self.deleted_files - self.quarantined_files - self.trashed_files
+    }
+
     /// Menschlesbare Dateigröße
     pub fn formatted_size(&self) -> String {
         format_bytes(self.total_size)
@@ -121,6 +233,19 @@ impl CleaningSummary {
     }
 }
 
+/// Was mit Dateien geschieht, die bereinigt werden
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeletionMode {
+    /// Endgültig löschen
+    #[default]
+    Delete,
+    /// In ein wiederherstellbares Quarantäne-Verzeichnis verschieben
+    Quarantine,
+    /// In den freedesktop.org-Papierkorb (`~/.local/share/Trash`) verschieben
+    Trash,
+}
+
 /// Erweiterte Optionen für die Bereinigung
 #[derive(Debug, Clone)]
 pub struct CleaningOptions {
@@ -138,12 +263,29 @@ pub struct CleaningOptions {
     pub max_file_size: u64,
     /// Minimale Dateigröße in Bytes (0 = alle)
     pub min_file_size: u64,
-    /// Ausgeschlossene Dateinamen/Muster
+    /// Ausgeschlossene Dateien: Globs auf Dateinamen (`*.log`) oder relative Pfade (`**/Cache/index`)
+    /// sowie `regex:`-Ausdrücke, siehe [`PatternSet`]
     pub excluded_patterns: Vec<String>,
+    /// Nur Dateien, die mindestens einem dieser Muster entsprechen (leer = alle)
+    pub included_patterns: Vec<String>,
     /// Verbose Logging
     pub verbose: bool,
-    /// Dry Run (simulieren ohne zu löschen)
+    /// Dry Run (nur scannen, nichts löschen)
     pub dry_run: bool,
+    /// Ergebnis jeder einzelnen Datei in der Zusammenfassung festhalten (Detailbericht)
+    pub log_files: bool,
+    /// Löschen, in die Quarantäne oder in den Papierkorb verschieben
+    pub deletion_mode: DeletionMode,
+    /// Quarantäne-Verzeichnis (`None` = Standardort im Benutzerprofil)
+    pub quarantine_dir: Option<PathBuf>,
+    /// Tage, nach denen ein Quarantäne-Lauf endgültig gelöscht wird
+    pub quarantine_retention_days: u64,
+    /// Projektverzeichnisse, unter denen nach veralteten Build-Verzeichnissen (`target/`) gesucht wird
+    pub project_roots: Vec<PathBuf>,
+    /// Build-Verzeichnisse nur löschen, wenn das Projekt so viele Tage unverändert ist
+    pub stale_project_days: u64,
+    /// Anzahl paralleler Worker für Scan und Löschung (0 = automatisch)
+    pub worker_threads: usize,
 }
 
 impl Default for CleaningOptions {
@@ -157,30 +299,58 @@ impl Default for CleaningOptions {
             max_file_size: 0,
             min_file_size: 0,
             excluded_patterns: Vec::new(),
+            included_patterns: Vec::new(),
             verbose: false,
             dry_run: false,
+            log_files: false,
+            deletion_mode: DeletionMode::Delete,
+            quarantine_dir: None,
+            quarantine_retention_days: 14,
+            project_roots: Vec::new(),
+            stale_project_days: 30,
+            worker_threads: 0,
         }
     }
 }
 
-/// Browser-spezifische Cache-Informationen
-#[derive(Debug)]
-struct BrowserCacheInfo {
-    name: &'static str,
-    base_paths: Vec<String>,
-    cache_subdirs: Vec<&'static str>,
-}
-
 /// Löscht temporäre Dateien aus standard Verzeichnissen
+///
+/// Verwendet für jedes Ziel dessen eigene Standardoptionen.
 pub fn clean_temp_files() -> Result<CleaningSummary, String> {
-    clean_temp_files_with_options(CleaningOptions::default())
+    Ok(clean_temp_files_with_monitor(None, &CleaningMonitor::default()))
+}
+
+/// Scannt und bereinigt alle eingebauten Ziele mit Fortschrittsmeldungen und Abbruchmöglichkeit
+///
+/// Ohne `options` werden die Standardoptionen des jeweiligen Ziels verwendet. Wird während
+/// des Scans abgebrochen, wird nichts gelöscht; ein Abbruch während der Löschphase liefert
+/// die Zusammenfassung der bis dahin gelöschten Dateien.
+pub fn clean_temp_files_with_monitor(options: Option<CleaningOptions>, monitor: &CleaningMonitor) -> CleaningSummary {
+    let registry = TargetRegistry::with_builtin_targets();
+    let targets: Vec<&dyn CleanTarget> = registry.iter().collect();
+    clean_targets_with_monitor(&targets, options, monitor)
+}
+
+/// Wie [`clean_temp_files_with_monitor`], aber nur für die angegebenen Ziele (z.B. aus einem Profil)
+pub fn clean_targets_with_monitor(
+    targets: &[&dyn CleanTarget],
+    options: Option<CleaningOptions>,
+    monitor: &CleaningMonitor
+) -> CleaningSummary {
+    let plan = scan_targets_with_monitor(targets, options.as_ref(), monitor);
+
+    if plan.cancelled {
+        monitor.finish();
+        return plan.to_cancelled_summary();
+    }
+
+    execute_with_monitor(&plan, &options.unwrap_or_default(), monitor)
 }
 
 /// Löscht temporäre Dateien mit benutzerdefinierten Optionen
+///
+/// Entspricht `scan` gefolgt von `execute`; im Dry-Run-Modus wird nur gescannt.
 pub fn clean_temp_files_with_options(options: CleaningOptions) -> Result<CleaningSummary, String> {
-    let start_time = SystemTime::now();
-    let mut summary = CleaningSummary::new();
-
     if options.verbose {
         println!("Starte Temp-Dateien Bereinigung...");
         if options.dry_run {
@@ -188,591 +358,448 @@ pub fn clean_temp_files_with_options(options: CleaningOptions) -> Result<Cleanin
         }
     }
 
-    // Standard Windows Temp-Verzeichnisse
-    let temp_locations = get_all_temp_locations();
-
-    for (location_name, paths) in temp_locations {
-        let location_files_before = summary.deleted_files;
-        let location_size_before = summary.total_size;
-        let location_errors_before = summary.errors.len();
-        let location_skipped_before = summary.skipped_files;
-        
-        for path in paths {
-            if path.exists() {
-                if let Err(e) = clean_directory_advanced(&path, &location_name, &mut summary, &options) {
-                    summary.add_error(CleaningError::IoError(path.clone(), e));
-                }
-            } else if options.verbose {
-                println!("Verzeichnis nicht gefunden: {}", path.display());
-            }
-        }
-        
-        // Statistiken für diesen Standort speichern
-        let files_cleaned = summary.deleted_files - location_files_before;
-        let size_cleaned = summary.total_size - location_size_before;
-        let errors_count = summary.errors.len() - location_errors_before;
-        let skipped_count = summary.skipped_files - location_skipped_before;
-        
-        if files_cleaned > 0 || errors_count > 0 || skipped_count > 0 {
-            summary.add_location_data(&location_name, files_cleaned, size_cleaned, errors_count, skipped_count);
-        }
-
-        if options.verbose {
-            println!("{}: {} Dateien gelöscht, {} übersprungen, {} Fehler", 
-                location_name, files_cleaned, skipped_count, errors_count);
-        }
-    }
-
-    // Browser-spezifische Bereinigung
-    clean_browser_caches(&mut summary, &options)?;
-
-    if let Ok(elapsed) = start_time.elapsed() {
-        summary.processing_time = elapsed;
-    }
+    let plan = scan(&options);
+    let summary = execute(&plan, &options);
 
     if options.verbose {
         println!("Bereinigung abgeschlossen in {:?}", summary.processing_time);
         println!("Insgesamt: {} Dateien gelöscht, {} übersprungen, {} Fehler", 
             summary.deleted_files, summary.skipped_files, summary.errors.len());
+        if summary.trashed_files > 0 {
+            println!("{} Dateien ({}) in den Papierkorb verschoben", summary.trashed_files, format_bytes(summary.trashed_size));
+        }
+        if let Some(run_id) = &summary.quarantine_run_id {
+            println!("{} Dateien in Quarantäne (Lauf {})", summary.quarantined_files, run_id);
+        }
     }
 
     Ok(summary)
 }
 
-/// Erweiterte Verzeichnisbereinigung mit verbesserter Performance
-fn clean_directory_advanced(
-    dir: &Path,
-    location_name: &str,
-    summary: &mut CleaningSummary,
-    options: &CleaningOptions
-) -> Result<(), String> {
-    if !dir.exists() || !dir.is_dir() {
-        return Err(format!("{} existiert nicht oder ist kein Verzeichnis", dir.display()));
-    }
-
-    // Prüfen, ob maximale Dateianzahl bereits erreicht wurde
-    if options.max_files > 0 && summary.deleted_files >= options.max_files {
-        return Ok(());
-    }
+/// Durchsucht alle eingebauten Ziele, ohne etwas zu löschen
+pub fn scan(options: &CleaningOptions) -> CleaningPlan {
+    let registry = TargetRegistry::with_builtin_targets();
+    let targets: Vec<&dyn CleanTarget> = registry.iter().collect();
+    scan_targets(&targets, Some(options))
+}
 
-    let entries = match fs::read_dir(dir) {
-        Ok(entries) => entries,
-        Err(e) => return Err(format!("Verzeichnis {} konnte nicht gelesen werden: {}", dir.display(), e))
-    };
+/// Durchsucht die angegebenen Ziele, ohne etwas zu löschen
+///
+/// Ohne `options` werden die Standardoptionen des jeweiligen Ziels verwendet.
+pub fn scan_targets(targets: &[&dyn CleanTarget], options: Option<&CleaningOptions>) -> CleaningPlan {
+    scan_targets_with_monitor(targets, options, &CleaningMonitor::default())
+}
 
-    let mut directories_to_process = Vec::new();
+/// Wie [`scan_targets`], meldet aber Fortschritt und bricht auf Anforderung ab (`plan.cancelled`)
+///
+/// Die Ziele werden nacheinander durchsucht, damit überlappende Orte dem zuerst
+/// registrierten Ziel zugeordnet werden; innerhalb eines Ziels arbeiten mehrere Worker parallel.
+pub fn scan_targets_with_monitor(
+    targets: &[&dyn CleanTarget],
+    options: Option<&CleaningOptions>,
+    monitor: &CleaningMonitor
+) -> CleaningPlan {
+    let start_time = SystemTime::now();
+    let mut plan = CleaningPlan::new();
 
-    // Direkte Verarbeitung der Einträge für bessere Performance
-    for entry in entries {
-        let entry = match entry {
-            Ok(entry) => entry,
-        Err(e) => {
-            summary.add_error(CleaningError::IoError(dir.to_path_buf(), e.to_string()));
-            continue;
-        }
-        };
-        
-        let path = entry.path();
-        
-        // Maximale Dateienanzahl prüfen
-        if options.max_files > 0 && summary.deleted_files >= options.max_files {
+    for target in targets {
+        if monitor.is_cancelled() {
+            plan.cancelled = true;
             break;
         }
 
-        if path.is_file() {
-            process_file(&path, location_name, summary, options);
-        } else if path.is_dir() && options.recursive {
-            // Symlinks überspringen
-            let is_symlink = fs::symlink_metadata(&path)
-                .map(|m| m.file_type().is_symlink())
-                .unwrap_or(false);
-            
-            if !is_symlink {
-                directories_to_process.push(path);
-            } else {
-                summary.skipped_files += 1;
+        let target_options = match options {
+            Some(options) => options.clone(),
+            None => target.default_options(),
+        };
+        let filter = match FileFilter::new(&target_options, &target.protected_patterns()) {
+            Ok(filter) => filter,
+            Err(error) => {
+                // Ohne gültige Ausschlüsse lieber nichts anfassen
+                plan.add_error(target.display_name(), error.into());
+                continue;
             }
+        };
+        let context = ScanContext {
+            target: *target,
+            location: target.display_name(),
+            filter,
+            protection: ProtectedPaths::global(),
+            monitor,
+        };
+        monitor.start_location(context.location);
+
+        match target.planned_entries(&target_options) {
+            Some(Ok(entries)) => add_target_entries(&context, entries, &mut plan, &target_options),
+            Some(Err(error)) => plan.add_error(context.location, error),
+            None => scan_target_roots(&context, target.root_paths(&target_options), &mut plan, &target_options),
         }
     }
 
-    // Unterverzeichnisse rekursiv verarbeiten
-    for dir_path in directories_to_process {
-        clean_directory_advanced(&dir_path, location_name, summary, options)?;
+    if let Ok(elapsed) = start_time.elapsed() {
+        plan.scan_time = elapsed;
     }
 
-    // Leere Verzeichnisse entfernen
-    if options.remove_empty_dirs {
-        remove_empty_directories_safe(dir, summary, options)?;
+    if options.map(|o| o.verbose).unwrap_or(false) {
+        println!("Scan abgeschlossen: {} Dateien ({}) zur Löschung vorgesehen",
+            plan.file_count(), format_bytes(plan.total_size()));
     }
 
-    Ok(())
+    plan
 }
 
-/// Verbesserte Dateiverarbeitung
-fn process_file(
-    path: &Path,
-    location_name: &str,
-    summary: &mut CleaningSummary,
-    options: &CleaningOptions
-) {
-    // Überspringe spezielle Systemdateien
-    if should_skip_file_advanced(path, location_name, options) {
-        summary.skipped_files += 1;
-        return;
+/// Zustand, der beim Durchsuchen eines Ziels gleich bleibt
+struct ScanContext<'a> {
+    target: &'a dyn CleanTarget,
+    location: &'a str,
+    filter: FileFilter,
+    protection: &'a ProtectedPaths,
+    monitor: &'a CleaningMonitor,
+}
+
+/// Einmal je Ziel übersetzte Muster aus Optionen und Ziel
+#[derive(Default)]
+struct FileFilter {
+    excluded: PatternSet,
+    included: PatternSet,
+    protected: PatternSet,
+}
+
+impl FileFilter {
+    fn new(options: &CleaningOptions, protected: &[String]) -> Result<Self, PatternError> {
+        Ok(Self {
+            excluded: PatternSet::new(&options.excluded_patterns)?,
+            included: PatternSet::new(&options.included_patterns)?,
+            protected: PatternSet::new(protected)?,
+        })
     }
+}
 
-    // Überprüfe Dateiendung, falls gewünscht
-    if let Some(extensions) = &options.target_extensions {
-        if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-            if !extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)) {
-                summary.skipped_files += 1;
-                return;
+/// Zu durchsuchendes Verzeichnis samt Pfad relativ zur Wurzel des Ziels
+struct ScanDir {
+    path: PathBuf,
+    relative: PathBuf,
+}
+
+/// Ergebnisse eines einzelnen Workers, werden nach dem Scan in den Plan übernommen
+#[derive(Default)]
+struct WorkerScan {
+    entries: Vec<PlannedFile>,
+    skipped: usize,
+    errors: Vec<CleaningError>,
+}
+
+/// Durchsucht alle Wurzeln eines Ziels parallel und übernimmt die Treffer in den Plan
+fn scan_target_roots(context: &ScanContext, roots: Vec<PathBuf>, plan: &mut CleaningPlan, options: &CleaningOptions) {
+    let location_name = context.location;
+    let mut scan_roots: Vec<PathBuf> = Vec::new();
+
+    for root in roots {
+        if !root.is_dir() {
+            if options.verbose {
+                println!("Verzeichnis nicht gefunden: {}", root.display());
             }
-        } else {
-            summary.skipped_files += 1;
-            return;
+            continue;
+        }
+        // Auch das Ziel eines Symlinks prüfen, damit die Sperrliste nicht umgangen wird
+        let checked = context.protection.check_dir(&root).and_then(|()| match fs::canonicalize(&root) {
+            Ok(resolved) => context.protection.check_dir(&resolved),
+            Err(_) => Ok(()),
+        });
+        if let Err(error) = checked {
+            plan.add_error(location_name, error);
+            continue;
         }
+        // Verschachtelte oder doppelte Wurzeln nur einmal durchsuchen
+        if scan_roots.iter().any(|existing| root.starts_with(existing)) {
+            continue;
+        }
+        scan_roots.retain(|existing| !existing.starts_with(&root));
+        scan_roots.push(root);
     }
 
-    // Dateigröße prüfen
-    let metadata = match fs::metadata(path) {
-        Ok(metadata) => metadata,
-        Err(e) => {
-            summary.add_error(CleaningError::IoError(path.to_path_buf(), e.to_string()));
+    if scan_roots.is_empty() {
+        return;
+    }
+    plan.location_mut(location_name).roots.extend(scan_roots.iter().cloned());
+
+    // Plätze für max_files werden vor dem Aufnehmen reserviert, damit das Limit exakt gilt
+    let reserved = AtomicUsize::new(plan.file_count());
+    let claimed = &*plan;
+    let queue = WorkQueue::new(scan_roots.into_iter().map(|path| ScanDir { path, relative: PathBuf::new() }));
+    let results = run_workers(&queue, worker_count(options.worker_threads), |dir, queue, local: &mut WorkerScan| {
+        if context.monitor.is_cancelled() || limit_reached(&reserved, options) {
+            queue.close();
             return;
         }
-    };
-
-    let file_size = metadata.len();
+        scan_directory(&dir, context, claimed, &reserved, queue, local, options);
+    });
 
-    // Größenfilter anwenden
-    if options.max_file_size > 0 && file_size > options.max_file_size {
-        summary.skipped_files += 1;
-        return;
+    for result in results {
+        for entry in result.entries {
+            plan.push(entry);
+        }
+        plan.add_skipped(location_name, result.skipped);
+        for error in result.errors {
+            plan.add_error(location_name, error);
+        }
     }
 
-    if options.min_file_size > 0 && file_size < options.min_file_size {
-        summary.skipped_files += 1;
-        return;
+    if context.monitor.is_cancelled() {
+        plan.cancelled = true;
     }
+}
 
-    // Nur Dateien löschen, die älter als die angegebene Zeit sind
-    if !is_file_older_than_days(path, options.min_file_age_days).unwrap_or(false) {
-        summary.skipped_files += 1;
-        return;
-    }
+/// Übernimmt die vom Ziel selbst geplanten Einträge; jeder Eintrag zählt samt Begleitdatei als eine Datei
+fn add_target_entries(context: &ScanContext, entries: Vec<PlannedFile>, plan: &mut CleaningPlan, options: &CleaningOptions) {
+    let location_name = context.location;
+    let roots = context.target.root_paths(options).into_iter().filter(|root| root.is_dir());
+    plan.location_mut(location_name).roots.extend(roots);
 
-    // Datei löschen oder simulieren
-    if options.dry_run {
-        if options.verbose {
-            println!("DRY RUN: Würde löschen: {}", path.display());
+    for entry in entries {
+        if context.monitor.is_cancelled() {
+            plan.cancelled = true;
+            return;
         }
-        summary.deleted_files += 1;
-        summary.total_size += file_size;
-    } else {
-        match fs::remove_file(path) {
-            Ok(()) => {
-                summary.deleted_files += 1;
-                summary.total_size += file_size;
-                if options.verbose {
-                    println!("Gelöscht: {}", path.display());
-                }
-            },
-            Err(e) => {
-                if is_file_in_use_error(&e) {
-                    summary.add_error(CleaningError::FileInUse(path.to_path_buf()));
-                } else if e.kind() == io::ErrorKind::PermissionDenied {
-                    summary.add_error(CleaningError::PermissionDenied(path.to_path_buf()));
-                } else {
-                    summary.add_error(CleaningError::IoError(path.to_path_buf(), e.to_string()));
-                }
-                summary.skipped_files += 1;
-            }
+        if options.max_files > 0 && plan.file_count() >= options.max_files {
+            return;
         }
+        context.monitor.file_scanned(&entry.path);
+
+        let checked = context.protection.check(&entry.path).and_then(|()| match &entry.companion {
+            Some(companion) => context.protection.check(companion),
+            None => Ok(()),
+        });
+        if let Err(error) = checked {
+            plan.add_error(location_name, error);
+            continue;
+        }
+        let relative = entry.path.file_name().map(PathBuf::from).unwrap_or_default();
+        if should_skip_file_advanced(&relative, &context.filter) {
+            plan.add_skipped(location_name, 1);
+            continue;
+        }
+
+        if options.verbose && options.dry_run {
+            println!("DRY RUN: Würde löschen: {}", entry.path.display());
+        }
+        plan.push(entry);
     }
 }
 
-/// Erweiterte Dateifilterung mit Pattern-Matching
-fn should_skip_file_advanced(path: &Path, location_name: &str, options: &CleaningOptions) -> bool {
-    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-    
-    // Versteckte Dateien überspringen
-    if file_name.starts_with('.') {
-        return true;
-    }
+fn limit_reached(reserved: &AtomicUsize, options: &CleaningOptions) -> bool {
+    options.max_files > 0 && reserved.load(Ordering::Relaxed) >= options.max_files
+}
 
-    // Benutzerdefinierte Ausschlussmuster
-    for pattern in &options.excluded_patterns {
-        if matches_pattern(file_name, pattern) {
-            return true;
-        }
-    }
-    
-    // Location-spezifische geschützte Dateien
-    let protected_files: &[&str] = match location_name {
-        "Windows Temp" => &["desktop.ini", "thumbs.db", "*.log"],
-        "Benutzer Temp" => &["desktop.ini", "some_app_config"],
-        "Windows Prefetch" => &["layout.ini", "pf.txt"],
-        "Internet Explorer Cache" => &["index.dat", "*.dat"],
-        "Chrome Cache" => &["index", "data_*"],
-        "Firefox Cache" => &["places.sqlite", "cookies.sqlite", "*.sqlite-wal", "*.sqlite-shm"],
-        "Edge Cache" => &["index", "data_*"],
-        "Brave Cache" => &["index", "data_*"],
-        "Windows Miniaturansichten" => &["thumbcache_*.db", "iconcache_*.db"],
-        "Windows Update Cache" => &["*.cab", "*.msu"],
-        _ => &["desktop.ini", "thumbs.db", "ntuser.dat", "iconcache.db", "bootsect.bak", "pagefile.sys"],
-    };
-    
-    protected_files.iter().any(|pattern| matches_pattern(file_name, pattern))
-}
-
-/// Pattern-Matching mit Wildcard-Unterstützung
-fn matches_pattern(filename: &str, pattern: &str) -> bool {
-    if pattern.contains('*') {
-        let parts: Vec<&str> = pattern.split('*').collect();
-        match parts.len() {
-            1 => filename.eq_ignore_ascii_case(pattern),
-            2 => {
-                filename.to_lowercase().starts_with(&parts[0].to_lowercase()) && 
-                filename.to_lowercase().ends_with(&parts[1].to_lowercase())
-            },
-            _ => {
-                // Komplexere Wildcard-Muster (vereinfacht)
-                let pattern_lower = pattern.to_lowercase();
-                let filename_lower = filename.to_lowercase();
-                
-                let mut pattern_chars = pattern_lower.chars().peekable();
-                let mut filename_chars = filename_lower.chars().peekable();
-                
-                while let Some(p_char) = pattern_chars.next() {
-                    if p_char == '*' {
-                        if pattern_chars.peek().is_none() {
-                            return true; // Pattern endet mit *, passt zu allem
-                        }
-                        // Vereinfachte Wildcard-Logik
-                        while filename_chars.peek().is_some() {
-                            let remaining_filename: String = filename_chars.clone().collect();
-                            let remaining_pattern: String = pattern_chars.clone().collect();
-                            if matches_pattern(&remaining_filename, &remaining_pattern) {
-                                return true;
-                            }
-                            filename_chars.next();
-                        }
-                        return false;
-                    } else if let Some(f_char) = filename_chars.next() {
-                        if p_char != f_char {
-                            return false;
-                        }
-                    } else {
-                        return false;
-                    }
-                }
-                
-                filename_chars.peek().is_none()
-            }
-        }
-    } else {
-        filename.eq_ignore_ascii_case(pattern)
+/// Reserviert einen Platz im Plan; schlägt fehl, wenn `max_files` erreicht ist
+fn reserve_slot(reserved: &AtomicUsize, options: &CleaningOptions) -> bool {
+    if options.max_files == 0 {
+        reserved.fetch_add(1, Ordering::Relaxed);
+        return true;
     }
+    reserved
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| (n < options.max_files).then_some(n + 1))
+        .is_ok()
 }
 
-/// Sichere Entfernung leerer Verzeichnisse
-fn remove_empty_directories_safe(
-    dir: &Path,
-    summary: &mut CleaningSummary,
+/// Durchsucht ein Verzeichnis: Dateien werden geprüft, Unterverzeichnisse in die Warteschlange gelegt
+fn scan_directory(
+    dir: &ScanDir,
+    context: &ScanContext,
+    claimed: &CleaningPlan,
+    reserved: &AtomicUsize,
+    queue: &WorkQueue<ScanDir>,
+    local: &mut WorkerScan,
     options: &CleaningOptions
-) -> Result<(), String> {
-    let entries = match fs::read_dir(dir) {
+) {
+    let entries = match fs::read_dir(&dir.path) {
         Ok(entries) => entries,
-        Err(_) => return Ok(()), // Verzeichnis ist möglicherweise bereits gelöscht
+        Err(e) => {
+            local.errors.push(CleaningError::IoError(
+                dir.path.clone(),
+                format!("Verzeichnis konnte nicht gelesen werden: {}", e),
+            ));
+            return;
+        }
     };
 
     for entry in entries {
-        let entry = entry.map_err(|e| format!("Fehler beim Lesen des Eintrags: {}", e))?;
-        let path = entry.path();
-        
-        if path.is_dir() {
-            // Rekursiv in Unterverzeichnisse
-            remove_empty_directories_safe(&path, summary, options)?;
-            
-            // Prüfen, ob Verzeichnis jetzt leer ist
-            if is_directory_empty(&path).unwrap_or(false) {
-                if options.dry_run {
-                    if options.verbose {
-                        println!("DRY RUN: Würde leeres Verzeichnis löschen: {}", path.display());
-                    }
-                    summary.empty_dirs_removed += 1;
-                } else {
-                    match fs::remove_dir(&path) {
-                        Ok(()) => {
-                            summary.empty_dirs_removed += 1;
-                            if options.verbose {
-                                println!("Leeres Verzeichnis entfernt: {}", path.display());
-                            }
-                        },
-                        Err(e) => {
-                            summary.add_error(CleaningError::IoError(path, e.to_string()));
-                        }
-                    }
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                local.errors.push(CleaningError::IoError(dir.path.clone(), e.to_string()));
+                continue;
+            }
+        };
+
+        if context.monitor.is_cancelled() || limit_reached(reserved, options) {
+            queue.close();
+            return;
+        }
+
+        // Dateityp stammt aus dem Verzeichniseintrag und kostet keinen weiteren Systemaufruf
+        let file_type = match entry.file_type() {
+            Ok(file_type) => file_type,
+            Err(e) => {
+                local.errors.push(CleaningError::IoError(entry.path(), e.to_string()));
+                continue;
+            }
+        };
+
+        // Symlinks überspringen
+        if file_type.is_symlink() {
+            local.skipped += 1;
+        } else if file_type.is_dir() {
+            if options.recursive {
+                let path = entry.path();
+                if let Err(error) = context.protection.check_dir(&path) {
+                    local.errors.push(error);
+                    continue;
+                }
+                // Vom Ziel abgelehnte Verzeichnisse nicht betreten
+                match entry.metadata() {
+                    Ok(metadata) if context.target.accepts(&path, &metadata) => queue.push(ScanDir {
+                        relative: dir.relative.join(entry.file_name()),
+                        path,
+                    }),
+                    Ok(_) => local.skipped += 1,
+                    Err(e) => local.errors.push(CleaningError::IoError(path, e.to_string())),
                 }
             }
+        } else if file_type.is_file() {
+            scan_file(&entry, &dir.relative.join(entry.file_name()), context, claimed, reserved, local, options);
         }
     }
-    
-    Ok(())
 }
 
-/// Alle Temp-Verzeichnisse mit erweiterten Browser-Caches
-fn get_all_temp_locations() -> HashMap<String, Vec<PathBuf>> {
-    let mut locations = HashMap::new();
-    
-    // Standard Windows Temp-Verzeichnisse
-    if let Some(dir) = get_windows_temp_dir() {
-        locations.insert("Windows Temp".to_string(), vec![dir]);
-    }
-    
-    if let Some(dir) = get_user_temp_dir() {
-        locations.insert("Benutzer Temp".to_string(), vec![dir]);
-    }
-    
-    if let Some(dir) = get_prefetch_dir() {
-        locations.insert("Windows Prefetch".to_string(), vec![dir]);
-    }
-    
-    if let Some(dir) = get_ie_cache_dir() {
-        locations.insert("Internet Explorer Cache".to_string(), vec![dir]);
-    }
-    
-    if let Some(dir) = get_thumbnails_cache_dir() {
-        locations.insert("Windows Miniaturansichten".to_string(), vec![dir]);
-    }
-    
-    if let Some(dir) = get_windows_update_dir() {
-        locations.insert("Windows Update Cache".to_string(), vec![dir]);
+/// Prüft eine einzelne Datei und nimmt sie bei Bedarf auf (genau eine Metadaten-Abfrage)
+fn scan_file(
+    entry: &fs::DirEntry,
+    relative: &Path,
+    context: &ScanContext,
+    claimed: &CleaningPlan,
+    reserved: &AtomicUsize,
+    local: &mut WorkerScan,
+    options: &CleaningOptions
+) {
+    let path = entry.path();
+    context.monitor.file_scanned(&path);
+
+    // Bereits von einem anderen Ziel erfasst
+    if claimed.contains(&path) {
+        return;
     }
 
-    // Zusätzliche Windows-spezifische Temp-Verzeichnisse
-    if let Some(dirs) = get_additional_windows_temp_dirs() {
-        locations.insert("Weitere Windows Temp".to_string(), dirs);
+    if let Err(error) = context.protection.check(&path) {
+        local.errors.push(error);
+        return;
     }
 
-    // System-spezifische Temp-Verzeichnisse
-    if let Some(dirs) = get_system_temp_dirs() {
-        locations.insert("System Temp".to_string(), dirs);
+    // Überspringe spezielle Systemdateien
+    if should_skip_file_advanced(relative, &context.filter) {
+        local.skipped += 1;
+        return;
     }
-    
-    locations
-}
 
-/// Browser-spezifische Cache-Bereinigung
-fn clean_browser_caches(summary: &mut CleaningSummary, options: &CleaningOptions) -> Result<(), String> {
-    let browsers = get_browser_cache_info();
-    
-    for browser in browsers {
-        let location_files_before = summary.deleted_files;
-        let location_size_before = summary.total_size;
-        let location_errors_before = summary.errors.len();
-        let location_skipped_before = summary.skipped_files;
-        
-        for base_path_template in &browser.base_paths {
-            if let Some(base_path) = expand_environment_path(base_path_template) {
-                for cache_subdir in &browser.cache_subdirs {
-                    let cache_path = base_path.join(cache_subdir);
-                    if cache_path.exists() {
-                        if let Err(e) = clean_directory_advanced(&cache_path, browser.name, summary, options) {
-                            summary.add_error(CleaningError::IoError(cache_path, e));
-                        }
-                    }
-                }
+    // Überprüfe Dateiendung, falls gewünscht
+    let mut matched_extension = None;
+    if let Some(extensions) = &options.target_extensions {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)) => {
+                matched_extension = Some(ext.to_lowercase());
+            }
+            _ => {
+                local.skipped += 1;
+                return;
             }
-        }
-        
-        // Statistiken für diesen Browser
-        let files_cleaned = summary.deleted_files - location_files_before;
-        let size_cleaned = summary.total_size - location_size_before;
-        let errors_count = summary.errors.len() - location_errors_before;
-        let skipped_count = summary.skipped_files - location_skipped_before;
-        
-        if files_cleaned > 0 || errors_count > 0 || skipped_count > 0 {
-            summary.add_location_data(browser.name, files_cleaned, size_cleaned, errors_count, skipped_count);
         }
     }
-    
-    Ok(())
-}
-
-/// Browser-Cache-Informationen mit erweiterten Pfaden
-fn get_browser_cache_info() -> Vec<BrowserCacheInfo> {
-    vec![
-        BrowserCacheInfo {
-            name: "Chrome Cache",
-            base_paths: vec![
-                "%LOCALAPPDATA%\\Google\\Chrome\\User Data\\Default".to_string(),
-                "%LOCALAPPDATA%\\Google\\Chrome\\User Data\\Profile 1".to_string(),
-                "%LOCALAPPDATA%\\Google\\Chrome\\User Data\\Profile 2".to_string(),
-            ],
-            cache_subdirs: vec!["Cache", "GPUCache", "Code Cache", "Service Worker\\CacheStorage"],
-        },
-        BrowserCacheInfo {
-            name: "Firefox Cache",
-            base_paths: get_firefox_profile_paths(),
-            cache_subdirs: vec!["cache2", "startupCache", "OfflineCache", "thumbnails"],
-        },
-        BrowserCacheInfo {
-            name: "Edge Cache",
-            base_paths: vec![
-                "%LOCALAPPDATA%\\Microsoft\\Edge\\User Data\\Default".to_string(),
-                "%LOCALAPPDATA%\\Microsoft\\Edge\\User Data\\Profile 1".to_string(),
-            ],
-            cache_subdirs: vec!["Cache", "GPUCache", "Code Cache", "Service Worker\\CacheStorage"],
-        },
-        BrowserCacheInfo {
-            name: "Brave Cache",
-            base_paths: vec![
-                "%LOCALAPPDATA%\\BraveSoftware\\Brave-Browser\\User Data\\Default".to_string(),
-                "%LOCALAPPDATA%\\BraveSoftware\\Brave-Browser\\User Data\\Profile 1".to_string(),
-            ],
-            cache_subdirs: vec!["Cache", "GPUCache", "Code Cache"],
-        },
-        BrowserCacheInfo {
-            name: "Opera Cache",
-            base_paths: vec![
-                "%APPDATA%\\Opera Software\\Opera Stable".to_string(),
-                "%APPDATA%\\Opera Software\\Opera GX Stable".to_string(),
-            ],
-            cache_subdirs: vec!["Cache", "GPUCache", "Code Cache"],
-        },
-        BrowserCacheInfo {
-            name: "Vivaldi Cache",
-            base_paths: vec![
-                "%LOCALAPPDATA%\\Vivaldi\\User Data\\Default".to_string(),
-            ],
-            cache_subdirs: vec!["Cache", "GPUCache", "Code Cache"],
-        },
-    ]
-}
 
-/// Firefox-Profile dynamisch ermitteln
-fn get_firefox_profile_paths() -> Vec<String> {
-    let mut paths = Vec::new();
-    
-    if let Ok(appdata) = env::var("APPDATA") {
-        let profiles_dir = PathBuf::from(appdata).join("Mozilla\\Firefox\\Profiles");
-        if let Ok(entries) = fs::read_dir(&profiles_dir) {
-            for entry in entries.flatten() {
-                if entry.path().is_dir() {
-                    paths.push(entry.path().to_string_lossy().to_string());
-                }
-            }
+    let metadata = match entry.metadata() {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            local.errors.push(CleaningError::IoError(path, e.to_string()));
+            return;
         }
-    }
-    
-    if paths.is_empty() {
-        paths.push("%APPDATA%\\Mozilla\\Firefox\\Profiles\\*".to_string());
-    }
-    
-    paths
-}
+    };
 
-/// Umgebungsvariablen in Pfaden expandieren
-fn expand_environment_path(path_template: &str) -> Option<PathBuf> {
-    let mut expanded = path_template.to_string();
-    
-    // Ersetze Umgebungsvariablen
-    if let Ok(localappdata) = env::var("LOCALAPPDATA") {
-        expanded = expanded.replace("%LOCALAPPDATA%", &localappdata);
-    }
-    if let Ok(appdata) = env::var("APPDATA") {
-        expanded = expanded.replace("%APPDATA%", &appdata);
+    // Zielspezifischer Filter (z.B. Eigentümer)
+    if !context.target.accepts(&path, &metadata) {
+        local.skipped += 1;
+        return;
     }
-    if let Ok(userprofile) = env::var("USERPROFILE") {
-        expanded = expanded.replace("%USERPROFILE%", &userprofile);
+
+    let file_size = metadata.len();
+
+    // Größenfilter anwenden
+    if options.max_file_size > 0 && file_size > options.max_file_size {
+        local.skipped += 1;
+        return;
     }
-    
-    // Wildcard-Unterstützung für Profile
-    if expanded.contains('*') {
-        let parent = PathBuf::from(&expanded.replace("\\*", ""));
-        if let Ok(entries) = fs::read_dir(&parent) {
-            for entry in entries.flatten() {
-                if entry.path().is_dir() {
-                    return Some(entry.path());
-                }
-            }
-        }
-        return None;
+
+    if options.min_file_size > 0 && file_size < options.min_file_size {
+        local.skipped += 1;
+        return;
     }
-    
-    Some(PathBuf::from(expanded))
-}
 
-/// Zusätzliche Windows Temp-Verzeichnisse
-fn get_additional_windows_temp_dirs() -> Option<Vec<PathBuf>> {
-    let mut dirs = Vec::new();
-    
-    // Windows Installer Cache
-    if let Ok(windows) = env::var("SystemRoot") {
-        let windows_path = PathBuf::from(&windows);
-        dirs.push(windows_path.join("Installer\\$PatchCache$"));
-        dirs.push(windows_path.join("SoftwareDistribution\\DataStore"));
-        dirs.push(windows_path.join("Logs"));
+    // Nur Dateien aufnehmen, die älter als die angegebene Zeit sind
+    if !is_file_older_than_days(&metadata, options.min_file_age_days) {
+        local.skipped += 1;
+        return;
     }
-    
-    // IIS Logs
-    if let Some(iis_logs) = env::var("SystemDrive").ok().map(|d| PathBuf::from(d).join("inetpub\\logs")) {
-        if iis_logs.exists() {
-            dirs.push(iis_logs);
-        }
+
+    if !reserve_slot(reserved, options) {
+        return;
     }
-    
-    if dirs.is_empty() { None } else { Some(dirs) }
-}
 
-/// System-spezifische Temp-Verzeichnisse
-fn get_system_temp_dirs() -> Option<Vec<PathBuf>> {
-    let mut dirs = Vec::new();
-    
-    // Benutzer-spezifische Temp-Verzeichnisse
-    if let Ok(userprofile) = env::var("USERPROFILE") {
-        let user_path = PathBuf::from(userprofile);
-        dirs.push(user_path.join("AppData\\Local\\Temp"));
-        dirs.push(user_path.join("AppData\\Local\\Microsoft\\Windows\\INetCache"));
-        dirs.push(user_path.join("AppData\\Local\\Microsoft\\Windows\\WebCache"));
-        dirs.push(user_path.join("AppData\\Roaming\\Microsoft\\Windows\\Recent"));
+    if options.verbose && options.dry_run {
+        println!("DRY RUN: Würde löschen: {}", path.display());
     }
-    
-    if dirs.is_empty() { None } else { Some(dirs) }
-}
 
-/// Prüft, ob ein Verzeichnis leer ist
-fn is_directory_empty(dir: &Path) -> io::Result<bool> {
-    Ok(fs::read_dir(dir)?.next().is_none())
+    local.entries.push(PlannedFile {
+        location: context.location.to_string(),
+        path,
+        size: file_size,
+        modified: metadata.modified().ok(),
+        reason: match matched_extension {
+            Some(ext) => MatchReason::Extension(ext),
+            None => MatchReason::OlderThan(options.min_file_age_days),
+        },
+        companion: None,
+    });
 }
 
-/// Prüft, ob eine Datei älter als eine bestimmte Anzahl Tage ist
-fn is_file_older_than_days(path: &Path, days: u64) -> io::Result<bool> {
-    let metadata = fs::metadata(path)?;
-    let modified = metadata.modified()?;
-    let now = SystemTime::now();
+/// Erweiterte Dateifilterung mit Pattern-Matching auf dem Pfad relativ zur Wurzel des Ziels
+fn should_skip_file_advanced(relative: &Path, filter: &FileFilter) -> bool {
+    let file_name = relative.file_name().and_then(|n| n.to_str()).unwrap_or("");
     
-    match now.duration_since(modified) {
-        Ok(duration) => Ok(duration >= Duration::from_secs(days * 24 * 60 * 60)),
-        Err(_) => Ok(false), // Falls die Uhr zurückgesetzt wurde
+    // Versteckte Dateien überspringen
+    if file_name.starts_with('.') {
+        return true;
     }
-}
 
-/// Verbesserte Prüfung für "Datei in Benutzung" Fehler mit Windows-Konstanten
-#[cfg(windows)]
-fn is_file_in_use_error(error: &io::Error) -> bool {
-    match error.raw_os_error() {
-        Some(err) if err == ERROR_SHARING_VIOLATION.0 as i32 => true,
-        Some(err) if err == ERROR_LOCK_VIOLATION.0 as i32 => true,
-        _ => error.kind() == io::ErrorKind::PermissionDenied,
+    // Benutzerdefinierte Ein- und Ausschlussmuster
+    if filter.excluded.is_match(relative) {
+        return true;
+    }
+    if !filter.included.is_empty() && !filter.included.is_match(relative) {
+        return true;
     }
+    
+    // Vom Ziel geschützte Dateien
+    filter.protected.is_match(relative)
 }
 
-#[cfg(not(windows))]
-fn is_file_in_use_error(error: &io::Error) -> bool {
-    error.kind() == io::ErrorKind::PermissionDenied
+/// Prüft anhand der Metadaten, ob eine Datei älter als eine bestimmte Anzahl Tage ist
+fn is_file_older_than_days(metadata: &fs::Metadata, days: u64) -> bool {
+    let Ok(modified) = metadata.modified() else {
+        return false;
+    };
+
+    match SystemTime::now().duration_since(modified) {
+        Ok(duration) => duration >= Duration::from_secs(days * 24 * 60 * 60),
+        Err(_) => false, // Falls die Uhr zurückgesetzt wurde
+    }
 }
 
 /// Formatiert Bytes in eine lesbare Größe
@@ -793,50 +820,6 @@ pub fn format_bytes(bytes: u64) -> String {
     }
 }
 
-/// Windows System-TEMP mit Umgebungsvariable
-fn get_windows_temp_dir() -> Option<PathBuf> {
-    env::var("SystemRoot")
-        .ok()
-        .map(|root| PathBuf::from(root).join("Temp"))
-}
-
-/// Benutzer-TEMP mit Umgebungsvariable
-fn get_user_temp_dir() -> Option<PathBuf> {
-    env::var("TEMP").ok().map(PathBuf::from)
-}
-
-/// Windows Prefetch-Verzeichnis
-fn get_prefetch_dir() -> Option<PathBuf> {
-    env::var("SystemRoot")
-        .ok()
-        .map(|root| PathBuf::from(root).join("Prefetch"))
-}
-
-/// Internet Explorer Cache
-fn get_ie_cache_dir() -> Option<PathBuf> {
-    if let Some(local_app_data) = env::var("LOCALAPPDATA").ok().map(PathBuf::from) {
-        Some(local_app_data.join("Microsoft\\Windows\\Temporary Internet Files"))
-    } else {
-        None
-    }
-}
-
-/// Windows Miniaturansichten Cache
-fn get_thumbnails_cache_dir() -> Option<PathBuf> {
-    if let Some(local_app_data) = env::var("LOCALAPPDATA").ok().map(PathBuf::from) {
-        Some(local_app_data.join("Microsoft\\Windows\\Explorer"))
-    } else {
-        None
-    }
-}
-
-/// Windows Update-Cache
-fn get_windows_update_dir() -> Option<PathBuf> {
-    env::var("SystemRoot")
-        .ok()
-        .map(|root| PathBuf::from(root).join("SoftwareDistribution\\Download"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -847,17 +830,31 @@ mod tests {
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(0), "0.0 Bytes");
-        assert_eq!(format_bytes(1024), "1.0 KB");
-        assert_eq!(format_bytes(1048576), "1.0 MB");
-        assert_eq!(format_bytes(1073741824), "1.0 GB");
+        assert_eq!(format_bytes(1024), "1.00 KB");
+        assert_eq!(format_bytes(1048576), "1.00 MB");
+        assert_eq!(format_bytes(1073741824), "1.00 GB");
+        assert_eq!(format_bytes(20 * 1024 * 1024), "20.0 MB");
     }
 
     #[test]
-    fn test_matches_pattern() {
-        assert!(matches_pattern("test.txt", "*.txt"));
-        assert!(matches_pattern("thumbcache_1024.db", "thumbcache_*.db"));
-        assert!(!matches_pattern("test.log", "*.txt"));
-        assert!(matches_pattern("test.txt", "test.txt"));
+    fn test_should_skip_file_advanced() {
+        let options = CleaningOptions {
+            excluded_patterns: vec!["**/Cache/index".to_string(), "regex:\\.keep$".to_string()],
+            included_patterns: vec!["*.tmp".to_string(), "Cache/*".to_string()],
+            ..CleaningOptions::default()
+        };
+        let filter = FileFilter::new(&options, &["thumbcache_*.db".to_string()]).unwrap();
+
+        assert!(!should_skip_file_advanced(Path::new("a/b/test.tmp"), &filter));
+        assert!(!should_skip_file_advanced(Path::new("Cache/data_1"), &filter));
+        assert!(should_skip_file_advanced(Path::new("Cache/index"), &filter));
+        assert!(should_skip_file_advanced(Path::new("a/test.tmp.keep"), &filter));
+        assert!(should_skip_file_advanced(Path::new("a/test.log"), &filter));
+        assert!(should_skip_file_advanced(Path::new(".hidden.tmp"), &filter));
+
+        let protected = FileFilter::new(&CleaningOptions::default(), &["thumbcache_*.db".to_string()]).unwrap();
+        assert!(should_skip_file_advanced(Path::new("x/thumbcache_1024.db"), &protected));
+        assert!(!should_skip_file_advanced(Path::new("x/test.db"), &protected));
     }
 
     #[test]
@@ -874,6 +871,26 @@ mod tests {
         assert!(summary.success_rate() > 60.0 && summary.success_rate() < 70.0);
     }
 
+    struct TestTarget;
+
+    impl CleanTarget for TestTarget {
+        fn id(&self) -> &str {
+            "test"
+        }
+
+        fn display_name(&self) -> &str {
+            "Test"
+        }
+
+        fn category(&self) -> TargetCategory {
+            TargetCategory::User
+        }
+
+        fn root_paths(&self, _options: &CleaningOptions) -> Vec<PathBuf> {
+            Vec::new()
+        }
+    }
+
     #[test]
     fn test_temp_directory_cleaning() {
         let temp_dir = TempDir::new().unwrap();
@@ -884,14 +901,85 @@ mod tests {
         let mut file = File::create(&test_file).unwrap();
         writeln!(file, "test content").unwrap();
         
-        let mut options = CleaningOptions::default();
-        options.min_file_age_days = 0; // Alle Dateien löschen
-        options.dry_run = true; // Nur simulieren
+        let options = CleaningOptions {
+            min_file_age_days: 0, // Alle Dateien löschen
+            dry_run: true, // Nur simulieren
+            ..CleaningOptions::default()
+        };
         
-        let mut summary = CleaningSummary::new();
+        let mut plan = CleaningPlan::new();
+        let monitor = CleaningMonitor::default();
+        let protection = ProtectedPaths::new();
+        let context = ScanContext { target: &TestTarget, location: "Test", filter: FileFilter::default(), protection: &protection, monitor: &monitor };
         
-        // Test der Verzeichnisbereinigung
-        let result = clean_directory_advanced(temp_path, "Test", &mut summary, &options);
-        assert!(result.is_ok());
+        // Test des Verzeichnis-Scans
+        scan_target_roots(&context, vec![temp_path.to_path_buf()], &mut plan, &options);
+        assert!(plan.errors.is_empty());
+        assert_eq!(plan.file_count(), 1);
+        assert!(test_file.exists());
+    }
+
+    #[test]
+    fn test_scan_never_enters_protected_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let documents = temp_dir.path().join("Documents");
+        let repo = temp_dir.path().join("cache").join("projekt");
+        fs::create_dir_all(&documents).unwrap();
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        File::create(documents.join("brief.tmp")).unwrap();
+        File::create(repo.join("main.rs")).unwrap();
+        File::create(temp_dir.path().join("cache").join("alt.tmp")).unwrap();
+
+        let options = CleaningOptions { min_file_age_days: 0, ..CleaningOptions::default() };
+        let monitor = CleaningMonitor::default();
+        let protection = ProtectedPaths::new().with_prefix(&documents).with_git_worktrees();
+        let context = ScanContext { target: &TestTarget, location: "Test", filter: FileFilter::default(), protection: &protection, monitor: &monitor };
+
+        let cache = temp_dir.path().join("cache");
+        let mut plan = CleaningPlan::new();
+        scan_target_roots(&context, vec![cache.clone()], &mut plan, &options);
+        let planned: Vec<_> = plan.entries.iter().map(|e| e.path.clone()).collect();
+        assert_eq!(planned, vec![cache.join("alt.tmp")]);
+        assert!(matches!(&plan.errors[..], [CleaningError::ProtectedPath(p)] if *p == repo));
+
+        // Gesperrte Wurzeln und ihre Elternverzeichnisse werden gar nicht erst durchsucht
+        for root in [documents.clone(), temp_dir.path().to_path_buf()] {
+            let mut plan = CleaningPlan::new();
+            scan_target_roots(&context, vec![root.clone()], &mut plan, &options);
+            assert!(plan.is_empty());
+            assert!(matches!(&plan.errors[..], [CleaningError::ProtectedPath(p)] if *p == root));
+        }
+    }
+
+    #[test]
+    fn test_parallel_scan_honours_max_files_exactly() {
+        let temp_dir = TempDir::new().unwrap();
+        for dir in 0..10 {
+            let sub = temp_dir.path().join(format!("dir{}", dir)).join("tiefer");
+            fs::create_dir_all(&sub).unwrap();
+            for file in 0..10 {
+                File::create(sub.join(format!("{}.tmp", file))).unwrap();
+                File::create(sub.parent().unwrap().join(format!("{}.tmp", file))).unwrap();
+            }
+        }
+
+        let options = CleaningOptions {
+            min_file_age_days: 0,
+            max_files: 37,
+            worker_threads: 4,
+            ..CleaningOptions::default()
+        };
+        let monitor = CleaningMonitor::default();
+        let protection = ProtectedPaths::new();
+        let context = ScanContext { target: &TestTarget, location: "Test", filter: FileFilter::default(), protection: &protection, monitor: &monitor };
+
+        let mut plan = CleaningPlan::new();
+        scan_target_roots(&context, vec![temp_dir.path().to_path_buf()], &mut plan, &options);
+        assert_eq!(plan.file_count(), 37);
+
+        let unlimited = CleaningOptions { max_files: 0, ..options };
+        let mut plan = CleaningPlan::new();
+        scan_target_roots(&context, vec![temp_dir.path().to_path_buf()], &mut plan, &unlimited);
+        assert_eq!(plan.file_count(), 200);
     }
 }
@@ -0,0 +1,242 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{Local, NaiveDateTime, TimeZone};
+
+use super::quarantine::move_file;
+use super::temp_files::CleaningError;
+
+const INFO_EXTENSION: &str = "trashinfo";
+
+/// Format von `DeletionDate=` (lokale Zeit ohne Zeitzone)
+const DELETION_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Ein Eintrag im Papierkorb: `files/<name>` samt `info/<name>.trashinfo`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrashItem {
+    /// Gelöschte Datei bzw. gelöschtes Verzeichnis; kann fehlen, wenn nur die Info-Datei übrig ist
+    pub payload: PathBuf,
+    pub info: PathBuf,
+    /// `DeletionDate` aus der Info-Datei, sofern lesbar
+    pub deleted: Option<SystemTime>,
+}
+
+/// Papierkorb nach der freedesktop.org Trash-Spezifikation (`files/` + `info/`)
+///
+/// Dateien von anderen Dateisystemen werden in den Home-Papierkorb kopiert,
+/// statt einen eigenen `.Trash-$uid` auf dem jeweiligen Laufwerk anzulegen.
+#[derive(Debug, Clone)]
+pub struct Trash {
+    root: PathBuf,
+}
+
+impl Trash {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Home-Papierkorb `$XDG_DATA_HOME/Trash`
+    pub fn home() -> Result<Self, CleaningError> {
+        if cfg!(windows) {
+            return Err(CleaningError::InvalidPath("Papierkorb-Modus wird unter Windows nicht unterstützt".to_string()));
+        }
+        super::targets::xdg_data_home()
+            .map(|data| Self::new(data.join("Trash")))
+            .ok_or_else(|| CleaningError::InvalidPath("Kein Papierkorb-Verzeichnis ermittelbar".to_string()))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Alle Einträge anhand der Info-Dateien; ein fehlendes `info/` bedeutet einen leeren Papierkorb
+    ///
+    /// Dateien in `files/` ohne Info-Datei gehören zu keinem Eintrag und werden nicht geliefert.
+    pub fn items(&self) -> io::Result<Vec<TrashItem>> {
+        let info_dir = self.root.join("info");
+        let entries = match fs::read_dir(&info_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut items = Vec::new();
+        for entry in entries {
+            let info = entry?.path();
+            if info.extension().is_none_or(|ext| ext != INFO_EXTENSION) {
+                continue;
+            }
+            let Some(name) = info.file_stem() else {
+                continue;
+            };
+            let payload = self.root.join("files").join(name);
+            let deleted = fs::read_to_string(&info).ok().and_then(|content| parse_deletion_date(&content));
+            items.push(TrashItem { payload, info, deleted });
+        }
+        items.sort_by(|a, b| a.info.cmp(&b.info));
+        Ok(items)
+    }
+
+    /// Liegt der Pfad bereits im Papierkorb?
+    pub fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.root)
+    }
+
+    /// Verschiebt eine Datei in den Papierkorb und liefert ihren neuen Pfad
+    pub fn put(&self, path: &Path) -> io::Result<PathBuf> {
+        let files_dir = self.root.join("files");
+        let info_dir = self.root.join("info");
+        fs::create_dir_all(&files_dir)?;
+        fs::create_dir_all(&info_dir)?;
+
+        let original = if path.is_absolute() { path.to_path_buf() } else { std::env::current_dir()?.join(path) };
+        let (info_path, mut info_file, trashed_path) = self.reserve_name(&original, &files_dir, &info_dir)?;
+
+        let deletion_date = Local::now().format(DELETION_DATE_FORMAT);
+        let info = format!("[Trash Info]\nPath={}\nDeletionDate={}\n", encode_trash_path(&original), deletion_date);
+
+        let result = info_file
+            .write_all(info.as_bytes())
+            .and_then(|_| move_file(&original, &trashed_path));
+        if let Err(e) = result {
+            let _ = fs::remove_file(&info_path);
+            return Err(e);
+        }
+
+        Ok(trashed_path)
+    }
+
+    /// Reserviert einen freien Namen, indem die `.trashinfo`-Datei exklusiv angelegt wird
+    ///
+    /// Bei Kollisionen wird eine Nummer vor der Dateiendung eingefügt (`log.2.txt`, `log.3.txt`, ...).
+    fn reserve_name(&self, original: &Path, files_dir: &Path, info_dir: &Path) -> io::Result<(PathBuf, File, PathBuf)> {
+        let file_name = original
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Pfad ohne Dateinamen"))?
+            .to_string_lossy()
+            .to_string();
+
+        for attempt in 1.. {
+            let name = match attempt {
+                1 => file_name.clone(),
+                n => numbered_name(&file_name, n),
+            };
+            let info_path = info_dir.join(format!("{}.{}", name, INFO_EXTENSION));
+            let trashed_path = files_dir.join(&name);
+
+            match File::options().write(true).create_new(true).open(&info_path) {
+                // Auch verwaiste Dateien ohne Info-Datei nicht überschreiben
+                Ok(_) if fs::symlink_metadata(&trashed_path).is_ok() => {
+                    let _ = fs::remove_file(&info_path);
+                }
+                Ok(file) => return Ok((info_path, file, trashed_path)),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        unreachable!("unbegrenzte Namenssuche")
+    }
+}
+
+fn numbered_name(file_name: &str, n: usize) -> String {
+    match file_name.rfind('.') {
+        Some(dot) if dot > 0 => format!("{}.{}{}", &file_name[..dot], n, &file_name[dot..]),
+        _ => format!("{}.{}", file_name, n),
+    }
+}
+
+/// Liest `DeletionDate=` aus dem Inhalt einer `.trashinfo`-Datei
+fn parse_deletion_date(content: &str) -> Option<SystemTime> {
+    let value = content.lines().find_map(|line| line.trim().strip_prefix("DeletionDate="))?;
+    let naive = NaiveDateTime::parse_from_str(value.trim(), DELETION_DATE_FORMAT).ok()?;
+    Local.from_local_datetime(&naive).earliest().map(SystemTime::from)
+}
+
+/// Größe einer Datei bzw. Gesamtgröße eines Verzeichnisses; Symlinks werden nicht verfolgt
+pub(crate) fn payload_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| payload_size(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Prozentkodierung des Pfads für `Path=` (RFC 2396, `/` bleibt erhalten)
+fn encode_trash_path(path: &Path) -> String {
+    let mut encoded = String::new();
+    for &byte in path.as_os_str().as_encoded_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_put_writes_trashinfo_and_handles_collisions() {
+        let files = TempDir::new().unwrap();
+        let trash_dir = TempDir::new().unwrap();
+        let trash = Trash::new(trash_dir.path());
+
+        let first = files.path().join("mein bericht.txt");
+        fs::write(&first, "eins").unwrap();
+        let trashed = trash.put(&first).unwrap();
+        assert!(!first.exists());
+        assert_eq!(trashed, trash.root().join("files").join("mein bericht.txt"));
+
+        let info = fs::read_to_string(trash.root().join("info").join("mein bericht.txt.trashinfo")).unwrap();
+        let mut lines = info.lines();
+        assert_eq!(lines.next(), Some("[Trash Info]"));
+        assert_eq!(lines.next().unwrap(), format!("Path={}", encode_trash_path(&first)));
+        assert!(lines.next().unwrap().starts_with("DeletionDate="));
+        assert!(info.contains("mein%20bericht.txt"));
+
+        fs::write(&first, "zwei").unwrap();
+        let second = trash.put(&first).unwrap();
+        assert_eq!(second, trash.root().join("files").join("mein bericht.2.txt"));
+        assert_eq!(fs::read_to_string(second).unwrap(), "zwei");
+        assert!(trash.root().join("info").join("mein bericht.2.txt.trashinfo").exists());
+    }
+
+    #[test]
+    fn test_items_read_deletion_date() {
+        let files = TempDir::new().unwrap();
+        let trash_dir = TempDir::new().unwrap();
+        let trash = Trash::new(trash_dir.path());
+
+        let report = files.path().join("bericht.txt");
+        fs::write(&report, "inhalt").unwrap();
+        let trashed = trash.put(&report).unwrap();
+        fs::write(trash.root().join("info").join("alt.trashinfo"), "[Trash Info]\nPath=/alt\nDeletionDate=2020-01-02T03:04:05\n").unwrap();
+        fs::write(trash.root().join("files").join("ohne-info"), "").unwrap();
+
+        let items = trash.items().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].payload, trash.root().join("files").join("alt"));
+        let expected = Local.with_ymd_and_hms(2020, 1, 2, 3, 4, 5).earliest().map(SystemTime::from);
+        assert_eq!(items[0].deleted, expected);
+        assert_eq!(items[1].payload, trashed);
+        assert!(items[1].deleted.is_some());
+        assert_eq!(payload_size(&trash.root().join("files")), 6);
+    }
+
+    #[test]
+    fn test_numbered_name() {
+        assert_eq!(numbered_name("log.txt", 2), "log.2.txt");
+        assert_eq!(numbered_name("README", 3), "README.3");
+        assert_eq!(numbered_name(".hidden", 2), ".hidden.2");
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+/// Obergrenze für automatisch gewählte Worker (Dateisystem-I/O skaliert nicht beliebig)
+const MAX_AUTO_WORKERS: usize = 8;
+
+/// Anzahl der Worker: `requested` oder (bei 0) die verfügbare Parallelität, höchstens `MAX_AUTO_WORKERS`
+pub(crate) fn worker_count(requested: usize) -> usize {
+    if requested > 0 {
+        return requested;
+    }
+    thread::available_parallelism()
+        .map(|n| n.get().min(MAX_AUTO_WORKERS))
+        .unwrap_or(1)
+}
+
+/// Gemeinsame Arbeitswarteschlange, in die Worker während der Abarbeitung neue Einträge legen
+///
+/// `next` blockiert, bis Arbeit vorhanden ist, und liefert `None`, sobald die Schlange leer
+/// ist und kein Worker mehr neue Einträge erzeugen kann (oder `close` aufgerufen wurde).
+pub(crate) struct WorkQueue<T> {
+    state: Mutex<QueueState<T>>,
+    ready: Condvar,
+}
+
+struct QueueState<T> {
+    items: VecDeque<T>,
+    in_progress: usize,
+    closed: bool,
+}
+
+impl<T> WorkQueue<T> {
+    pub(crate) fn new(initial: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                items: initial.into_iter().collect(),
+                in_progress: 0,
+                closed: false,
+            }),
+            ready: Condvar::new(),
+        }
+    }
+
+    pub(crate) fn push(&self, item: T) {
+        if let Ok(mut state) = self.state.lock() {
+            state.items.push_back(item);
+            self.ready.notify_one();
+        }
+    }
+
+    /// Nächster Eintrag; jeder gelieferte Eintrag muss mit `done` abgeschlossen werden
+    fn next(&self) -> Option<T> {
+        let mut state = self.state.lock().ok()?;
+        loop {
+            if state.closed {
+                return None;
+            }
+            if let Some(item) = state.items.pop_front() {
+                state.in_progress += 1;
+                return Some(item);
+            }
+            if state.in_progress == 0 {
+                return None;
+            }
+            state = self.ready.wait(state).ok()?;
+        }
+    }
+
+    fn done(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.in_progress -= 1;
+            if state.in_progress == 0 && state.items.is_empty() {
+                self.ready.notify_all();
+            }
+        }
+    }
+
+    /// Beendet die Abarbeitung vorzeitig (z.B. bei Abbruch)
+    pub(crate) fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+            self.ready.notify_all();
+        }
+    }
+}
+
+struct DoneGuard<'a, T>(&'a WorkQueue<T>);
+
+impl<T> Drop for DoneGuard<'_, T> {
+    fn drop(&mut self) {
+        self.0.done();
+    }
+}
+
+/// Arbeitet die Warteschlange mit `workers` Threads ab
+///
+/// Jeder Worker sammelt in seinem eigenen Zustand `S`; die Zustände werden am Ende zurückgegeben
+/// und vom Aufrufer zusammengeführt, sodass während der Arbeit keine gemeinsamen Zähler nötig sind.
+pub(crate) fn run_workers<T, S, F>(queue: &WorkQueue<T>, workers: usize, work: F) -> Vec<S>
+where
+    T: Send,
+    S: Default + Send,
+    F: Fn(T, &WorkQueue<T>, &mut S) + Sync,
+{
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers.max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut state = S::default();
+                    while let Some(item) = queue.next() {
+                        // Abschluss auch bei Panic melden, sonst warten die anderen Worker ewig
+                        let _done = DoneGuard(queue);
+                        work(item, queue, &mut state);
+                    }
+                    state
+                })
+            })
+            .collect();
+
+        // Panic eines Workers weiterreichen, statt seine Ergebnisse stillschweigend zu verlieren
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|payload| std::panic::resume_unwind(payload)))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workers_process_generated_items() {
+        // Jeder Eintrag n > 0 erzeugt zwei Kinder n - 1: insgesamt 2^(n+1) - 1 Einträge
+        let queue = WorkQueue::new([5u32]);
+        let counts: Vec<usize> = run_workers(&queue, 4, |n, queue, count: &mut usize| {
+            *count += 1;
+            if n > 0 {
+                queue.push(n - 1);
+                queue.push(n - 1);
+            }
+        });
+
+        assert_eq!(counts.len(), 4);
+        assert_eq!(counts.iter().sum::<usize>(), 63);
+    }
+
+    #[test]
+    #[should_panic(expected = "Worker-Fehler")]
+    fn test_worker_panic_is_propagated() {
+        let queue = WorkQueue::new(0..8u32);
+        run_workers(&queue, 2, |n, _, _: &mut ()| {
+            if n == 3 {
+                panic!("Worker-Fehler");
+            }
+        });
+    }
+
+    #[test]
+    fn test_closed_queue_stops_workers() {
+        let queue = WorkQueue::new(0..1000);
+        let counts: Vec<usize> = run_workers(&queue, 2, |_, queue, count: &mut usize| {
+            *count += 1;
+            queue.close();
+        });
+
+        assert!(counts.iter().sum::<usize>() <= 2);
+    }
+}
@@ -0,0 +1,356 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::cleaning::quarantine::MAX_RETENTION_DAYS;
+use crate::cleaning::targets::MAX_STALE_PROJECT_DAYS;
+use crate::cleaning::{patterns, CleaningOptions, DeletionMode};
+
+/// Kommandozeilenwerkzeug zum Bereinigen ohne Oberfläche
+#[derive(Debug, Parser)]
+#[command(name = "rustyclean-cli", version, about = "Temporäre Dateien und Caches bereinigen")]
+pub struct Cli {
+    /// Andere Konfigurationsdatei statt der im Benutzerprofil verwenden
+    #[arg(long, global = true, value_name = "DATEI")]
+    pub config: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: CliCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CliCommand {
+    /// Zeigt, was gelöscht würde, ohne etwas zu verändern
+    Scan {
+        #[command(flatten)]
+        selection: TargetSelection,
+        #[command(flatten)]
+        options: OptionArgs,
+        /// Jede geplante Datei auflisten
+        #[arg(long)]
+        files: bool,
+    },
+    /// Scannt und bereinigt die ausgewählten Ziele
+    Clean {
+        #[command(flatten)]
+        selection: TargetSelection,
+        #[command(flatten)]
+        options: OptionArgs,
+        /// Bericht (JSON + CSV) in diesem Verzeichnis speichern
+        #[arg(long, value_name = "VERZEICHNIS")]
+        report: Option<PathBuf>,
+    },
+    /// Listet alle bekannten Bereinigungsziele
+    ListTargets,
+    /// Listet die Profile aus der Konfiguration
+    ListProfiles,
+    /// Listet die geplanten Bereinigungen mit ihrem nächsten Termin
+    ListSchedules,
+    /// Hintergrunddienst: führt die Zeitpläne aus `[[schedules]]` der Konfiguration aus
+    Daemon {
+        /// Nur einmal prüfen und fällige Zeitpläne ausführen (z.B. für cron oder systemd-Timer)
+        #[arg(long)]
+        once: bool,
+        /// Prüfintervall in Sekunden
+        #[arg(long, value_name = "SEKUNDEN", default_value_t = crate::scheduler::DEFAULT_INTERVAL.as_secs())]
+        interval: u64,
+    },
+    /// Verschiebt die Dateien eines Quarantäne-Laufs an ihren ursprünglichen Ort zurück
+    Restore {
+        /// Kennung des Laufs, wie sie `clean` ausgibt
+        run_id: String,
+        /// Profil, dessen Quarantäne-Verzeichnis verwendet wird (ohne Angabe: Standardprofil)
+        #[arg(short, long, value_name = "PROFIL")]
+        profile: Option<String>,
+        /// Quarantäne-Verzeichnis; geht dem des Profils vor
+        #[arg(long, value_name = "VERZEICHNIS")]
+        quarantine_dir: Option<PathBuf>,
+    },
+    /// Zeigt einen gespeicherten JSON-Bericht an
+    Report {
+        /// Pfad zur JSON-Datei (aus `clean --report`)
+        file: PathBuf,
+        /// Ausgabeformat
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+        /// Bei CSV das Detailprotokoll je Datei statt der Orte ausgeben
+        #[arg(long)]
+        files: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ModeArg {
+    Delete,
+    Quarantine,
+    Trash,
+}
+
+/// Auswahl der Ziele per ID oder Anzeigename bzw. über ein Profil
+#[derive(Debug, Args)]
+pub struct TargetSelection {
+    /// Profil aus der Konfiguration (Ziele und Optionen); Schalter überschreiben dessen Werte
+    #[arg(short, long, value_name = "PROFIL")]
+    pub profile: Option<String>,
+    /// Nur diese Ziele bereinigen (mehrfach angebbar; ohne Angabe: alle bzw. die des Profils)
+    #[arg(short, long = "target", value_name = "ZIEL")]
+    pub targets: Vec<String>,
+}
+
+/// Ein Schalter pro Feld von `CleaningOptions`
+///
+/// Ja/Nein-Optionen gibt es paarweise (`--dry-run`/`--no-dry-run`), damit sich die Werte
+/// eines Profils in beide Richtungen überschreiben lassen; der zuletzt angegebene gilt.
+#[derive(Debug, Args)]
+pub struct OptionArgs {
+    /// Minimales Dateialter in Tagen
+    #[arg(long = "min-age", value_name = "TAGE")]
+    pub min_file_age_days: Option<u64>,
+    /// Unterverzeichnisse durchsuchen
+    #[arg(long, overrides_with = "no_recursive")]
+    pub recursive: bool,
+    /// Unterverzeichnisse nicht durchsuchen
+    #[arg(long, overrides_with = "recursive")]
+    pub no_recursive: bool,
+    /// Leer gewordene Verzeichnisse entfernen
+    #[arg(long, overrides_with = "keep_empty_dirs")]
+    pub remove_empty_dirs: bool,
+    /// Leer gewordene Verzeichnisse nicht entfernen
+    #[arg(long, overrides_with = "remove_empty_dirs")]
+    pub keep_empty_dirs: bool,
+    /// Nur Dateien mit diesen Endungen (mehrfach oder kommagetrennt)
+    #[arg(long = "ext", value_name = "ENDUNG", value_delimiter = ',')]
+    pub target_extensions: Vec<String>,
+    /// Höchstens so viele Dateien bereinigen (0 = unbegrenzt)
+    #[arg(long, value_name = "ANZAHL")]
+    pub max_files: Option<usize>,
+    /// Nur Dateien bis zu dieser Größe (z.B. 500K, 20M, 1G)
+    #[arg(long = "max-size", value_name = "GRÖSSE", value_parser = parse_size)]
+    pub max_file_size: Option<u64>,
+    /// Nur Dateien ab dieser Größe (z.B. 1K)
+    #[arg(long = "min-size", value_name = "GRÖSSE", value_parser = parse_size)]
+    pub min_file_size: Option<u64>,
+    /// Dateien ausschließen: Glob auf Namen (*.log) oder relativen Pfad (**/Cache/index), regex:... (mehrfach angebbar)
+    #[arg(long = "exclude", value_name = "MUSTER", value_parser = parse_pattern)]
+    pub excluded_patterns: Vec<String>,
+    /// Nur Dateien, die einem dieser Muster entsprechen (mehrfach angebbar)
+    #[arg(long = "include", value_name = "MUSTER", value_parser = parse_pattern)]
+    pub included_patterns: Vec<String>,
+    /// Jede Aktion ausgeben
+    #[arg(short, long, overrides_with = "no_verbose")]
+    pub verbose: bool,
+    /// Nicht jede Aktion ausgeben
+    #[arg(long, overrides_with = "verbose")]
+    pub no_verbose: bool,
+    /// Nur scannen, nichts löschen
+    #[arg(short = 'n', long, overrides_with = "no_dry_run")]
+    pub dry_run: bool,
+    /// Wirklich bereinigen, auch wenn das Profil einen Dry Run vorsieht
+    #[arg(long, overrides_with = "dry_run")]
+    pub no_dry_run: bool,
+    /// Ergebnis jeder Datei im Bericht festhalten
+    #[arg(long, overrides_with = "no_log_files")]
+    pub log_files: bool,
+    /// Kein Ergebnis je Datei festhalten
+    #[arg(long, overrides_with = "log_files")]
+    pub no_log_files: bool,
+    /// Löschen, in Quarantäne oder in den Papierkorb verschieben
+    #[arg(long, value_enum, value_name = "MODUS")]
+    pub mode: Option<ModeArg>,
+    /// Quarantäne-Verzeichnis
+    #[arg(long, value_name = "VERZEICHNIS")]
+    pub quarantine_dir: Option<PathBuf>,
+    /// Aufbewahrungsdauer der Quarantäne in Tagen
+    #[arg(long = "retention-days", value_name = "TAGE", value_parser = clap::value_parser!(u64).range(..=MAX_RETENTION_DAYS))]
+    pub quarantine_retention_days: Option<u64>,
+    /// Projektverzeichnis für veraltete target-Verzeichnisse (mehrfach angebbar)
+    #[arg(long = "project-root", value_name = "VERZEICHNIS")]
+    pub project_roots: Vec<PathBuf>,
+    /// Projekte gelten nach so vielen Tagen ohne Änderung als veraltet
+    #[arg(long = "stale-days", value_name = "TAGE", value_parser = clap::value_parser!(u64).range(..=MAX_STALE_PROJECT_DAYS))]
+    pub stale_project_days: Option<u64>,
+    /// Anzahl paralleler Worker (0 = automatisch)
+    #[arg(long = "threads", value_name = "ANZAHL")]
+    pub worker_threads: Option<usize>,
+}
+
+impl OptionArgs {
+    /// Überschreibt die angegebenen Felder in `options`
+    pub fn apply(&self, options: &mut CleaningOptions) {
+        /// Setzt `target`, wenn einer der beiden Schalter eines Paares angegeben wurde
+        fn switch(target: &mut bool, on: bool, off: bool) {
+            if on || off {
+                *target = on;
+            }
+        }
+
+        if let Some(days) = self.min_file_age_days {
+            options.min_file_age_days = days;
+        }
+        switch(&mut options.recursive, self.recursive, self.no_recursive);
+        switch(&mut options.remove_empty_dirs, self.remove_empty_dirs, self.keep_empty_dirs);
+        if !self.target_extensions.is_empty() {
+            let extensions = self.target_extensions.iter().map(|e| e.trim_start_matches('.').to_string()).collect();
+            options.target_extensions = Some(extensions);
+        }
+        if let Some(max_files) = self.max_files {
+            options.max_files = max_files;
+        }
+        if let Some(size) = self.max_file_size {
+            options.max_file_size = size;
+        }
+        if let Some(size) = self.min_file_size {
+            options.min_file_size = size;
+        }
+        options.excluded_patterns.extend(self.excluded_patterns.iter().cloned());
+        options.included_patterns.extend(self.included_patterns.iter().cloned());
+        switch(&mut options.verbose, self.verbose, self.no_verbose);
+        switch(&mut options.dry_run, self.dry_run, self.no_dry_run);
+        switch(&mut options.log_files, self.log_files, self.no_log_files);
+        if let Some(mode) = self.mode {
+            options.deletion_mode = match mode {
+                ModeArg::Delete => DeletionMode::Delete,
+                ModeArg::Quarantine => DeletionMode::Quarantine,
+                ModeArg::Trash => DeletionMode::Trash,
+            };
+        }
+        if let Some(dir) = &self.quarantine_dir {
+            options.quarantine_dir = Some(dir.clone());
+        }
+        if let Some(days) = self.quarantine_retention_days {
+            options.quarantine_retention_days = days;
+        }
+        options.project_roots.extend(self.project_roots.iter().cloned());
+        if let Some(days) = self.stale_project_days {
+            options.stale_project_days = days;
+        }
+        if let Some(threads) = self.worker_threads {
+            options.worker_threads = threads;
+        }
+    }
+}
+
+fn parse_pattern(value: &str) -> Result<String, String> {
+    patterns::validate(value).map_err(|e| e.to_string())?;
+    Ok(value.to_string())
+}
+
+/// Größenangabe in Bytes mit optionalem Suffix K, M, G oder T (Basis 1024)
+fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let number: u64 = number.parse().map_err(|_| format!("Ungültige Größe: {}", value))?;
+    let factor: u64 = match unit.trim().to_ascii_uppercase().trim_end_matches('B') {
+        "" => 1,
+        "K" => 1024,
+        "M" => 1024 * 1024,
+        "G" => 1024 * 1024 * 1024,
+        "T" => 1024 * 1024 * 1024 * 1024,
+        _ => return Err(format!("Unbekannte Einheit in {}", value)),
+    };
+
+    number
+        .checked_mul(factor)
+        .ok_or_else(|| format!("Größe zu groß: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_flags_map_onto_options() {
+        let cli = Cli::try_parse_from([
+            "rustyclean-cli", "clean", "-t", "npm-cache", "--target", "pip-cache",
+            "--min-age", "7", "--no-recursive", "--ext", "log,.tmp", "--exclude", "*.keep",
+            "--max-files", "10", "--max-size", "20M", "--min-size", "1k", "-n", "--mode", "trash",
+        ])
+        .unwrap();
+
+        let CliCommand::Clean { selection, options: args, .. } = cli.command else {
+            panic!("falscher Befehl");
+        };
+        assert_eq!(selection.targets, vec!["npm-cache", "pip-cache"]);
+
+        let mut options = CleaningOptions::default();
+        args.apply(&mut options);
+        assert_eq!(options.min_file_age_days, 7);
+        assert!(!options.recursive);
+        assert!(options.remove_empty_dirs);
+        assert_eq!(options.target_extensions, Some(vec!["log".to_string(), "tmp".to_string()]));
+        assert_eq!(options.excluded_patterns, vec!["*.keep"]);
+        assert_eq!(options.max_files, 10);
+        assert_eq!(options.max_file_size, 20 * 1024 * 1024);
+        assert_eq!(options.min_file_size, 1024);
+        assert!(options.dry_run);
+        assert_eq!(options.deletion_mode, DeletionMode::Trash);
+    }
+
+    #[test]
+    fn test_flags_override_profile_both_ways() {
+        let cli = Cli::try_parse_from([
+            "rustyclean-cli", "clean", "--no-dry-run", "--recursive", "--keep-empty-dirs", "--log-files", "--no-log-files",
+        ])
+        .unwrap();
+        let CliCommand::Clean { options: args, .. } = cli.command else {
+            panic!("falscher Befehl");
+        };
+
+        // Werte, wie sie ein Profil gesetzt haben könnte
+        let mut options = CleaningOptions { dry_run: true, recursive: false, log_files: true, verbose: true, ..CleaningOptions::default() };
+        args.apply(&mut options);
+        assert!(!options.dry_run);
+        assert!(options.recursive);
+        assert!(!options.remove_empty_dirs);
+        // Der zuletzt angegebene Schalter eines Paares gilt, nicht angegebene bleiben unverändert
+        assert!(!options.log_files);
+        assert!(options.verbose);
+    }
+
+    #[test]
+    fn test_restore_takes_run_and_quarantine_dir() {
+        let cli = Cli::try_parse_from(["rustyclean-cli", "restore", "1735732800-123", "--quarantine-dir", "/srv/q"]).unwrap();
+        let CliCommand::Restore { run_id, profile, quarantine_dir } = cli.command else {
+            panic!("falscher Befehl");
+        };
+        assert_eq!(run_id, "1735732800-123");
+        assert_eq!(profile, None);
+        assert_eq!(quarantine_dir, Some(PathBuf::from("/srv/q")));
+        assert!(Cli::try_parse_from(["rustyclean-cli", "restore"]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_patterns_are_rejected() {
+        assert!(Cli::try_parse_from(["rustyclean-cli", "scan", "--include", "**/Cache/*.tmp"]).is_ok());
+        assert!(Cli::try_parse_from(["rustyclean-cli", "scan", "--exclude", "regex:(offen"]).is_err());
+    }
+
+    #[test]
+    fn test_day_counts_are_limited() {
+        assert!(Cli::try_parse_from(["rustyclean-cli", "clean", "--retention-days", "30"]).is_ok());
+        assert!(Cli::try_parse_from(["rustyclean-cli", "clean", "--retention-days", "18446744073709551615"]).is_err());
+        assert!(Cli::try_parse_from(["rustyclean-cli", "scan", "--stale-days", "18446744073709551615"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("512"), Ok(512));
+        assert_eq!(parse_size("2KB"), Ok(2048));
+        assert!(parse_size("3X").is_err());
+        assert!(parse_size("abc").is_err());
+    }
+}
@@ -0,0 +1,311 @@
+mod args;
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::Parser;
+
+use crate::cleaning::{
+    execute, format_bytes, report, restore, scan_targets, CleanTarget, CleaningOptions, CleaningPlan,
+    CleaningSummary, HistoryEntry, HistoryStore, TargetRegistry,
+};
+use crate::config::Config;
+use crate::scheduler::{self, Daemon, SchedulerState};
+
+pub use args::{Cli, CliCommand, ModeArg, OptionArgs, ReportFormat, TargetSelection};
+
+/// Alles erledigt, keine Fehler
+pub const EXIT_OK: u8 = 0;
+/// Lauf abgeschlossen, aber mit Fehlern (z.B. gesperrte Dateien)
+pub const EXIT_ERRORS: u8 = 1;
+/// Ungültige Eingabe (unbekanntes Ziel, fehlerhafte Konfiguration, unlesbarer Bericht, ...)
+pub const EXIT_USAGE: u8 = 2;
+
+/// Einstiegspunkt des Kommandozeilenprogramms
+pub fn main() -> ExitCode {
+    ExitCode::from(run(Cli::parse()))
+}
+
+/// Führt einen bereits geparsten Befehl aus und liefert den Exit-Code
+pub fn run(cli: Cli) -> u8 {
+    let registry = TargetRegistry::with_builtin_targets();
+    let load_config = || match &cli.config {
+        Some(path) => Config::load_from(path),
+        None => Config::load(),
+    }
+    .map_err(|e| e.to_string());
+
+    let result = match &cli.command {
+        CliCommand::ListTargets => {
+            list_targets(&registry);
+            Ok(EXIT_OK)
+        }
+        CliCommand::ListProfiles => load_config().map(|config| {
+            list_profiles(&config);
+            EXIT_OK
+        }),
+        CliCommand::ListSchedules => load_config().and_then(|config| list_schedules(&config)),
+        CliCommand::Daemon { once, interval } => load_config().and_then(|config| daemon_command(config, *once, *interval)),
+        CliCommand::Scan { selection, options, files } => load_config()
+            .and_then(|config| resolve(&registry, &config, selection, options))
+            .map(|selected| scan_command(&selected.targets, &selected.options, *files)),
+        CliCommand::Clean { selection, options, report } => load_config()
+            .and_then(|config| resolve(&registry, &config, selection, options))
+            .and_then(|selected| clean_command(&selected, report.as_deref())),
+        CliCommand::Restore { run_id, profile, quarantine_dir } => load_config()
+            .and_then(|config| restore_command(&config, run_id, profile.as_deref(), quarantine_dir)),
+        CliCommand::Report { file, format, files } => report_command(file, *format, *files),
+    };
+
+    result.unwrap_or_else(|error| {
+        eprintln!("Fehler: {}", error);
+        EXIT_USAGE
+    })
+}
+
+/// Für einen Lauf ausgewählte Ziele und Optionen
+struct Selection<'a> {
+    targets: Vec<&'a dyn CleanTarget>,
+    options: CleaningOptions,
+    /// Name des verwendeten Profils für den Verlauf
+    profile: Option<String>,
+}
+
+/// Ziele und Optionen aus Profil, Zielauswahl und Schaltern
+///
+/// Ohne `--profile` gilt das Standardprofil der Konfiguration, sonst alle Ziele mit Standardoptionen.
+/// Mit `--target` ersetzt die Auswahl die Ziele des Profils; die Optionen des Profils bleiben.
+fn resolve<'a>(
+    registry: &'a TargetRegistry,
+    config: &Config,
+    selection: &TargetSelection,
+    args: &OptionArgs
+) -> Result<Selection<'a>, String> {
+    let profile_name = selection.profile.as_deref().or(config.default_profile());
+    let profile = match profile_name {
+        Some(name) => Some(config.profile(name).ok_or_else(|| format!("Unbekanntes Profil: {}", name))?),
+        None => None,
+    };
+
+    let targets = if !selection.targets.is_empty() {
+        registry.select(&selection.targets)?
+    } else if let Some(profile) = profile {
+        profile.select(registry)
+    } else {
+        registry.iter().collect()
+    };
+
+    let mut options = profile.map(|p| p.options()).unwrap_or_default();
+    args.apply(&mut options);
+    Ok(Selection { targets, options, profile: profile_name.map(String::from) })
+}
+
+fn list_targets(registry: &TargetRegistry) {
+    let options = CleaningOptions::default();
+    for target in registry.iter() {
+        println!("{:<24} {:<36} [{}]", target.id(), target.display_name(), target.category());
+        for root in target.root_paths(&options) {
+            let marker = if root.is_dir() { " " } else { "-" };
+            println!("    {} {}", marker, root.display());
+        }
+    }
+}
+
+fn list_profiles(config: &Config) {
+    for name in config.profile_names() {
+        let marker = if config.default_profile() == Some(name) { "*" } else { " " };
+        let description = config.profile(name).and_then(|p| p.description.as_deref()).unwrap_or("");
+        println!("{} {:<16} {}", marker, name, description);
+    }
+}
+
+fn list_schedules(config: &Config) -> Result<u8, String> {
+    let state = match SchedulerState::default_path() {
+        Some(path) => SchedulerState::load(&path).map_err(|e| format!("{}: {}", path.display(), e))?,
+        None => SchedulerState::default(),
+    };
+
+    for run in scheduler::upcoming(config, &state, chrono::Local::now()) {
+        println!("{:<16} {:<40} nächster Lauf: {}", run.schedule.profile, run.schedule.trigger.to_string(), run.next);
+    }
+    Ok(EXIT_OK)
+}
+
+fn daemon_command(config: Config, once: bool, interval: u64) -> Result<u8, String> {
+    if config.schedules().is_empty() {
+        return Err(String::from("Keine Zeitpläne konfiguriert ([[schedules]] in der Konfiguration)"));
+    }
+    let monitoring = config.monitoring().clone();
+    let mut daemon = Daemon::new(config).map_err(|e| e.to_string())?;
+    let stop = crate::cleaning::CancellationToken::new();
+    let mut success = true;
+
+    let mut report = |schedule: &crate::config::Schedule, result: &Result<CleaningSummary, String>| {
+        println!("[{}] {} ({})", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"), schedule.profile, schedule.trigger);
+        match result {
+            Ok(summary) => {
+                print_summary(summary);
+                success &= summary.errors.is_empty();
+            }
+            Err(error) => {
+                eprintln!("Fehler: {}", error);
+                success = false;
+            }
+        }
+    };
+
+    if once {
+        let free_percent = scheduler::free_disk_percent(&monitoring);
+        for (schedule, result) in daemon.tick(chrono::Local::now(), free_percent, &stop) {
+            report(&schedule, &result);
+        }
+    } else {
+        for run in daemon.upcoming(chrono::Local::now()) {
+            println!("{} ({}): nächster Lauf {}", run.schedule.profile, run.schedule.trigger, run.next);
+        }
+        daemon.run(Duration::from_secs(interval.max(1)), &stop, report);
+    }
+
+    Ok(exit_code(success))
+}
+
+fn scan_command(targets: &[&dyn CleanTarget], options: &CleaningOptions, list_files: bool) -> u8 {
+    let plan = scan_targets(targets, Some(options));
+    print_plan(&plan, list_files);
+    exit_code(plan.errors.is_empty())
+}
+
+fn clean_command(selected: &Selection, report_dir: Option<&std::path::Path>) -> Result<u8, String> {
+    let options = &selected.options;
+    let plan = scan_targets(&selected.targets, Some(options));
+    let summary = execute(&plan, options);
+    print_summary(&summary);
+
+    if !options.dry_run {
+        let entry = HistoryEntry::from_summary(&summary, selected.profile.as_deref(), chrono::Utc::now());
+        if let Err(e) = HistoryStore::open_default().and_then(|store| store.append(&entry)) {
+            eprintln!("Verlauf konnte nicht gespeichert werden: {}", e);
+        }
+    }
+
+    if let Some(dir) = report_dir {
+        let written = report::save_report(&summary, dir, &report::report_name()).map_err(|e| e.to_string())?;
+        for path in written {
+            println!("Bericht gespeichert: {}", path.display());
+        }
+    }
+
+    Ok(exit_code(summary.errors.is_empty()))
+}
+
+/// Stellt einen Lauf aus dem Quarantäne-Verzeichnis von `--quarantine-dir`, Profil oder Standardort wieder her
+fn restore_command(config: &Config, run_id: &str, profile: Option<&str>, quarantine_dir: &Option<PathBuf>) -> Result<u8, String> {
+    let mut options = match profile.or(config.default_profile()) {
+        Some(name) => config.profile(name).ok_or_else(|| format!("Unbekanntes Profil: {}", name))?.options(),
+        None => CleaningOptions::default(),
+    };
+    if quarantine_dir.is_some() {
+        options.quarantine_dir = quarantine_dir.clone();
+    }
+
+    let summary = restore(run_id, &options).map_err(|e| e.to_string())?;
+    println!("{} Dateien ({}) wiederhergestellt", summary.restored_files, format_bytes(summary.restored_size));
+    for path in &summary.conflicts {
+        println!("Nicht wiederhergestellt, am Ursprungsort liegt wieder eine Datei: {}", path.display());
+    }
+    for error in &summary.errors {
+        eprintln!("{}", error);
+    }
+
+    Ok(exit_code(summary.errors.is_empty() && summary.conflicts.is_empty()))
+}
+
+fn report_command(file: &std::path::Path, format: ReportFormat, files: bool) -> Result<u8, String> {
+    let json = fs::read_to_string(file).map_err(|e| format!("{}: {}", file.display(), e))?;
+    let summary = report::from_json(&json).map_err(|e| format!("{}: {}", file.display(), e))?;
+
+    match format {
+        ReportFormat::Text => print_summary(&summary),
+        ReportFormat::Json => println!("{}", report::to_json(&summary).map_err(|e| e.to_string())?),
+        ReportFormat::Csv if files => print!("{}", report::files_csv(&summary)),
+        ReportFormat::Csv => print!("{}", report::locations_csv(&summary)),
+    }
+
+    Ok(exit_code(summary.errors.is_empty()))
+}
+
+fn exit_code(success: bool) -> u8 {
+    if success {
+        EXIT_OK
+    } else {
+        EXIT_ERRORS
+    }
+}
+
+fn print_plan(plan: &CleaningPlan, list_files: bool) {
+    for location in &plan.locations {
+        let (files, size) = plan
+            .entries_for(&location.name)
+            .fold((0, 0), |(n, s), e| (n + 1, s + e.size));
+        println!(
+            "{}: {} Dateien ({}), {} übersprungen, {} Fehler",
+            location.name, files, format_bytes(size), location.skipped_files, location.errors
+        );
+
+        if list_files {
+            for entry in plan.entries_for(&location.name) {
+                println!("    {} ({}, {})", entry.path.display(), format_bytes(entry.size), entry.reason);
+            }
+        }
+    }
+
+    for error in &plan.errors {
+        eprintln!("{}", error);
+    }
+
+    println!(
+        "Gesamt: {} Dateien ({}) in {:.1?}",
+        plan.file_count(),
+        format_bytes(plan.total_size()),
+        plan.scan_time
+    );
+}
+
+fn print_summary(summary: &CleaningSummary) {
+    let mut locations: Vec<_> = summary.cleaned_locations.values().collect();
+    locations.sort_by(|a, b| a.location_name.cmp(&b.location_name));
+
+    for location in locations {
+        println!(
+            "{}: {} Dateien ({}), {} übersprungen, {} Fehler",
+            location.location_name,
+            location.deleted_files,
+            format_bytes(location.total_size),
+            location.skipped_files,
+            location.errors
+        );
+    }
+
+    for error in &summary.errors {
+        eprintln!("{}", error);
+    }
+
+    println!(
+        "{}: {} Dateien ({}), {} übersprungen, {} leere Verzeichnisse entfernt, {} Fehler in {:.1?}",
+        if summary.cancelled { "Abgebrochen" } else { "Bereinigt" },
+        summary.deleted_files,
+        summary.formatted_size(),
+        summary.skipped_files,
+        summary.empty_dirs_removed,
+        summary.errors.len(),
+        summary.processing_time
+    );
+    if summary.trashed_files > 0 {
+        println!("Davon im Papierkorb: {} Dateien ({})", summary.trashed_files, format_bytes(summary.trashed_size));
+    }
+    if let Some(run_id) = &summary.quarantine_run_id {
+        println!("Davon in Quarantäne: {} Dateien (Lauf {})", summary.quarantined_files, run_id);
+    }
+}
@@ -0,0 +1,391 @@
+mod monitoring;
+mod profile;
+mod schedule;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use toml::Spanned;
+
+use crate::cleaning::{patterns, TargetCategory, TargetRegistry};
+
+pub use monitoring::MonitoringSettings;
+pub use profile::Profile;
+pub use schedule::{Schedule, Trigger};
+
+use schedule::ScheduleEntry;
+
+/// Dateiname der Konfiguration im Konfigurationsverzeichnis
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// Profil, das ohne eigene Auswahl verwendet wird (entspricht dem bisherigen Verhalten: alle Ziele)
+pub const FALLBACK_PROFILE: &str = "deep";
+
+/// Fehler beim Laden der Konfiguration, mit Zeile und Spalte (1-basiert) in der Datei
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, line: usize, column: usize, message: String },
+    UnknownTarget { path: PathBuf, line: usize, column: usize, profile: String, target: String },
+    UnknownProfile { path: PathBuf, line: usize, column: usize, name: String },
+    InvalidPattern { path: PathBuf, line: usize, column: usize, profile: String, message: String },
+    InvalidSchedule { path: PathBuf, line: usize, column: usize, message: String },
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConfigError::Io { path, message } =>
+                write!(f, "{}: {}", path.display(), message),
+            ConfigError::Parse { path, line, column, message } =>
+                write!(f, "{}:{}:{}: {}", path.display(), line, column, message),
+            ConfigError::UnknownTarget { path, line, column, profile, target } =>
+                write!(f, "{}:{}:{}: Unbekanntes Bereinigungsziel \"{}\" im Profil \"{}\"", path.display(), line, column, target, profile),
+            ConfigError::UnknownProfile { path, line, column, name } =>
+                write!(f, "{}:{}:{}: Unbekanntes Profil \"{}\"", path.display(), line, column, name),
+            ConfigError::InvalidPattern { path, line, column, profile, message } =>
+                write!(f, "{}:{}:{}: {} im Profil \"{}\"", path.display(), line, column, message, profile),
+            ConfigError::InvalidSchedule { path, line, column, message } =>
+                write!(f, "{}:{}:{}: {}", path.display(), line, column, message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Inhalt der `config.toml`
+///
+/// ```toml
+/// default_profile = "quick"
+///
+/// [profiles.quick]
+/// categories = ["system", "user"]
+/// min_file_age_days = 3
+///
+/// [profiles.logs]
+/// targets = ["user-temp"]
+/// target_extensions = ["log"]
+///
+/// [[schedules]]
+/// profile = "logs"
+/// daily = "02:00"
+///
+/// [monitoring]
+/// history_minutes = 30
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    default_profile: Option<Spanned<String>>,
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+    /// Geplante Bereinigungen für den Hintergrunddienst
+    #[serde(default)]
+    schedules: Vec<ScheduleEntry>,
+    #[serde(default)]
+    monitoring: MonitoringSettings,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl Config {
+    /// Eingebaute Profile, die ohne Konfigurationsdatei zur Verfügung stehen
+    pub fn builtin() -> Self {
+        let mut profiles = BTreeMap::new();
+        profiles.insert(
+            "quick".to_string(),
+            Profile {
+                min_file_age_days: Some(3),
+                ..Profile::for_categories("Temporäre Dateien von System und Benutzer", &[TargetCategory::System, TargetCategory::User])
+            },
+        );
+        profiles.insert(FALLBACK_PROFILE.to_string(), Profile {
+            description: Some("Alle Ziele".to_string()),
+            ..Profile::default()
+        });
+        profiles.insert(
+            "dev-caches".to_string(),
+            Profile {
+                min_file_age_days: Some(7),
+                ..Profile::for_categories("Caches von Entwicklerwerkzeugen", &[TargetCategory::Developer])
+            },
+        );
+
+        Self { default_profile: None, profiles, schedules: Vec::new(), monitoring: MonitoringSettings::default() }
+    }
+
+    /// Standardpfad der Konfiguration im Benutzerprofil
+    pub fn default_path() -> Option<PathBuf> {
+        crate::cleaning::targets::app_config_dir().map(|dir| dir.join(CONFIG_FILE))
+    }
+
+    /// Lädt die Konfiguration vom Standardpfad; ohne Datei gelten die eingebauten Profile
+    pub fn load() -> Result<Self, ConfigError> {
+        match Self::default_path() {
+            Some(path) if path.exists() => Self::load_from(&path),
+            _ => Ok(Self::builtin()),
+        }
+    }
+
+    /// Lädt eine bestimmte Konfigurationsdatei
+    pub fn load_from(path: &Path) -> Result<Self, ConfigError> {
+        let source = fs::read_to_string(path).map_err(|e| io_error(path, &e))?;
+        Self::parse(&source, path)
+    }
+
+    /// Liest und prüft eine Konfiguration; `path` dient nur den Fehlermeldungen
+    ///
+    /// Profile aus der Datei ersetzen gleichnamige eingebaute Profile, alle anderen bleiben erhalten.
+    pub fn parse(source: &str, path: &Path) -> Result<Self, ConfigError> {
+        let parsed: Config = toml::from_str(source).map_err(|e| {
+            let (line, column) = e.span().map(|span| position(source, span.start)).unwrap_or((1, 1));
+            ConfigError::Parse {
+                path: path.to_path_buf(),
+                line,
+                column,
+                message: e.message().to_string(),
+            }
+        })?;
+
+        let mut config = Self::builtin();
+        config.default_profile = parsed.default_profile;
+        config.profiles.extend(parsed.profiles);
+        config.schedules = parsed.schedules;
+        config.monitoring = parsed.monitoring;
+        config.validate(source, path)?;
+        Ok(config)
+    }
+
+    /// Prüft Zielnamen, Muster, Zeitpläne und Standardprofil
+    fn validate(&self, source: &str, path: &Path) -> Result<(), ConfigError> {
+        // Ziele anderer Plattformen sind erlaubt, damit eine Datei überall funktioniert
+        let registry = TargetRegistry::with_all_platform_targets();
+
+        for (name, profile) in &self.profiles {
+            for target in &profile.targets {
+                if registry.get(target.get_ref()).is_none() {
+                    let (line, column) = position(source, target.span().start);
+                    return Err(ConfigError::UnknownTarget {
+                        path: path.to_path_buf(),
+                        line,
+                        column,
+                        profile: name.clone(),
+                        target: target.get_ref().clone(),
+                    });
+                }
+            }
+
+            let patterns = profile.excluded_patterns.iter().chain(&profile.included_patterns).flatten();
+            for pattern in patterns {
+                if let Err(error) = patterns::validate(pattern.get_ref()) {
+                    let (line, column) = position(source, pattern.span().start);
+                    return Err(ConfigError::InvalidPattern {
+                        path: path.to_path_buf(),
+                        line,
+                        column,
+                        profile: name.clone(),
+                        message: error.to_string(),
+                    });
+                }
+            }
+        }
+
+        for entry in &self.schedules {
+            let checked = entry.to_schedule().and_then(|schedule| {
+                if self.profiles.contains_key(&schedule.profile) {
+                    Ok(())
+                } else {
+                    Err((entry.profile.span().start, format!("Unbekanntes Profil \"{}\"", schedule.profile)))
+                }
+            });
+            if let Err((offset, message)) = checked {
+                let (line, column) = position(source, offset);
+                return Err(ConfigError::InvalidSchedule {
+                    path: path.to_path_buf(),
+                    line,
+                    column,
+                    message,
+                });
+            }
+        }
+
+        if let Some(default) = &self.default_profile {
+            if !self.profiles.contains_key(default.get_ref()) {
+                let (line, column) = position(source, default.span().start);
+                return Err(ConfigError::UnknownProfile {
+                    path: path.to_path_buf(),
+                    line,
+                    column,
+                    name: default.get_ref().clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.get(name)
+    }
+
+    /// Namen aller Profile in alphabetischer Reihenfolge
+    pub fn profile_names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// Geplante Bereinigungen in der Reihenfolge der Datei
+    pub fn schedules(&self) -> Vec<Schedule> {
+        // Beim Laden geprüft, ungültige Einträge gibt es hier nicht
+        self.schedules.iter().filter_map(|entry| entry.to_schedule().ok()).collect()
+    }
+
+    /// Einstellungen der Systemüberwachung
+    pub fn monitoring(&self) -> &MonitoringSettings {
+        &self.monitoring
+    }
+
+    /// In der Datei festgelegtes Standardprofil
+    pub fn default_profile(&self) -> Option<&str> {
+        self.default_profile.as_ref().map(|name| name.get_ref().as_str())
+    }
+}
+
+fn io_error(path: &Path, error: &io::Error) -> ConfigError {
+    ConfigError::Io {
+        path: path.to_path_buf(),
+        message: error.to_string(),
+    }
+}
+
+/// Zeile und Spalte (1-basiert) eines Byte-Offsets
+fn position(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map(|l| l.chars().count()).unwrap_or(0) + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cleaning::DeletionMode;
+
+    fn parse(source: &str) -> Result<Config, ConfigError> {
+        Config::parse(source, Path::new("config.toml"))
+    }
+
+    #[test]
+    fn test_builtin_profiles() {
+        let config = Config::builtin();
+        assert_eq!(config.profile_names().collect::<Vec<_>>(), vec!["deep", "dev-caches", "quick"]);
+
+        let registry = TargetRegistry::with_builtin_targets();
+        let dev = config.profile("dev-caches").unwrap();
+        assert!(dev.select(&registry).iter().all(|t| t.category() == TargetCategory::Developer));
+        assert_eq!(config.profile("deep").unwrap().select(&registry).len(), registry.len());
+    }
+
+    #[test]
+    fn test_profile_overrides_options() {
+        let config = parse(
+            r#"
+default_profile = "logs"
+
+[profiles.logs]
+targets = ["npm-cache", "user-temp"]
+target_extensions = ["log"]
+excluded_patterns = ["keep*"]
+deletion_mode = "quarantine"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.default_profile(), Some("logs"));
+        assert!(config.profile("quick").is_some());
+
+        let options = config.profile("logs").unwrap().options();
+        assert_eq!(options.target_extensions, Some(vec!["log".to_string()]));
+        assert_eq!(options.excluded_patterns, vec!["keep*"]);
+        assert_eq!(options.deletion_mode, DeletionMode::Quarantine);
+        assert_eq!(options.min_file_age_days, 1);
+    }
+
+    #[test]
+    fn test_schedules() {
+        let config = parse(
+            r#"
+[[schedules]]
+profile = "dev-caches"
+daily = "02:00"
+
+[[schedules]]
+profile = "quick"
+free_disk_below = 10
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.schedules(),
+            vec![
+                Schedule { profile: "dev-caches".to_string(), trigger: Trigger::Daily(chrono::NaiveTime::from_hms_opt(2, 0, 0).unwrap()) },
+                Schedule { profile: "quick".to_string(), trigger: Trigger::FreeDiskBelow(10.0) },
+            ]
+        );
+
+        let error = parse("[[schedules]]\nprofile = \"quick\"\ndaily = \"25:00\"\n").unwrap_err();
+        assert!(matches!(error, ConfigError::InvalidSchedule { line: 3, column: 9, .. }), "{:?}", error);
+        let error = parse("[[schedules]]\nprofile = \"fehlt\"\ndaily = \"02:00\"\n").unwrap_err();
+        assert!(matches!(error, ConfigError::InvalidSchedule { line: 2, .. }), "{:?}", error);
+        let error = parse("[[schedules]]\nprofile = \"quick\"\n").unwrap_err();
+        assert!(matches!(error, ConfigError::InvalidSchedule { line: 2, .. }), "{:?}", error);
+    }
+
+    #[test]
+    fn test_monitoring_settings() {
+        assert_eq!(Config::builtin().monitoring().history_window(), std::time::Duration::from_secs(600));
+
+        let config = parse("[monitoring]\nhistory_minutes = 30\n").unwrap();
+        assert_eq!(config.monitoring().history_window(), std::time::Duration::from_secs(1800));
+        assert_eq!(config.monitoring().interval(), std::time::Duration::from_secs(2));
+
+        let config = parse("[monitoring]\nhistory_minutes = 9223372036854775807\n").unwrap();
+        assert_eq!(config.monitoring().history_window(), std::time::Duration::from_secs(24 * 60 * 60));
+
+        let error = parse("[monitoring]\nhistory = 30\n").unwrap_err();
+        assert!(matches!(error, ConfigError::Parse { line: 2, .. }), "{:?}", error);
+    }
+
+    #[test]
+    fn test_errors_carry_line_numbers() {
+        let error = parse("[profiles.quick]\nmin_file_age_days = 3\nmin_age = 4\n").unwrap_err();
+        assert!(matches!(error, ConfigError::Parse { line: 3, .. }), "{:?}", error);
+
+        let error = parse("[profiles.quick]\nmin_file_age_days = \"drei\"\n").unwrap_err();
+        assert!(matches!(error, ConfigError::Parse { line: 2, .. }), "{:?}", error);
+
+        let error = parse("[profiles.x]\ntargets = [\n  \"npm-cache\",\n  \"gibt-es-nicht\",\n]\n").unwrap_err();
+        assert_eq!(
+            error,
+            ConfigError::UnknownTarget {
+                path: PathBuf::from("config.toml"),
+                line: 4,
+                column: 3,
+                profile: "x".to_string(),
+                target: "gibt-es-nicht".to_string(),
+            }
+        );
+
+        let error = parse("[profiles.x]\nexcluded_patterns = [\"**/Cache/index\", \"regex:(offen\"]\n").unwrap_err();
+        assert!(matches!(error, ConfigError::InvalidPattern { line: 2, column: 40, .. }), "{:?}", error);
+
+        let error = parse("\n\ndefault_profile = \"fehlt\"\n").unwrap_err();
+        assert!(matches!(error, ConfigError::UnknownProfile { line: 3, column: 19, .. }), "{:?}", error);
+    }
+}
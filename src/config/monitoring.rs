@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Längster Zeitraum der Verlaufsdiagramme in Minuten (ein Tag); größere Angaben werden begrenzt
+pub const MAX_HISTORY_MINUTES: u64 = 24 * 60;
+
+/// Einstellungen der Systemüberwachung unter `[monitoring]`
+///
+/// ```toml
+/// [monitoring]
+/// history_minutes = 30
+/// primary_disk = "/home"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct MonitoringSettings {
+    /// Zeitraum der Verlaufsdiagramme in Minuten
+    pub history_minutes: u64,
+    /// Abstand zwischen zwei Messungen in Sekunden
+    pub interval_secs: u64,
+    /// Pfad, dessen Laufwerk als Hauptlaufwerk gilt (ohne Angabe das Systemlaufwerk)
+    pub primary_disk: Option<PathBuf>,
+    /// Anzahl angezeigter Prozesse
+    pub process_rows: usize,
+}
+
+impl Default for MonitoringSettings {
+    fn default() -> Self {
+        Self {
+            history_minutes: 10,
+            interval_secs: 2,
+            primary_disk: None,
+            process_rows: 25,
+        }
+    }
+}
+
+impl MonitoringSettings {
+    /// Zeitraum der Verlaufsdiagramme, zwischen einer Minute und [`MAX_HISTORY_MINUTES`]
+    pub fn history_window(&self) -> Duration {
+        Duration::from_secs(self.history_minutes.clamp(1, MAX_HISTORY_MINUTES) * 60)
+    }
+
+    /// Abstand zwischen zwei Messungen, mindestens eine Sekunde
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs.max(1))
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use toml::Spanned;
+
+use crate::cleaning::{CleanTarget, CleaningOptions, DeletionMode, TargetCategory, TargetRegistry};
+
+/// Benanntes Bereinigungsprofil: Auswahl der Ziele plus abweichende Optionen
+///
+/// Nicht gesetzte Felder behalten die Werte aus `CleaningOptions::default()`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub description: Option<String>,
+    /// Ziele per ID oder Anzeigename
+    #[serde(default)]
+    pub targets: Vec<Spanned<String>>,
+    /// Alle Ziele dieser Kategorien
+    #[serde(default)]
+    pub categories: Vec<TargetCategory>,
+
+    pub min_file_age_days: Option<u64>,
+    pub recursive: Option<bool>,
+    pub remove_empty_dirs: Option<bool>,
+    pub target_extensions: Option<Vec<String>>,
+    pub max_files: Option<usize>,
+    pub max_file_size: Option<u64>,
+    pub min_file_size: Option<u64>,
+    /// Muster wie in `CleaningOptions`; mit Position für Fehlermeldungen
+    pub excluded_patterns: Option<Vec<Spanned<String>>>,
+    pub included_patterns: Option<Vec<Spanned<String>>>,
+    pub verbose: Option<bool>,
+    pub dry_run: Option<bool>,
+    pub log_files: Option<bool>,
+    pub deletion_mode: Option<DeletionMode>,
+    pub quarantine_dir: Option<PathBuf>,
+    pub quarantine_retention_days: Option<u64>,
+    pub project_roots: Option<Vec<PathBuf>>,
+    pub stale_project_days: Option<u64>,
+    pub worker_threads: Option<usize>,
+}
+
+impl Profile {
+    /// Profil für die angegebenen Kategorien ohne weitere Anpassungen
+    pub fn for_categories(description: &str, categories: &[TargetCategory]) -> Self {
+        Self {
+            description: Some(description.to_string()),
+            categories: categories.to_vec(),
+            ..Self::default()
+        }
+    }
+
+    /// Ausgewählte Ziele; ohne `targets` und `categories` alle Ziele der Registry
+    ///
+    /// Unbekannte Ziele werden bereits beim Laden der Konfiguration gemeldet und hier übergangen.
+    pub fn select<'a>(&self, registry: &'a TargetRegistry) -> Vec<&'a dyn CleanTarget> {
+        if self.targets.is_empty() && self.categories.is_empty() {
+            return registry.iter().collect();
+        }
+
+        // Reihenfolge der Registry beibehalten, damit überlappende Ziele gleich zugeordnet werden
+        registry
+            .iter()
+            .filter(|target| {
+                self.categories.contains(&target.category())
+                    || self.targets.iter().any(|name| {
+                        registry.get(name.get_ref()).is_some_and(|selected| selected.id() == target.id())
+                    })
+            })
+            .collect()
+    }
+
+    /// Standardoptionen mit den Anpassungen dieses Profils
+    pub fn options(&self) -> CleaningOptions {
+        let mut options = CleaningOptions::default();
+        self.apply(&mut options);
+        options
+    }
+
+    /// Überschreibt die im Profil gesetzten Felder in `options`
+    pub fn apply(&self, options: &mut CleaningOptions) {
+        fn set<T: Clone>(target: &mut T, value: &Option<T>) {
+            if let Some(value) = value {
+                *target = value.clone();
+            }
+        }
+
+        set(&mut options.min_file_age_days, &self.min_file_age_days);
+        set(&mut options.recursive, &self.recursive);
+        set(&mut options.remove_empty_dirs, &self.remove_empty_dirs);
+        if self.target_extensions.is_some() {
+            options.target_extensions = self.target_extensions.clone();
+        }
+        set(&mut options.max_files, &self.max_files);
+        set(&mut options.max_file_size, &self.max_file_size);
+        set(&mut options.min_file_size, &self.min_file_size);
+        if let Some(patterns) = &self.excluded_patterns {
+            options.excluded_patterns = patterns.iter().map(|p| p.get_ref().clone()).collect();
+        }
+        if let Some(patterns) = &self.included_patterns {
+            options.included_patterns = patterns.iter().map(|p| p.get_ref().clone()).collect();
+        }
+        set(&mut options.verbose, &self.verbose);
+        set(&mut options.dry_run, &self.dry_run);
+        set(&mut options.log_files, &self.log_files);
+        set(&mut options.deletion_mode, &self.deletion_mode);
+        if self.quarantine_dir.is_some() {
+            options.quarantine_dir = self.quarantine_dir.clone();
+        }
+        set(&mut options.quarantine_retention_days, &self.quarantine_retention_days);
+        set(&mut options.project_roots, &self.project_roots);
+        set(&mut options.stale_project_days, &self.stale_project_days);
+        set(&mut options.worker_threads, &self.worker_threads);
+    }
+}
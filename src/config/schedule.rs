@@ -0,0 +1,89 @@
+use chrono::NaiveTime;
+use serde::Deserialize;
+use toml::Spanned;
+
+/// Eintrag unter `[[schedules]]` wie in der Datei, mit Positionen für Fehlermeldungen
+///
+/// ```toml
+/// [[schedules]]
+/// profile = "dev-caches"
+/// daily = "02:00"
+///
+/// [[schedules]]
+/// profile = "quick"
+/// free_disk_below = 10
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ScheduleEntry {
+    pub(super) profile: Spanned<String>,
+    pub(super) daily: Option<Spanned<String>>,
+    /// Prozent freier Speicher auf dem Hauptlaufwerk
+    pub(super) free_disk_below: Option<Spanned<f32>>,
+}
+
+/// Auslöser einer geplanten Bereinigung
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    /// Täglich zur angegebenen lokalen Uhrzeit
+    Daily(NaiveTime),
+    /// Sobald weniger als so viel Prozent des Hauptlaufwerks frei sind
+    FreeDiskBelow(f32),
+}
+
+impl std::fmt::Display for Trigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Trigger::Daily(time) => write!(f, "täglich um {}", time.format("%H:%M")),
+            Trigger::FreeDiskBelow(percent) => write!(f, "bei weniger als {}% freiem Speicher", percent),
+        }
+    }
+}
+
+/// Geprüfte geplante Bereinigung
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schedule {
+    pub profile: String,
+    pub trigger: Trigger,
+}
+
+impl Schedule {
+    /// Eindeutiger Schlüssel, unter dem der letzte Lauf gespeichert wird
+    pub fn key(&self) -> String {
+        match self.trigger {
+            Trigger::Daily(time) => format!("{}@daily-{}", self.profile, time.format("%H:%M")),
+            Trigger::FreeDiskBelow(percent) => format!("{}@disk-{}", self.profile, percent),
+        }
+    }
+}
+
+impl ScheduleEntry {
+    /// Übersetzt den Eintrag; der Fehler enthält den Byte-Offset der beanstandeten Stelle
+    pub(super) fn to_schedule(&self) -> Result<Schedule, (usize, String)> {
+        let trigger = match (&self.daily, &self.free_disk_below) {
+            (Some(daily), None) => {
+                let time = NaiveTime::parse_from_str(daily.get_ref(), "%H:%M")
+                    .map_err(|_| (daily.span().start, format!("Ungültige Uhrzeit \"{}\", erwartet HH:MM", daily.get_ref())))?;
+                Trigger::Daily(time)
+            }
+            (None, Some(percent)) => {
+                let value = *percent.get_ref();
+                if !(0.0..=100.0).contains(&value) {
+                    return Err((percent.span().start, format!("Prozentwert {} liegt nicht zwischen 0 und 100", value)));
+                }
+                Trigger::FreeDiskBelow(value)
+            }
+            _ => {
+                return Err((
+                    self.profile.span().start,
+                    String::from("Genau eines von \"daily\" oder \"free_disk_below\" angeben"),
+                ))
+            }
+        };
+
+        Ok(Schedule {
+            profile: self.profile.get_ref().clone(),
+            trigger,
+        })
+    }
+}
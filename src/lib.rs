@@ -1,3 +1,6 @@
 pub mod cleaning; 
+pub mod cli;
+pub mod config;
 pub mod ui;
-pub mod monitoring; 
\ No newline at end of file
+pub mod monitoring;
+pub mod scheduler; 
\ No newline at end of file
@@ -3,8 +3,12 @@ use iced::{Application, Settings};
 
 fn main() -> iced::Result {
     // Starte die GUI-Anwendung mit deaktiviertem Drag-and-Drop
+    #[allow(unused_mut)]
     let mut settings = Settings::default();
-    settings.window.platform_specific.drag_and_drop = false;
+    #[cfg(windows)]
+    {
+        settings.window.platform_specific.drag_and_drop = false;
+    }
     
     RustyCleanApp::run(settings)
 }
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// Größe eines Sektors in `/proc/diskstats`, unabhängig vom Gerät
+const SECTOR_SIZE: u64 = 512;
+
+/// Durchsatz eines Blockgeräts
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceIo {
+    pub name: String,          // Gerätename, z.B. sda oder nvme0n1
+    pub read_per_sec: u64,     // Gelesen seit der letzten Messung in Bytes/s
+    pub written_per_sec: u64,  // Geschrieben seit der letzten Messung in Bytes/s
+    pub total_read: u64,       // Seit dem Start gelesen in Bytes
+    pub total_written: u64,    // Seit dem Start geschrieben in Bytes
+}
+
+/// Gelesene und geschriebene Bytes eines Geräts seit dem Start
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IoCounters {
+    pub read: u64,
+    pub written: u64,
+}
+
+/// Zähler aller Blockgeräte; außerhalb von Linux leer
+///
+/// Partitionen, Loop-, RAM- und virtuelle Geräte werden übergangen, damit jede Platte nur einmal zählt.
+#[cfg(target_os = "linux")]
+pub fn read_counters() -> HashMap<String, IoCounters> {
+    let Ok(content) = std::fs::read_to_string("/proc/diskstats") else {
+        return HashMap::new();
+    };
+    parse_diskstats(&content)
+        .into_iter()
+        .filter(|(name, _)| is_physical_device(Path::new("/sys/block"), name))
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_counters() -> HashMap<String, IoCounters> {
+    HashMap::new()
+}
+
+/// Ganze Platte statt Partition, Loop- oder RAM-Gerät
+///
+/// Device-Mapper- und RAID-Geräte (dm-*, md*) führen ihre Platten unter `slaves`; deren Zugriffe
+/// stehen schon bei den Platten selbst und würden sonst doppelt gezählt.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn is_physical_device(sys_block: &Path, name: &str) -> bool {
+    if name.starts_with("loop") || name.starts_with("ram") {
+        return false;
+    }
+    let device = sys_block.join(name);
+    if !device.exists() {
+        return false;
+    }
+    std::fs::read_dir(device.join("slaves")).map_or(true, |mut slaves| slaves.next().is_none())
+}
+
+/// Liest Gerätename und Sektorzähler aus dem Inhalt von `/proc/diskstats`
+///
+/// Spalten: major minor name reads merged sectors_read ms writes merged sectors_written ...
+pub fn parse_diskstats(content: &str) -> Vec<(String, IoCounters)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let sectors_read: u64 = fields.get(5)?.parse().ok()?;
+            let sectors_written: u64 = fields.get(9)?.parse().ok()?;
+            Some((
+                fields[2].to_string(),
+                IoCounters { read: sectors_read * SECTOR_SIZE, written: sectors_written * SECTOR_SIZE },
+            ))
+        })
+        .collect()
+}
+
+/// Raten zwischen zwei Ständen der Zähler; Geräte ohne jeden Zugriff fehlen, Ergebnis nach Name sortiert
+pub fn rates(previous: &HashMap<String, IoCounters>, current: &HashMap<String, IoCounters>, elapsed: Duration) -> Vec<DeviceIo> {
+    let mut devices: Vec<DeviceIo> = current
+        .iter()
+        .filter(|(_, counters)| counters.read + counters.written > 0)
+        .map(|(name, counters)| {
+            // Ohne früheren Stand (neues Gerät) gibt es noch keine Rate
+            let before = previous.get(name).copied().unwrap_or(*counters);
+            DeviceIo {
+                name: name.clone(),
+                read_per_sec: super::system_info::rate(counters.read.saturating_sub(before.read), elapsed),
+                written_per_sec: super::system_info::rate(counters.written.saturating_sub(before.written), elapsed),
+                total_read: counters.read,
+                total_written: counters.written,
+            }
+        })
+        .collect();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_and_rates() {
+        let before = "   8       0 sda 100 0 2000 50 40 0 1000 30 0 80 80 0 0 0 0\n   8       1 sda1 90 0 1800 45 40 0 1000 30 0 70 75 0 0 0 0\n";
+        let after = "   8       0 sda 120 0 6000 60 50 0 3000 40 0 90 100 0 0 0 0\n   7       0 loop0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0\nkaputt\n";
+
+        let parsed = parse_diskstats(before);
+        assert_eq!(parsed[0], ("sda".to_string(), IoCounters { read: 2000 * 512, written: 1000 * 512 }));
+        assert_eq!(parsed.len(), 2);
+
+        let previous: HashMap<_, _> = parsed.into_iter().collect();
+        let current: HashMap<_, _> = parse_diskstats(after).into_iter().collect();
+        let devices = rates(&previous, &current, Duration::from_secs(2));
+        assert_eq!(
+            devices,
+            vec![DeviceIo {
+                name: "sda".to_string(),
+                read_per_sec: 4000 * 512 / 2,
+                written_per_sec: 2000 * 512 / 2,
+                total_read: 6000 * 512,
+                total_written: 3000 * 512,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_virtual_devices_are_skipped() {
+        let sys_block = TempDir::new().unwrap();
+        for dir in ["sda/slaves", "dm-0/slaves/sda2", "md0/slaves/sdb1", "loop0"] {
+            std::fs::create_dir_all(sys_block.path().join(dir)).unwrap();
+        }
+
+        assert!(is_physical_device(sys_block.path(), "sda"));
+        assert!(!is_physical_device(sys_block.path(), "sda1"));
+        assert!(!is_physical_device(sys_block.path(), "dm-0"));
+        assert!(!is_physical_device(sys_block.path(), "md0"));
+        assert!(!is_physical_device(sys_block.path(), "loop0"));
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::system_info::SystemStatus;
+
+/// Eine Messung der Auslastung, alle Werte in Prozent
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub time: Instant,
+    pub cpu_usage: f32,
+    pub memory_usage: f32,
+    pub disk_usage: f32,
+}
+
+impl Sample {
+    pub fn from_status(status: &SystemStatus, time: Instant) -> Self {
+        Self {
+            time,
+            cpu_usage: status.cpu_usage,
+            memory_usage: percentage(status.memory_used, status.memory_total),
+            disk_usage: percentage(status.disk_used, status.disk_total),
+        }
+    }
+
+    pub fn value(&self, metric: Metric) -> f32 {
+        match metric {
+            Metric::Cpu => self.cpu_usage,
+            Metric::Memory => self.memory_usage,
+            Metric::Disk => self.disk_usage,
+        }
+    }
+}
+
+/// Anteil `used` an `total` in Prozent; 0 bei unbekannter Gesamtgröße
+pub(crate) fn percentage(used: u64, total: u64) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        (used as f64 / total as f64 * 100.0) as f32
+    }
+}
+
+/// Gemessene Größe einer [`Sample`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Cpu,
+    Memory,
+    Disk,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 3] = [Metric::Cpu, Metric::Memory, Metric::Disk];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Metric::Cpu => "CPU",
+            Metric::Memory => "Arbeitsspeicher",
+            Metric::Disk => "Festplatte",
+        }
+    }
+}
+
+/// Minimum, Durchschnitt und Maximum einer Größe im Zeitraum
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: f32,
+    pub avg: f32,
+    pub max: f32,
+}
+
+/// Ringpuffer der Messungen eines festen Zeitraums; ältere Messungen fallen heraus
+#[derive(Debug, Clone)]
+pub struct SampleHistory {
+    window: Duration,
+    samples: VecDeque<Sample>,
+}
+
+impl SampleHistory {
+    pub fn new(window: Duration) -> Self {
+        Self { window, samples: VecDeque::new() }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Ändert den Zeitraum; bei einer Verkleinerung werden ältere Messungen verworfen
+    pub fn set_window(&mut self, window: Duration) {
+        self.window = window;
+        self.evict();
+    }
+
+    pub fn push(&mut self, sample: Sample) {
+        self.samples.push_back(sample);
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        let Some(newest) = self.samples.back().map(|sample| sample.time) else {
+            return;
+        };
+        while self.samples.front().is_some_and(|oldest| newest.duration_since(oldest.time) > self.window) {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Älteste zuerst
+    pub fn iter(&self) -> impl Iterator<Item = &Sample> {
+        self.samples.iter()
+    }
+
+    pub fn latest(&self) -> Option<&Sample> {
+        self.samples.back()
+    }
+
+    pub fn stats(&self, metric: Metric) -> Option<Stats> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut stats = Stats { min: f32::MAX, avg: 0.0, max: f32::MIN };
+        for sample in &self.samples {
+            let value = sample.value(metric);
+            stats.min = stats.min.min(value);
+            stats.max = stats.max.max(value);
+            stats.avg += value;
+        }
+        stats.avg /= self.samples.len() as f32;
+        Some(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(time: Instant, cpu_usage: f32) -> Sample {
+        Sample { time, cpu_usage, memory_usage: 50.0, disk_usage: 0.0 }
+    }
+
+    #[test]
+    fn test_window_drops_old_samples() {
+        let start = Instant::now();
+        let mut history = SampleHistory::new(Duration::from_secs(10));
+        assert_eq!(history.stats(Metric::Cpu), None);
+
+        for (second, cpu) in [(0, 80.0), (4, 10.0), (8, 30.0), (12, 20.0)] {
+            history.push(sample(start + Duration::from_secs(second), cpu));
+        }
+        // Die Messung bei 0s liegt mehr als 10s vor der neuesten
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.stats(Metric::Cpu), Some(Stats { min: 10.0, avg: 20.0, max: 30.0 }));
+        assert_eq!(history.stats(Metric::Memory).unwrap().avg, 50.0);
+
+        history.set_window(Duration::from_secs(4));
+        assert_eq!(history.iter().map(|s| s.cpu_usage).collect::<Vec<_>>(), vec![30.0, 20.0]);
+        assert_eq!(history.latest().unwrap().cpu_usage, 20.0);
+    }
+}
@@ -1,3 +1,9 @@
+pub mod disk_io;
+pub mod history;
+pub mod process_control;
 pub mod system_info;
 
-pub use system_info::*; 
\ No newline at end of file
+pub use disk_io::DeviceIo;
+pub use history::{Metric, Sample, SampleHistory, Stats};
+pub use process_control::{ProcessAction, ProcessActionError};
+pub use system_info::*;
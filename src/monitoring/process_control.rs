@@ -0,0 +1,191 @@
+use std::fmt;
+
+/// Niedrigste (höchste Priorität) und höchste Nice-Stufe
+pub const PRIORITY_RANGE: std::ops::RangeInclusive<i32> = -20..=19;
+
+/// Aktion auf einen Prozess
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessAction {
+    /// Bitten, sich zu beenden (SIGTERM bzw. Schließen-Anforderung unter Windows)
+    Terminate,
+    /// Sofort beenden (SIGKILL bzw. TerminateProcess)
+    Kill,
+    /// Nice-Stufe setzen; kleinere Werte bedeuten höhere Priorität
+    SetPriority(i32),
+}
+
+impl fmt::Display for ProcessAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProcessAction::Terminate => write!(f, "beenden"),
+            ProcessAction::Kill => write!(f, "sofort beenden"),
+            ProcessAction::SetPriority(nice) => write!(f, "auf Priorität {} setzen", nice),
+        }
+    }
+}
+
+/// Fehler einer Prozessaktion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcessActionError {
+    /// PID 1 (init) oder die Anwendung selbst
+    Protected(u32),
+    NotFound(u32),
+    /// PID außerhalb des Wertebereichs des Betriebssystems
+    InvalidPid(u32),
+    PermissionDenied(u32),
+    InvalidPriority(i32),
+    Unsupported(&'static str),
+    Failed(String),
+}
+
+impl fmt::Display for ProcessActionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProcessActionError::Protected(pid) => write!(f, "Prozess {} ist geschützt (Systemstart oder diese Anwendung)", pid),
+            ProcessActionError::NotFound(pid) => write!(f, "Prozess {} existiert nicht mehr", pid),
+            ProcessActionError::InvalidPid(pid) => write!(f, "Ungültige Prozess-ID {}", pid),
+            ProcessActionError::PermissionDenied(pid) => write!(
+                f,
+                "Keine Berechtigung für Prozess {}; er gehört einem anderen Benutzer oder erfordert Administrator- bzw. root-Rechte",
+                pid
+            ),
+            ProcessActionError::InvalidPriority(nice) => write!(
+                f,
+                "Ungültige Priorität {}, erlaubt sind {} bis {}",
+                nice,
+                PRIORITY_RANGE.start(),
+                PRIORITY_RANGE.end()
+            ),
+            ProcessActionError::Unsupported(message) => write!(f, "{}", message),
+            ProcessActionError::Failed(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ProcessActionError {}
+
+/// PID 1 und die eigene Anwendung dürfen nicht angetastet werden
+pub fn is_protected(pid: u32) -> bool {
+    pid <= 1 || pid == std::process::id()
+}
+
+/// Führt eine Aktion auf einen Prozess aus
+pub fn perform(pid: u32, action: ProcessAction) -> Result<(), ProcessActionError> {
+    if is_protected(pid) {
+        return Err(ProcessActionError::Protected(pid));
+    }
+    if let ProcessAction::SetPriority(nice) = action {
+        if !PRIORITY_RANGE.contains(&nice) {
+            return Err(ProcessActionError::InvalidPriority(nice));
+        }
+    }
+    platform::perform(pid, action)
+}
+
+#[cfg(unix)]
+mod platform {
+    use std::io;
+
+    use super::{ProcessAction, ProcessActionError};
+
+    pub fn perform(pid: u32, action: ProcessAction) -> Result<(), ProcessActionError> {
+        // Negative Werte würden bei kill eine ganze Prozessgruppe (oder mit -1 alle Prozesse) treffen
+        let target = libc::pid_t::try_from(pid).map_err(|_| ProcessActionError::InvalidPid(pid))?;
+        let result = match action {
+            // SAFETY: kill und setpriority lesen nur die übergebenen Werte
+            ProcessAction::Terminate => unsafe { libc::kill(target, libc::SIGTERM) },
+            ProcessAction::Kill => unsafe { libc::kill(target, libc::SIGKILL) },
+            ProcessAction::SetPriority(nice) => unsafe { libc::setpriority(libc::PRIO_PROCESS as _, target as libc::id_t, nice) },
+        };
+        if result == 0 {
+            return Ok(());
+        }
+
+        let error = io::Error::last_os_error();
+        match error.raw_os_error() {
+            Some(libc::EPERM) | Some(libc::EACCES) => Err(ProcessActionError::PermissionDenied(pid)),
+            Some(libc::ESRCH) => Err(ProcessActionError::NotFound(pid)),
+            _ => Err(ProcessActionError::Failed(error.to_string())),
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::process::Command;
+
+    use sysinfo::{Pid, PidExt, ProcessExt, System, SystemExt};
+
+    use super::{ProcessAction, ProcessActionError};
+
+    pub fn perform(pid: u32, action: ProcessAction) -> Result<(), ProcessActionError> {
+        match action {
+            // Ohne /F bittet taskkill das Programm, sich selbst zu schließen
+            ProcessAction::Terminate => {
+                let output = Command::new("taskkill")
+                    .args(["/PID", &pid.to_string()])
+                    .output()
+                    .map_err(|e| ProcessActionError::Failed(e.to_string()))?;
+                if output.status.success() {
+                    Ok(())
+                } else {
+                    let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                    if message.contains("Zugriff verweigert") || message.contains("Access is denied") {
+                        Err(ProcessActionError::PermissionDenied(pid))
+                    } else {
+                        Err(ProcessActionError::Failed(message))
+                    }
+                }
+            }
+            ProcessAction::Kill => {
+                let mut system = System::new();
+                let pid = Pid::from_u32(pid);
+                if !system.refresh_process(pid) {
+                    return Err(ProcessActionError::NotFound(pid.as_u32()));
+                }
+                match system.process(pid) {
+                    Some(process) if process.kill() => Ok(()),
+                    // TerminateProcess scheitert fast immer an fehlenden Rechten
+                    Some(_) => Err(ProcessActionError::PermissionDenied(pid.as_u32())),
+                    None => Err(ProcessActionError::NotFound(pid.as_u32())),
+                }
+            }
+            ProcessAction::SetPriority(_) => {
+                Err(ProcessActionError::Unsupported("Prioritäten lassen sich unter Windows noch nicht ändern"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refuses_protected_processes() {
+        let own = std::process::id();
+        assert_eq!(perform(1, ProcessAction::Kill), Err(ProcessActionError::Protected(1)));
+        assert_eq!(perform(own, ProcessAction::Terminate), Err(ProcessActionError::Protected(own)));
+        assert_eq!(perform(own + 1, ProcessAction::SetPriority(40)), Err(ProcessActionError::InvalidPriority(40)));
+        assert!(!is_protected(own + 1));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rejects_pids_outside_pid_t() {
+        assert_eq!(perform(u32::MAX, ProcessAction::Kill), Err(ProcessActionError::InvalidPid(u32::MAX)));
+        let first_negative = i32::MAX as u32 + 1;
+        assert_eq!(perform(first_negative, ProcessAction::Terminate), Err(ProcessActionError::InvalidPid(first_negative)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_renice_and_terminate_child() {
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id();
+
+        assert_eq!(perform(pid, ProcessAction::SetPriority(10)), Ok(()));
+        assert_eq!(perform(pid, ProcessAction::Terminate), Ok(()));
+        child.wait().unwrap();
+    }
+}
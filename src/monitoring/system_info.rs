@@ -1,49 +1,195 @@
-use sysinfo::{System, SystemExt, CpuExt, DiskExt, ProcessExt, Pid, PidExt};
+use super::disk_io::{self, DeviceIo, IoCounters};
+use sysinfo::{System, SystemExt, CpuExt, DiskExt, NetworkExt, NetworksExt, ProcessExt, PidExt, UserExt};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use once_cell::sync::Lazy;
 
 /// Enthält alle gesammelten Systeminformationen
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SystemStatus {
     pub cpu_usage: f32,             // CPU-Auslastung in Prozent
+    pub cores: Vec<CoreInfo>,       // Auslastung und Takt je Kern
     pub memory_used: u64,           // Belegter Arbeitsspeicher in Bytes
     pub memory_total: u64,          // Gesamter Arbeitsspeicher in Bytes
-    pub disk_used: u64,             // Belegter Festplattenplatz in Bytes
-    pub disk_total: u64,            // Gesamter Festplattenplatz in Bytes
-    pub top_processes: Vec<ProcessInfo>, // Top-Prozesse nach CPU-Nutzung
+    pub swap_used: u64,             // Belegter Auslagerungsspeicher in Bytes
+    pub swap_total: u64,            // Gesamter Auslagerungsspeicher in Bytes
+    pub load_average: LoadAverage,  // Systemlast (unter Windows immer 0)
+    pub boot_time: u64,             // Startzeitpunkt in Sekunden seit 1970
+    pub uptime: u64,                // Laufzeit seit dem Start in Sekunden
+    pub disk_used: u64,             // Belegter Platz auf dem Hauptlaufwerk in Bytes
+    pub disk_total: u64,            // Gesamter Platz auf dem Hauptlaufwerk in Bytes
+    pub primary_disk: Option<PathBuf>, // Einhängepunkt des Hauptlaufwerks
+    pub disks: Vec<DiskInfo>,       // Alle Laufwerke nach Einhängepunkt sortiert
+    pub disk_io: Vec<DeviceIo>,     // Durchsatz je Blockgerät (nur Linux)
+    pub networks: Vec<NetworkInfo>, // Netzwerkschnittstellen nach Name sortiert
+    pub processes: Vec<ProcessInfo>, // Alle Prozesse nach CPU-Nutzung sortiert
 }
 
-/// Informationen über einen einzelnen Prozess
+impl SystemStatus {
+    /// Summe der Empfangs- und Senderaten aller Schnittstellen in Bytes pro Sekunde
+    pub fn network_rates(&self) -> (u64, u64) {
+        self.networks.iter().fold((0, 0), |(rx, tx), network| (rx + network.received_per_sec, tx + network.transmitted_per_sec))
+    }
+}
+
+/// Ein eingehängtes Laufwerk
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskInfo {
+    pub name: String,        // Gerätename, z.B. /dev/sda1
+    pub mount_point: PathBuf, // Einhängepunkt bzw. Laufwerksbuchstabe
+    pub file_system: String, // Dateisystem, z.B. ext4 oder NTFS
+    pub total: u64,          // Gesamtgröße in Bytes
+    pub available: u64,      // Freier Platz in Bytes
+    pub removable: bool,     // Wechselmedium (USB-Stick, SD-Karte)
+}
+
+impl DiskInfo {
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.available)
+    }
+}
+
+/// Durchsatz und Zähler einer Netzwerkschnittstelle
 #[derive(Debug, Clone)]
+pub struct NetworkInfo {
+    pub name: String,              // Name der Schnittstelle
+    pub received_per_sec: u64,     // Empfangsrate seit der letzten Messung in Bytes/s
+    pub transmitted_per_sec: u64,  // Senderate seit der letzten Messung in Bytes/s
+    pub total_received: u64,       // Insgesamt empfangen in Bytes
+    pub total_transmitted: u64,    // Insgesamt gesendet in Bytes
+    pub receive_errors: u64,       // Fehler beim Empfangen (gesamt)
+    pub transmit_errors: u64,      // Fehler beim Senden (gesamt)
+}
+
+/// Auslastung eines einzelnen CPU-Kerns
+#[derive(Debug, Clone)]
+pub struct CoreInfo {
+    pub name: String,   // Bezeichnung des Kerns
+    pub usage: f32,     // Auslastung in Prozent
+    pub frequency: u64, // Aktueller Takt in MHz
+}
+
+/// Durchschnittliche Systemlast über 1, 5 und 15 Minuten
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+/// Informationen über einen einzelnen Prozess
+#[derive(Debug, Clone, Default)]
 pub struct ProcessInfo {
     pub name: String,      // Name des Prozesses
     pub pid: u32,          // Prozess-ID
+    pub parent_pid: Option<u32>, // Prozess-ID des Elternprozesses
+    pub user: Option<String>, // Name des Benutzers, unter dem der Prozess läuft
+    pub start_time: u64,   // Startzeitpunkt in Sekunden seit 1970
+    pub command: String,   // Vollständige Befehlszeile
+    pub status: String,    // Zustand, z.B. "Runnable" oder "Sleeping"
     pub cpu_usage: f32,    // CPU-Auslastung des Prozesses
     pub memory_usage: u64, // Speichernutzung des Prozesses in Bytes
+    pub disk_read_per_sec: u64,    // Von der Festplatte gelesen in Bytes/s
+    pub disk_written_per_sec: u64, // Auf die Festplatte geschrieben in Bytes/s
+}
+
+impl ProcessInfo {
+    /// Enthält Name, Befehlszeile, Benutzer oder PID den Suchtext (ohne Groß-/Kleinschreibung)?
+    pub fn matches(&self, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+        filter.is_empty()
+            || self.name.to_lowercase().contains(&filter)
+            || self.command.to_lowercase().contains(&filter)
+            || self.user.as_ref().is_some_and(|user| user.to_lowercase().contains(&filter))
+            || self.pid.to_string() == filter
+    }
+}
+
+/// Sortierung der Prozessliste; Zahlenwerte größter zuerst, PID und Name aufsteigend
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProcessSort {
+    #[default]
+    Cpu,
+    Memory,
+    DiskRead,
+    DiskWrite,
+    Pid,
+    Name,
+}
+
+/// Sortiert Prozesse nach dem gewählten Wert
+pub fn sort_processes(processes: &mut [ProcessInfo], sort: ProcessSort) {
+    match sort {
+        ProcessSort::Pid => processes.sort_by_key(|p| p.pid),
+        ProcessSort::Name => processes.sort_by_cached_key(|p| (p.name.to_lowercase(), p.pid)),
+        ProcessSort::Cpu => processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap_or(std::cmp::Ordering::Equal)),
+        ProcessSort::Memory => processes.sort_by_key(|p| std::cmp::Reverse(p.memory_usage)),
+        ProcessSort::DiskRead => processes.sort_by_key(|p| std::cmp::Reverse(p.disk_read_per_sec)),
+        ProcessSort::DiskWrite => processes.sort_by_key(|p| std::cmp::Reverse(p.disk_written_per_sec)),
+    }
+}
+
+/// System-Objekt mit dem Zeitpunkt der letzten Aktualisierung, aus dem die Raten berechnet werden
+struct Sampler {
+    system: System,
+    last_refresh: Instant,
+    /// Stand der Gerätezähler bei der letzten Aktualisierung
+    disk_counters: HashMap<String, IoCounters>,
+}
+
+impl Sampler {
+    /// Aktualisiert alle Daten und liefert die seit der letzten Aktualisierung vergangene Zeit
+    fn refresh(&mut self) -> Duration {
+        self.system.refresh_all();
+        // Auch neu eingehängte Laufwerke erfassen
+        self.system.refresh_disks_list();
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refresh);
+        self.last_refresh = now;
+        elapsed
+    }
 }
 
 /// Singleton-System-Instanz (thread-sicher über Mutex)
-static SYSTEM: Lazy<Mutex<System>> = Lazy::new(|| {
-    let mut sys = System::new_all();
-    sys.refresh_all();
-    Mutex::new(sys)
+static SYSTEM: Lazy<Mutex<Sampler>> = Lazy::new(|| {
+    let mut system = System::new_all();
+    system.refresh_all();
+    Mutex::new(Sampler { system, last_refresh: Instant::now(), disk_counters: disk_io::read_counters() })
 });
 
+/// Einhängepunkt oder Pfad des Hauptlaufwerks aus der Konfiguration
+static PRIMARY_DISK: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Legt fest, welches Laufwerk als Hauptlaufwerk gilt (das Laufwerk, auf dem `path` liegt)
+///
+/// Ohne Angabe ist es das Laufwerk mit dem Systemverzeichnis (`/` bzw. `%SystemDrive%`).
+pub fn set_primary_disk(path: Option<PathBuf>) {
+    if let Ok(mut primary) = PRIMARY_DISK.lock() {
+        *primary = path;
+    }
+}
+
 /// Initialisiert das System-Monitoring (aktualisiert einfach die Daten)
 pub fn init_monitoring() {
-    if let Ok(mut sys) = SYSTEM.lock() {
-        sys.refresh_all();
+    if let Ok(mut sampler) = SYSTEM.lock() {
+        sampler.refresh();
     }
 }
 
 /// Aktualisiert die Systemdaten und gibt einen Snapshot zurück
 pub fn get_system_status() -> Result<SystemStatus, &'static str> {
-    let mut sys_guard = match SYSTEM.lock() {
+    let mut sampler = match SYSTEM.lock() {
         Ok(guard) => guard,
         Err(_) => return Err("Konnte keine Sperre auf das System-Objekt erhalten"),
     };
-    
-    sys_guard.refresh_all();
+
+    let elapsed = sampler.refresh();
+    let disk_counters = disk_io::read_counters();
+    let disk_io = disk_io::rates(&sampler.disk_counters, &disk_counters, elapsed);
+    sampler.disk_counters = disk_counters;
+    let sys_guard = &sampler.system;
 
     // CPU-Auslastung berechnen (Durchschnitt aller Kerne)
     let cpu_usage = if sys_guard.cpus().is_empty() {
@@ -53,66 +199,157 @@ pub fn get_system_status() -> Result<SystemStatus, &'static str> {
         total / sys_guard.cpus().len() as f32
     };
 
+    let cores = sys_guard
+        .cpus()
+        .iter()
+        .map(|cpu| CoreInfo {
+            name: cpu.name().to_string(),
+            usage: cpu.cpu_usage(),
+            frequency: cpu.frequency(),
+        })
+        .collect();
+
     // Arbeitsspeicher-Informationen direkt in KB verwenden
     let memory_used = sys_guard.used_memory();  // Direkt in KB
     let memory_total = sys_guard.total_memory();  // Direkt in KB
+    let swap_used = sys_guard.used_swap();
+    let swap_total = sys_guard.total_swap();
 
-    // Nimm nur das Hauptlaufwerk (normalerweise C:)
-    let mut disk_used = 0;
-    let mut disk_total = 0;
-    
-    // Finde das Hauptsystemlaufwerk (i.d.R. das mit der größten Kapazität und echtem Dateisystem)
-    for disk in sys_guard.disks() {
-        let mount_point = disk.mount_point().to_string_lossy();
-        // Auf Windows ist das Systemlaufwerk typischerweise C:
-        if (mount_point.contains("C:") || mount_point.contains("/")) && disk.total_space() > 10_000_000_000 {
-            disk_used = disk.total_space() - disk.available_space();
-            disk_total = disk.total_space();
-            break; // Stoppen nach dem ersten passenden Laufwerk
-        }
-    }
-    
-    // Fallback: Nimm das größte Laufwerk, falls kein C: gefunden wurde
-    if disk_total == 0 {
-        let mut max_size = 0;
-        for disk in sys_guard.disks() {
-            if disk.total_space() > max_size && disk.total_space() < 10_000_000_000_000 {
-                max_size = disk.total_space();
-                disk_used = disk.total_space() - disk.available_space();
-                disk_total = disk.total_space();
-            }
-        }
-    }
+    let load = sys_guard.load_average();
+    let load_average = LoadAverage {
+        one: load.one,
+        five: load.five,
+        fifteen: load.fifteen,
+    };
+
+    let disks = disk_infos(sys_guard);
+    let configured = PRIMARY_DISK.lock().ok().and_then(|primary| primary.clone());
+    let primary = primary_disk(&disks, configured.as_deref());
+    let (disk_used, disk_total) = primary.map(|disk| (disk.used(), disk.total)).unwrap_or_default();
+    let primary_disk = primary.map(|disk| disk.mount_point.clone());
+
+    // Netzwerk: die Zähler seit der letzten Aktualisierung ergeben die Rate
+    let mut networks: Vec<NetworkInfo> = sys_guard
+        .networks()
+        .iter()
+        .map(|(name, data)| NetworkInfo {
+            name: name.clone(),
+            received_per_sec: rate(data.received(), elapsed),
+            transmitted_per_sec: rate(data.transmitted(), elapsed),
+            total_received: data.total_received(),
+            total_transmitted: data.total_transmitted(),
+            receive_errors: data.total_errors_on_received(),
+            transmit_errors: data.total_errors_on_transmitted(),
+        })
+        .collect();
+    networks.sort_by(|a, b| a.name.cmp(&b.name));
 
     // Alle Prozesse sammeln
     let mut processes: Vec<ProcessInfo> = Vec::new();
     let cpu_count = sys_guard.cpus().len() as f32;
     
     for (pid, process) in sys_guard.processes() {
+        let disk_usage = process.disk_usage();
+        let user = process
+            .user_id()
+            .and_then(|uid| sys_guard.get_user_by_id(uid))
+            .map(|user| user.name().to_string());
         processes.push(ProcessInfo {
             name: process.name().to_string(),
             pid: pid.as_u32(),
+            parent_pid: process.parent().map(|parent| parent.as_u32()),
+            user,
+            start_time: process.start_time(),
+            command: process.cmd().join(" "),
+            status: process.status().to_string(),
             cpu_usage: process.cpu_usage() / cpu_count, // CPU-Nutzung durch Anzahl der Kerne teilen
             memory_usage: process.memory(), // Speichernutzung direkt in KB ohne falsche Umrechnung
+            disk_read_per_sec: rate(disk_usage.read_bytes, elapsed),
+            disk_written_per_sec: rate(disk_usage.written_bytes, elapsed),
         });
     }
 
     // Prozesse nach CPU-Auslastung sortieren (absteigend)
-    processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap_or(std::cmp::Ordering::Equal));
-
-    // Top-5 Prozesse auswählen
-    let top_processes = processes.into_iter().take(5).collect();
+    sort_processes(&mut processes, ProcessSort::Cpu);
 
     Ok(SystemStatus {
         cpu_usage,
+        cores,
         memory_used,
         memory_total,
+        swap_used,
+        swap_total,
+        load_average,
+        boot_time: sys_guard.boot_time(),
+        uptime: sys_guard.uptime(),
         disk_used,
         disk_total,
-        top_processes,
+        primary_disk,
+        disks,
+        disk_io,
+        networks,
+        processes,
     })
 }
 
+/// Aktualisiert nur die Laufwerksliste und gibt alle Laufwerke zurück
+pub fn disks() -> Result<Vec<DiskInfo>, &'static str> {
+    let mut sampler = SYSTEM.lock().map_err(|_| "Konnte keine Sperre auf das System-Objekt erhalten")?;
+    sampler.system.refresh_disks_list();
+    Ok(disk_infos(&sampler.system))
+}
+
+fn disk_infos(system: &System) -> Vec<DiskInfo> {
+    let mut disks: Vec<DiskInfo> = system
+        .disks()
+        .iter()
+        .map(|disk| DiskInfo {
+            name: disk.name().to_string_lossy().into_owned(),
+            mount_point: disk.mount_point().to_path_buf(),
+            file_system: String::from_utf8_lossy(disk.file_system()).into_owned(),
+            total: disk.total_space(),
+            available: disk.available_space(),
+            removable: disk.is_removable(),
+        })
+        .collect();
+    disks.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    disks
+}
+
+/// Laufwerk, auf dem `configured` (ohne Angabe das Systemverzeichnis) liegt
+///
+/// Gewählt wird der längste passende Einhängepunkt, damit z.B. `/home` auf einem eigenen
+/// Laufwerk Vorrang vor `/` hat. Passt keiner, gilt das größte Laufwerk.
+pub fn primary_disk<'a>(disks: &'a [DiskInfo], configured: Option<&Path>) -> Option<&'a DiskInfo> {
+    let target = configured.map(Path::to_path_buf).unwrap_or_else(system_root);
+    disks
+        .iter()
+        .filter(|disk| target.starts_with(&disk.mount_point))
+        .max_by_key(|disk| disk.mount_point.components().count())
+        .or_else(|| disks.iter().max_by_key(|disk| disk.total))
+}
+
+#[cfg(windows)]
+fn system_root() -> PathBuf {
+    let drive = std::env::var("SystemDrive").unwrap_or_else(|_| String::from("C:"));
+    PathBuf::from(format!("{}\\", drive))
+}
+
+#[cfg(not(windows))]
+fn system_root() -> PathBuf {
+    PathBuf::from("/")
+}
+
+/// Bytes pro Sekunde aus einer Menge und der dafür gemessenen Zeit
+pub(crate) fn rate(bytes: u64, elapsed: Duration) -> u64 {
+    let seconds = elapsed.as_secs_f64();
+    if seconds > 0.0 {
+        (bytes as f64 / seconds).round() as u64
+    } else {
+        0
+    }
+}
+
 /// Formatiert Bytes in eine lesbare Größe
 pub fn format_bytes(bytes: u64) -> String {
     const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
@@ -138,3 +375,81 @@ pub fn format_bytes(bytes: u64) -> String {
 pub fn format_percentage(value: f32) -> String {
     format!("{:.1}%", value)
 }
+
+/// Formatiert eine Laufzeit in Sekunden, z.B. "3 T 4 Std 12 Min"
+pub fn format_uptime(seconds: u64) -> String {
+    let days = seconds / 86_400;
+    let hours = seconds % 86_400 / 3_600;
+    let minutes = seconds % 3_600 / 60;
+    if days > 0 {
+        format!("{} T {} Std {} Min", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{} Std {} Min", hours, minutes)
+    } else {
+        format!("{} Min", minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_and_uptime() {
+        assert_eq!(rate(3000, Duration::from_millis(1500)), 2000);
+        assert_eq!(rate(3000, Duration::ZERO), 0);
+
+        assert_eq!(format_uptime(59), "0 Min");
+        assert_eq!(format_uptime(2 * 3600 + 5 * 60), "2 Std 5 Min");
+        assert_eq!(format_uptime(3 * 86_400 + 4 * 3600 + 12 * 60), "3 T 4 Std 12 Min");
+    }
+
+    #[test]
+    fn test_process_filter_and_sort() {
+        let process = |pid: u32, name: &str, cpu_usage: f32, disk_written_per_sec: u64| ProcessInfo {
+            name: name.to_string(),
+            pid,
+            cpu_usage,
+            disk_written_per_sec,
+            command: format!("/usr/bin/{} --flag", name),
+            user: Some(String::from("anna")),
+            ..Default::default()
+        };
+        let mut processes = vec![process(30, "cargo", 5.0, 10), process(7, "Firefox", 40.0, 0), process(12, "bash", 0.5, 300)];
+
+        sort_processes(&mut processes, ProcessSort::Cpu);
+        assert_eq!(processes.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![7, 30, 12]);
+        sort_processes(&mut processes, ProcessSort::Name);
+        assert_eq!(processes.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![12, 30, 7]);
+        sort_processes(&mut processes, ProcessSort::DiskWrite);
+        assert_eq!(processes.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![12, 30, 7]);
+        sort_processes(&mut processes, ProcessSort::Pid);
+        assert_eq!(processes.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![7, 12, 30]);
+
+        assert!(processes[0].matches("fire"));
+        assert!(processes[0].matches(" 7 "));
+        assert!(processes[0].matches("ANNA"));
+        assert!(processes[2].matches("--flag"));
+        assert!(!processes[0].matches("70"));
+        assert!(processes[1].matches(""));
+    }
+
+    #[test]
+    fn test_primary_disk() {
+        let disk = |mount_point: &str, total: u64| DiskInfo {
+            name: String::new(),
+            mount_point: PathBuf::from(mount_point),
+            file_system: String::from("ext4"),
+            total,
+            available: 0,
+            removable: false,
+        };
+        let disks = vec![disk("/", 50), disk("/home", 500), disk("/mnt/daten", 2000)];
+
+        assert_eq!(primary_disk(&disks, Some(Path::new("/home"))).unwrap().total, 500);
+        assert_eq!(primary_disk(&disks, Some(Path::new("/home/benutzer"))).unwrap().total, 500);
+        assert_eq!(primary_disk(&disks, Some(Path::new("/var"))).unwrap().total, 50);
+        assert_eq!(primary_disk(&disks[1..], Some(Path::new("/var"))).unwrap().total, 2000);
+        assert_eq!(primary_disk(&[], None), None);
+    }
+}
@@ -0,0 +1,349 @@
+//! Hintergrunddienst für geplante Bereinigungen aus `[[schedules]]` der Konfiguration
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cleaning::{
+    clean_targets_with_monitor, CancellationToken, CleaningMonitor, CleaningSummary, HistoryEntry, HistoryStore,
+    TargetRegistry,
+};
+use crate::config::{Config, MonitoringSettings, Schedule, Trigger};
+use crate::monitoring::system_info;
+
+/// Dateiname des Zustands im Datenverzeichnis der Anwendung
+pub const STATE_FILE: &str = "scheduler.json";
+
+/// Prüfintervall des Dienstes
+pub const DEFAULT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Mindestabstand zwischen zwei Läufen eines Speicherplatz-Auslösers, damit ein dauerhaft
+/// volles Laufwerk nicht zu einer Bereinigung pro Prüfintervall führt
+pub fn disk_trigger_cooldown() -> Duration {
+    Duration::hours(6)
+}
+
+/// Letzter Lauf je Zeitplan, überdauert Neustarts des Dienstes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchedulerState {
+    /// Zeitpunkt des letzten Laufs bzw. der ersten Einplanung, nach [`Schedule::key`]
+    last_runs: BTreeMap<String, DateTime<Utc>>,
+}
+
+impl SchedulerState {
+    /// Standardpfad im Datenverzeichnis der Anwendung
+    pub fn default_path() -> Option<PathBuf> {
+        crate::cleaning::targets::app_data_dir().map(|data| data.join(STATE_FILE))
+    }
+
+    /// Lädt den Zustand; ohne Datei ist er leer
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read(path) {
+            Ok(json) => serde_json::from_slice(&json).map_err(io::Error::other),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Speichert über eine temporäre Datei, damit die GUI nie einen halben Zustand liest
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, serde_json::to_vec_pretty(self).map_err(io::Error::other)?)?;
+        fs::rename(&temp_path, path)
+    }
+
+    pub fn last_run(&self, schedule: &Schedule) -> Option<DateTime<Utc>> {
+        self.last_runs.get(&schedule.key()).copied()
+    }
+
+    pub fn record(&mut self, schedule: &Schedule, at: DateTime<Utc>) {
+        self.last_runs.insert(schedule.key(), at);
+    }
+
+    /// Neue tägliche Zeitpläne ab jetzt einplanen, damit sie nicht sofort nachgeholt werden
+    fn initialize(&mut self, schedules: &[Schedule], now: DateTime<Utc>) -> bool {
+        let mut changed = false;
+        for schedule in schedules {
+            if matches!(schedule.trigger, Trigger::Daily(_)) && self.last_run(schedule).is_none() {
+                self.record(schedule, now);
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Nächster Termin eines Zeitplans
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NextRun {
+    /// Zu diesem Zeitpunkt (lokale Zeit)
+    At(DateTime<Local>),
+    /// Sobald der freie Speicher unter diese Prozentzahl fällt, frühestens zum angegebenen Zeitpunkt
+    WhenDiskBelow(f32, Option<DateTime<Local>>),
+}
+
+impl std::fmt::Display for NextRun {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            NextRun::At(time) => write!(f, "{}", time.format("%d.%m.%Y %H:%M")),
+            NextRun::WhenDiskBelow(percent, None) => write!(f, "sobald weniger als {}% frei sind", percent),
+            NextRun::WhenDiskBelow(percent, Some(earliest)) => {
+                write!(f, "sobald weniger als {}% frei sind, frühestens {}", percent, earliest.format("%d.%m.%Y %H:%M"))
+            }
+        }
+    }
+}
+
+/// Zeitplan mit letztem und nächstem Lauf, z.B. für die Anzeige in der GUI
+#[derive(Debug, Clone)]
+pub struct UpcomingRun {
+    pub schedule: Schedule,
+    pub last_run: Option<DateTime<Utc>>,
+    pub next: NextRun,
+}
+
+/// Alle Zeitpläne der Konfiguration mit ihrem nächsten Termin, früheste zuerst
+pub fn upcoming(config: &Config, state: &SchedulerState, now: DateTime<Local>) -> Vec<UpcomingRun> {
+    let mut runs: Vec<_> = config
+        .schedules()
+        .into_iter()
+        .map(|schedule| {
+            let last_run = state.last_run(&schedule);
+            let next = next_run(&schedule, last_run, now);
+            UpcomingRun { schedule, last_run, next }
+        })
+        .collect();
+    runs.sort_by_key(|run| match run.next {
+        NextRun::At(time) => (0, Some(time)),
+        NextRun::WhenDiskBelow(..) => (1, None),
+    });
+    runs
+}
+
+/// Nächster Termin ausgehend vom letzten Lauf
+pub fn next_run(schedule: &Schedule, last_run: Option<DateTime<Utc>>, now: DateTime<Local>) -> NextRun {
+    match schedule.trigger {
+        Trigger::Daily(time) => {
+            let today = local_at(now.date_naive(), time);
+            if last_run.is_none_or(|last| last < today) && now >= today {
+                // Verpasster Termin von heute wird sofort nachgeholt
+                NextRun::At(now)
+            } else if now < today {
+                NextRun::At(today)
+            } else {
+                NextRun::At(local_at(now.date_naive().succ_opt().unwrap_or(now.date_naive()), time))
+            }
+        }
+        Trigger::FreeDiskBelow(percent) => {
+            let earliest = last_run
+                .map(|last| (last + disk_trigger_cooldown()).with_timezone(&Local))
+                .filter(|earliest| *earliest > now);
+            NextRun::WhenDiskBelow(percent, earliest)
+        }
+    }
+}
+
+/// Ist der Zeitplan jetzt fällig? `free_percent` ist der freie Speicher des Hauptlaufwerks
+pub fn is_due(schedule: &Schedule, last_run: Option<DateTime<Utc>>, now: DateTime<Local>, free_percent: Option<f32>) -> bool {
+    match next_run(schedule, last_run, now) {
+        NextRun::At(time) => time <= now,
+        NextRun::WhenDiskBelow(percent, earliest) => {
+            earliest.is_none() && free_percent.is_some_and(|free| free < percent)
+        }
+    }
+}
+
+/// Freier Speicher des Hauptlaufwerks in Prozent, nach derselben Auswahl wie die Systemüberwachung
+pub fn free_disk_percent(settings: &MonitoringSettings) -> Option<f32> {
+    let disks = system_info::disks().ok()?;
+    let disk = system_info::primary_disk(&disks, settings.primary_disk.as_deref())?;
+    if disk.total == 0 {
+        return None;
+    }
+    Some(disk.available as f32 / disk.total as f32 * 100.0)
+}
+
+/// Führt ein Profil so aus, wie es die GUI tun würde
+pub fn run_profile(config: &Config, profile_name: &str, cancel: CancellationToken) -> Result<CleaningSummary, String> {
+    let profile = config
+        .profile(profile_name)
+        .ok_or_else(|| format!("Unbekanntes Profil: {}", profile_name))?;
+    let registry = TargetRegistry::with_builtin_targets();
+    let monitor = CleaningMonitor::new(cancel);
+    Ok(clean_targets_with_monitor(&profile.select(&registry), Some(profile.options()), &monitor))
+}
+
+/// Hintergrunddienst: prüft regelmäßig alle Zeitpläne und führt fällige Profile aus
+pub struct Daemon {
+    config: Config,
+    state: SchedulerState,
+    state_path: PathBuf,
+    history: HistoryStore,
+}
+
+impl Daemon {
+    /// Dienst mit Zustand und Verlauf an den Standardorten
+    pub fn new(config: Config) -> io::Result<Self> {
+        let state_path = SchedulerState::default_path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Kein Datenverzeichnis ermittelbar"))?;
+        Self::with_paths(config, state_path, HistoryStore::open_default()?)
+    }
+
+    pub fn with_paths(config: Config, state_path: PathBuf, history: HistoryStore) -> io::Result<Self> {
+        let mut state = SchedulerState::load(&state_path)?;
+        if state.initialize(&config.schedules(), Utc::now()) {
+            state.save(&state_path)?;
+        }
+        Ok(Self { config, state, state_path, history })
+    }
+
+    pub fn upcoming(&self, now: DateTime<Local>) -> Vec<UpcomingRun> {
+        upcoming(&self.config, &self.state, now)
+    }
+
+    /// Führt alle jetzt fälligen Zeitpläne aus und hält sie in Verlauf und Zustand fest
+    pub fn tick(&mut self, now: DateTime<Local>, free_percent: Option<f32>, cancel: &CancellationToken) -> Vec<(Schedule, Result<CleaningSummary, String>)> {
+        let mut results = Vec::new();
+
+        for schedule in self.config.schedules() {
+            if cancel.is_cancelled() {
+                break;
+            }
+            if !is_due(&schedule, self.state.last_run(&schedule), now, free_percent) {
+                continue;
+            }
+
+            let result = run_profile(&self.config, &schedule.profile, cancel.clone());
+            let finished = Utc::now();
+            if let Ok(summary) = &result {
+                let dry_run = self.config.profile(&schedule.profile).and_then(|p| p.dry_run).unwrap_or(false);
+                if !dry_run {
+                    let entry = HistoryEntry::from_summary(summary, Some(&schedule.profile), finished);
+                    if let Err(e) = self.history.append(&entry) {
+                        eprintln!("Verlauf konnte nicht gespeichert werden: {}", e);
+                    }
+                }
+            }
+            self.state.record(&schedule, finished);
+            if let Err(e) = self.state.save(&self.state_path) {
+                eprintln!("Zustand des Dienstes konnte nicht gespeichert werden: {}", e);
+            }
+            results.push((schedule, result));
+        }
+
+        results
+    }
+
+    /// Läuft, bis `stop` ausgelöst wird; `on_run` erhält jedes Ergebnis
+    pub fn run(
+        &mut self,
+        interval: std::time::Duration,
+        stop: &CancellationToken,
+        mut on_run: impl FnMut(&Schedule, &Result<CleaningSummary, String>)
+    ) {
+        while !stop.is_cancelled() {
+            let needs_disk = self.config.schedules().iter().any(|s| matches!(s.trigger, Trigger::FreeDiskBelow(_)));
+            let free_percent = if needs_disk { free_disk_percent(self.config.monitoring()) } else { None };
+
+            for (schedule, result) in self.tick(Local::now(), free_percent, stop) {
+                on_run(&schedule, &result);
+            }
+
+            // In kurzen Schritten schlafen, damit ein Stopp schnell greift
+            let mut slept = std::time::Duration::ZERO;
+            while slept < interval && !stop.is_cancelled() {
+                let step = std::time::Duration::from_millis(500).min(interval - slept);
+                thread::sleep(step);
+                slept += step;
+            }
+        }
+    }
+}
+
+/// Lokaler Zeitpunkt; fällt er in eine Zeitumstellungslücke, wird die erste gültige Zeit danach genommen
+fn local_at(date: NaiveDate, time: NaiveTime) -> DateTime<Local> {
+    let naive = date.and_time(time);
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| Local.from_local_datetime(&(naive + Duration::hours(1))).earliest())
+        .unwrap_or_else(|| Local.from_utc_datetime(&naive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Lokale Zeit im Juli 2025; dort stellt keine Zeitzone die Uhr um, und `local_at` panikt nie
+    fn local(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        let date = NaiveDate::from_ymd_opt(2025, 7, day).unwrap();
+        local_at(date, NaiveTime::from_hms_opt(hour, minute, 0).unwrap())
+    }
+
+    fn daily(hour: u32) -> Schedule {
+        Schedule {
+            profile: "dev-caches".to_string(),
+            trigger: Trigger::Daily(NaiveTime::from_hms_opt(hour, 0, 0).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_daily_schedule() {
+        let schedule = daily(2);
+        let yesterday = Some(local(9, 2, 1).with_timezone(&Utc));
+        let today = Some(local(10, 2, 1).with_timezone(&Utc));
+
+        assert_eq!(next_run(&schedule, yesterday, local(10, 1, 0)), NextRun::At(local(10, 2, 0)));
+        assert!(!is_due(&schedule, yesterday, local(10, 1, 59), None));
+        assert!(is_due(&schedule, yesterday, local(10, 2, 0), None));
+        // Verpasst (Rechner war aus): wird am selben Tag nachgeholt
+        assert!(is_due(&schedule, yesterday, local(10, 15, 0), None));
+        assert!(!is_due(&schedule, today, local(10, 15, 0), None));
+        assert_eq!(next_run(&schedule, today, local(10, 15, 0)), NextRun::At(local(11, 2, 0)));
+    }
+
+    #[test]
+    fn test_disk_schedule_with_cooldown() {
+        let schedule = Schedule { profile: "quick".to_string(), trigger: Trigger::FreeDiskBelow(10.0) };
+        let now = local(10, 12, 0);
+
+        assert!(is_due(&schedule, None, now, Some(9.5)));
+        assert!(!is_due(&schedule, None, now, Some(25.0)));
+        assert!(!is_due(&schedule, None, now, None));
+
+        let recently = Some(local(10, 9, 0).with_timezone(&Utc));
+        assert!(!is_due(&schedule, recently, now, Some(5.0)));
+        assert_eq!(next_run(&schedule, recently, now), NextRun::WhenDiskBelow(10.0, Some(local(10, 15, 0))));
+        assert!(is_due(&schedule, Some(local(10, 5, 0).with_timezone(&Utc)), now, Some(5.0)));
+    }
+
+    #[test]
+    fn test_new_daily_schedules_are_not_run_immediately() {
+        let temp_dir = TempDir::new().unwrap();
+        // Nur ein Ziel im temporären Verzeichnis, damit ein versehentlicher Lauf nichts anderes berührt
+        let config = Config::parse(
+            &format!(
+                "[profiles.test]\ntargets = [\"cargo-target-dirs\"]\nproject_roots = ['{}']\ndry_run = true\n\n[[schedules]]\nprofile = \"test\"\ndaily = \"00:00\"\n",
+                temp_dir.path().display()
+            ),
+            Path::new("config.toml"),
+        )
+        .unwrap();
+        let state_path = temp_dir.path().join(STATE_FILE);
+        let history = HistoryStore::new(temp_dir.path().join("history.jsonl"));
+
+        let mut daemon = Daemon::with_paths(config, state_path.clone(), history).unwrap();
+        assert!(SchedulerState::load(&state_path).unwrap().last_run(&daemon.config.schedules()[0]).is_some());
+        assert!(daemon.tick(Local::now(), None, &CancellationToken::new()).is_empty());
+        assert!(matches!(daemon.upcoming(Local::now())[0].next, NextRun::At(time) if time > Local::now()));
+    }
+}
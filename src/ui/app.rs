@@ -1,26 +1,151 @@
-use iced::widget::{button, column, container, horizontal_space, row, scrollable, text};
+use iced::widget::{button, column, container, pick_list, progress_bar, row, scrollable, text};
 use iced::{Application, Command, Element, Length, Theme, Subscription, time};
-use std::time::Duration;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::cleaning::{
+    format_bytes, report, CancellationToken, CleaningPhase, CleaningPlan, CleaningProgress, CleaningSummary, DiskUsage,
+    DuplicateReport, HistoryEntry,
+};
+use crate::config::{Config, Profile, FALLBACK_PROFILE};
+use crate::monitoring::system_info::{self, ProcessSort, SystemStatus};
+use crate::monitoring::ProcessAction;
+use crate::scheduler::{self, SchedulerState, UpcomingRun};
+use crate::ui::disk_usage::DiskAnalyzer;
+use crate::ui::duplicates::DuplicateFinder;
+use crate::ui::history::{self, HistoryRange};
+use crate::ui::monitor::{self, SystemMonitor};
+use crate::ui::preview::{NodeId, PreviewTree};
+use crate::ui::{tasks, widgets};
+
+/// Laufende Bereinigung
+struct CleaningRun {
+    id: u64,
+    cancel: CancellationToken,
+    progress: Option<CleaningProgress>,
+    /// Beim Start festgehaltenes Profil, damit ein Wechsel den Lauf nicht verändert
+    profile_name: String,
+    profile: Profile,
+    /// Einzelnes Verzeichnis statt der Ziele des Profils (aus der Speicherplatzanalyse)
+    adhoc: Option<PathBuf>,
+    /// Auszuführender Plan; `None`, solange noch gesucht wird
+    plan: Option<Arc<CleaningPlan>>,
+}
 
-use crate::cleaning::clean_temp_files;
-use crate::cleaning::CleaningSummary;
-use crate::monitoring::system_info::{self, SystemStatus};
-use crate::ui::widgets;
+/// Gefundene Dateien, die vor dem Löschen bestätigt werden müssen
+struct CleaningPreview {
+    plan: CleaningPlan,
+    tree: PreviewTree,
+    profile_name: String,
+    profile: Profile,
+    adhoc: Option<PathBuf>,
+}
 
 pub struct RustyCleanApp {
+    config: Config,
+    /// Fehler beim Laden der Konfiguration; dann gelten die eingebauten Profile
+    config_error: Option<String>,
+    selected_profile: String,
     cleaning_result: Option<Result<CleaningSummary, String>>,
-    is_cleaning: bool,
-    system_status: Option<SystemStatus>,
-    monitoring_active: bool,
+    /// Ergebnis des letzten Speicherns des Berichts
+    report_saved: Option<Result<Vec<PathBuf>, String>>,
+    cleaning_run: Option<CleaningRun>,
+    preview: Option<CleaningPreview>,
+    next_run_id: u64,
+    monitor: SystemMonitor,
+    monitoring_visible: bool,
+    duplicates_visible: bool,
+    duplicates: DuplicateFinder,
+    disk_usage_visible: bool,
+    disk_usage: DiskAnalyzer,
+    history_visible: bool,
+    history_range: HistoryRange,
+    history: Option<Result<Vec<HistoryEntry>, String>>,
+    /// Zeitpläne des Hintergrunddienstes mit ihrem nächsten Termin
+    upcoming_runs: Vec<UpcomingRun>,
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     CleanTempFiles,
+    ProfileSelected(String),
+    CleaningProgressed(CleaningProgress),
+    CancelCleaning,
+    ScanCompleted(Result<CleaningPlan, String>),
+    PreviewExpandToggled(NodeId),
+    PreviewSelectionChanged(NodeId, bool),
+    ConfirmCleaning,
+    DiscardPreview,
     CleaningCompleted(Result<CleaningSummary, String>),
+    SaveReport,
+    ReportSaved(Result<Vec<PathBuf>, String>),
+    ToggleDuplicates,
+    DuplicateRootsChanged(String),
+    DuplicatePreferredChanged(String),
+    FindDuplicates,
+    CancelDuplicateSearch,
+    DuplicatesFound(Result<DuplicateReport, String>),
+    CleanDuplicates,
+    DuplicatesCleaned(Result<CleaningSummary, String>),
+    ToggleDiskUsage,
+    UsageRootChanged(String),
+    AnalyzeUsage,
+    CancelUsageAnalysis,
+    UsageAnalyzed(Result<Arc<DiskUsage>, String>),
+    UsageDrillDown(PathBuf),
+    UsageNavigateUp,
+    CleanPath(PathBuf),
+    ToggleHistory,
+    HistoryRangeSelected(HistoryRange),
+    HistoryLoaded(Result<Vec<HistoryEntry>, String>),
+    RefreshSchedules,
+    SchedulesRefreshed(Vec<UpcomingRun>),
     ToggleMonitoring,
     UpdateSystemStatus,
     SystemStatusUpdated(SystemStatus),
+    ProcessSortChanged(ProcessSort),
+    ProcessFilterChanged(String),
+    ProcessRowsSelected(usize),
+    ProcessSelected(u32),
+    ProcessPriorityChosen(i32),
+    ProcessActionRequested(ProcessAction),
+    ConfirmProcessAction,
+    CancelProcessAction,
+    ProcessActionFinished(Result<String, String>),
+}
+
+/// Nächste Termine aus dem Zustand des Hintergrunddienstes
+async fn load_upcoming(config: Config) -> Vec<UpcomingRun> {
+    let state = SchedulerState::default_path()
+        .and_then(|path| SchedulerState::load(&path).ok())
+        .unwrap_or_default();
+    scheduler::upcoming(&config, &state, chrono::Local::now())
+}
+
+impl RustyCleanApp {
+    /// Startet einen Lauf; ohne Plan wird zuerst gesucht, mit Plan wird gelöscht
+    fn start_run(&mut self, profile_name: String, profile: Profile, adhoc: Option<PathBuf>, plan: Option<CleaningPlan>) {
+        self.next_run_id += 1;
+        // Die Arbeit selbst startet über die Subscription
+        self.cleaning_run = Some(CleaningRun {
+            id: self.next_run_id,
+            cancel: CancellationToken::new(),
+            progress: None,
+            profile_name,
+            profile,
+            adhoc,
+            plan: plan.map(Arc::new),
+        });
+    }
+
+    /// Lädt den Verlauf neu, sofern er angezeigt wird
+    fn reload_history(&self) -> Command<Message> {
+        if self.history_visible {
+            Command::perform(history::load(self.history_range), Message::HistoryLoaded)
+        } else {
+            Command::none()
+        }
+    }
 }
 
 impl Application for RustyCleanApp {
@@ -32,15 +157,38 @@ impl Application for RustyCleanApp {
     fn new(_flags: Self::Flags) -> (Self, Command<Message>) {
         // Initialisiere das System-Monitoring
         system_info::init_monitoring();
-        
+
+        let (config, config_error) = match Config::load() {
+            Ok(config) => (config, None),
+            Err(error) => (Config::builtin(), Some(error.to_string())),
+        };
+        let selected_profile = config.default_profile().unwrap_or(FALLBACK_PROFILE).to_string();
+        let refresh_schedules = Command::perform(load_upcoming(config.clone()), Message::SchedulesRefreshed);
+        system_info::set_primary_disk(config.monitoring().primary_disk.clone());
+        let monitor = SystemMonitor::new(config.monitoring());
+
         (
             Self {
+                config,
+                config_error,
+                selected_profile,
                 cleaning_result: None,
-                is_cleaning: false,
-                system_status: None,
-                monitoring_active: false,
+                report_saved: None,
+                cleaning_run: None,
+                preview: None,
+                next_run_id: 0,
+                monitor,
+                monitoring_visible: false,
+                duplicates_visible: false,
+                duplicates: DuplicateFinder::new(),
+                disk_usage_visible: false,
+                disk_usage: DiskAnalyzer::new(),
+                history_visible: false,
+                history_range: HistoryRange::default(),
+                history: None,
+                upcoming_runs: Vec::new(),
             },
-            Command::none(),
+            Command::batch([refresh_schedules, Command::perform(monitor::refresh(), Message::SystemStatusUpdated)]),
         )
     }
 
@@ -51,107 +199,403 @@ impl Application for RustyCleanApp {
     fn update(&mut self, message: Message) -> Command<Message> {
         match message {
             Message::CleanTempFiles => {
-                self.is_cleaning = true;
-                self.cleaning_result = None;
-
-                Command::perform(
-                    async { clean_temp_files() },
-                    Message::CleaningCompleted,
-                )
+                if self.cleaning_run.is_none() && self.preview.is_none() {
+                    let profile = self.config.profile(&self.selected_profile).cloned().unwrap_or_default();
+                    self.cleaning_result = None;
+                    self.report_saved = None;
+                    self.start_run(self.selected_profile.clone(), profile, None, None);
+                }
+                Command::none()
+            }
+            Message::CleanPath(path) => {
+                if self.cleaning_run.is_none() && self.preview.is_none() {
+                    let profile = self.config.profile(&self.selected_profile).cloned().unwrap_or_default();
+                    self.cleaning_result = None;
+                    self.report_saved = None;
+                    self.start_run(self.selected_profile.clone(), profile, Some(path), None);
+                }
+                Command::none()
+            }
+            Message::ProfileSelected(name) => {
+                self.selected_profile = name;
+                Command::none()
+            }
+            Message::CleaningProgressed(progress) => {
+                if let Some(run) = &mut self.cleaning_run {
+                    run.progress = Some(progress);
+                }
+                Command::none()
+            }
+            Message::CancelCleaning => {
+                if let Some(run) = &self.cleaning_run {
+                    run.cancel.cancel();
+                }
+                Command::none()
+            }
+            Message::ScanCompleted(result) => {
+                let Some(run) = self.cleaning_run.take() else {
+                    return Command::none();
+                };
+                match result {
+                    Ok(plan) if plan.cancelled => self.cleaning_result = Some(Ok(plan.to_cancelled_summary())),
+                    // Nichts zu bestätigen; der Lauf wird trotzdem abgeschlossen und im Verlauf vermerkt
+                    Ok(plan) if plan.is_empty() => self.start_run(run.profile_name, run.profile, run.adhoc, Some(plan)),
+                    Ok(plan) => {
+                        self.preview = Some(CleaningPreview {
+                            tree: PreviewTree::new(&plan),
+                            plan,
+                            profile_name: run.profile_name,
+                            profile: run.profile,
+                            adhoc: run.adhoc,
+                        });
+                    }
+                    Err(error) => self.cleaning_result = Some(Err(error)),
+                }
+                Command::none()
+            }
+            Message::PreviewExpandToggled(node) => {
+                if let Some(preview) = &mut self.preview {
+                    preview.tree.toggle_expanded(node);
+                }
+                Command::none()
+            }
+            Message::PreviewSelectionChanged(node, selected) => {
+                if let Some(preview) = &mut self.preview {
+                    preview.tree.set_selected(node, selected);
+                }
+                Command::none()
+            }
+            Message::ConfirmCleaning => {
+                if let Some(mut preview) = self.preview.take() {
+                    // Abgewählte Dateien verlassen den Plan, bevor er ausgeführt wird
+                    preview.tree.apply(&mut preview.plan);
+                    self.start_run(preview.profile_name, preview.profile, preview.adhoc, Some(preview.plan));
+                }
+                Command::none()
+            }
+            Message::DiscardPreview => {
+                self.preview = None;
+                Command::none()
             }
             Message::CleaningCompleted(result) => {
                 self.cleaning_result = Some(result);
-                self.is_cleaning = false;
+                self.cleaning_run = None;
+                // Der Lauf steht jetzt im Verlauf
+                self.reload_history()
+            }
+            Message::SaveReport => {
+                let Some(Ok(summary)) = &self.cleaning_result else {
+                    return Command::none();
+                };
+                let summary = summary.clone();
+                Command::perform(
+                    async move {
+                        let dir = report::default_report_dir()
+                            .ok_or_else(|| String::from("Kein Berichtsverzeichnis ermittelbar"))?;
+                        report::save_report(&summary, &dir, &report::report_name()).map_err(|e| e.to_string())
+                    },
+                    Message::ReportSaved,
+                )
+            }
+            Message::ReportSaved(result) => {
+                self.report_saved = Some(result);
                 Command::none()
             }
-            Message::ToggleMonitoring => {
-                self.monitoring_active = !self.monitoring_active;
-                
-                if self.monitoring_active {
-                    // Sofort die Systemdaten laden, wenn aktiviert
-                    Command::perform(
-                        async { 
-                            match system_info::get_system_status() {
-                                Ok(status) => status,
-                                Err(_) => SystemStatus {
-                                    cpu_usage: 0.0,
-                                    memory_used: 0,
-                                    memory_total: 0,
-                                    disk_used: 0,
-                                    disk_total: 0,
-                                    top_processes: Vec::new(),
-                                }
-                            }
-                        },
-                        Message::SystemStatusUpdated,
-                    )
-                } else {
-                    self.system_status = None;
-                    Command::none()
+            Message::ToggleDuplicates => {
+                self.duplicates_visible = !self.duplicates_visible;
+                Command::none()
+            }
+            Message::DuplicateRootsChanged(roots) => {
+                self.duplicates.roots = roots;
+                Command::none()
+            }
+            Message::DuplicatePreferredChanged(preferred) => {
+                self.duplicates.preferred = preferred;
+                Command::none()
+            }
+            Message::FindDuplicates => {
+                if self.duplicates.is_busy() {
+                    return Command::none();
                 }
+                Command::perform(self.duplicates.search(), Message::DuplicatesFound)
             }
-            Message::UpdateSystemStatus => {
-                if self.monitoring_active {
-                    Command::perform(
-                        async { 
-                            match system_info::get_system_status() {
-                                Ok(status) => status,
-                                Err(_) => SystemStatus {
-                                    cpu_usage: 0.0,
-                                    memory_used: 0,
-                                    memory_total: 0,
-                                    disk_used: 0,
-                                    disk_total: 0,
-                                    top_processes: Vec::new(),
-                                }
-                            }
-                        },
-                        Message::SystemStatusUpdated,
-                    )
-                } else {
-                    Command::none()
+            Message::CancelDuplicateSearch => {
+                self.duplicates.cancel_search();
+                Command::none()
+            }
+            Message::DuplicatesFound(report) => {
+                self.duplicates.search_finished(report);
+                Command::none()
+            }
+            Message::CleanDuplicates => match self.duplicates.clean() {
+                Some(cleaning) => Command::perform(cleaning, Message::DuplicatesCleaned),
+                None => Command::none(),
+            },
+            Message::DuplicatesCleaned(result) => {
+                self.duplicates.clean_finished(result);
+                // Der Lauf steht jetzt im Verlauf
+                self.reload_history()
+            }
+            Message::ToggleDiskUsage => {
+                self.disk_usage_visible = !self.disk_usage_visible;
+                Command::none()
+            }
+            Message::UsageRootChanged(root) => {
+                self.disk_usage.root = root;
+                Command::none()
+            }
+            Message::AnalyzeUsage => {
+                if self.disk_usage.is_busy() {
+                    return Command::none();
+                }
+                Command::perform(self.disk_usage.analyze(), Message::UsageAnalyzed)
+            }
+            Message::CancelUsageAnalysis => {
+                self.disk_usage.cancel();
+                Command::none()
+            }
+            Message::UsageAnalyzed(usage) => {
+                self.disk_usage.analyzed(usage);
+                Command::none()
+            }
+            Message::UsageDrillDown(path) => {
+                self.disk_usage.open(path);
+                Command::none()
+            }
+            Message::UsageNavigateUp => {
+                self.disk_usage.up();
+                Command::none()
+            }
+            Message::ToggleHistory => {
+                self.history_visible = !self.history_visible;
+                if !self.history_visible {
+                    self.history = None;
                 }
+                self.reload_history()
             }
+            Message::HistoryRangeSelected(range) => {
+                self.history_range = range;
+                self.reload_history()
+            }
+            Message::HistoryLoaded(result) => {
+                self.history = Some(result);
+                Command::none()
+            }
+            Message::RefreshSchedules => Command::perform(load_upcoming(self.config.clone()), Message::SchedulesRefreshed),
+            Message::SchedulesRefreshed(runs) => {
+                self.upcoming_runs = runs;
+                Command::none()
+            }
+            Message::ToggleMonitoring => {
+                self.monitoring_visible = !self.monitoring_visible;
+                Command::none()
+            }
+            Message::UpdateSystemStatus => Command::perform(monitor::refresh(), Message::SystemStatusUpdated),
             Message::SystemStatusUpdated(status) => {
-                self.system_status = Some(status);
+                self.monitor.update(status);
+                Command::none()
+            }
+            Message::ProcessSortChanged(sort) => {
+                self.monitor.set_sort(sort);
+                Command::none()
+            }
+            Message::ProcessFilterChanged(filter) => {
+                self.monitor.set_filter(filter);
+                Command::none()
+            }
+            Message::ProcessRowsSelected(rows) => {
+                self.monitor.set_rows(rows);
+                Command::none()
+            }
+            Message::ProcessSelected(pid) => {
+                self.monitor.select(pid);
+                Command::none()
+            }
+            Message::ProcessPriorityChosen(priority) => {
+                self.monitor.set_priority(priority);
+                Command::none()
+            }
+            Message::ProcessActionRequested(action) => {
+                self.monitor.request(action);
+                Command::none()
+            }
+            Message::ConfirmProcessAction => match self.monitor.confirm() {
+                Some(work) => Command::perform(work, Message::ProcessActionFinished),
+                None => Command::none(),
+            },
+            Message::CancelProcessAction => {
+                self.monitor.cancel_action();
                 Command::none()
             }
+            Message::ProcessActionFinished(result) => {
+                self.monitor.action_finished(result);
+                // Liste sofort aktualisieren, damit ein beendeter Prozess verschwindet
+                Command::perform(monitor::refresh(), Message::SystemStatusUpdated)
+            }
         }
     }
 
     fn subscription(&self) -> Subscription<Message> {
-        if self.monitoring_active {
-            // Aktualisiere die Systemdaten alle 2 Sekunden
-            time::every(Duration::from_secs(2)).map(|_| Message::UpdateSystemStatus)
-        } else {
+        // Immer messen, auch wenn die Überwachung ausgeblendet ist
+        let monitoring = time::every(self.config.monitoring().interval()).map(|_| Message::UpdateSystemStatus);
+
+        let cleaning = match &self.cleaning_run {
+            Some(CleaningRun { id, cancel, profile, adhoc, plan: None, .. }) => {
+                tasks::scan_temp_files(*id, cancel.clone(), profile.clone(), adhoc.clone())
+            }
+            Some(CleaningRun { id, cancel, profile_name, profile, adhoc, plan: Some(plan), .. }) => {
+                let profile_name = adhoc.is_none().then(|| profile_name.clone());
+                tasks::clean_temp_files(*id, cancel.clone(), profile_name, profile.clone(), plan.clone())
+            }
+            None => Subscription::none(),
+        };
+
+        // Der Dienst läuft in einem eigenen Prozess; seinen Zustand regelmäßig neu einlesen
+        let schedules = if self.upcoming_runs.is_empty() {
             Subscription::none()
-        }
+        } else {
+            time::every(scheduler::DEFAULT_INTERVAL).map(|_| Message::RefreshSchedules)
+        };
+
+        Subscription::batch([monitoring, cleaning, schedules])
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let header = widgets::header("ZentifyCleaner", "Windows Optimierungstool");
 
         // Haupt-Bereich mit Kacheln für verschiedene Funktionen
         let mut features = column![].spacing(20).width(Length::Fill);
 
         // Kachel für temporäre Dateien
+        let is_cleaning = self.cleaning_run.is_some();
         let temp_files_card = widgets::feature_card(
             "Temporäre Dateien",
             "Entfernt temporäre Dateien aus Windows- und Benutzerverzeichnissen",
-            if self.is_cleaning { "Bereinigung läuft..." } else { "Jetzt bereinigen" },
+            if is_cleaning {
+                "Bereinigung läuft..."
+            } else if self.preview.is_some() {
+                "Vorschau prüfen"
+            } else {
+                "Jetzt bereinigen"
+            },
             Message::CleanTempFiles,
-            self.is_cleaning,
+            is_cleaning || self.preview.is_some(),
         );
 
         features = features.push(temp_files_card);
 
+        // Profilauswahl
+        let profiles: Vec<String> = self.config.profile_names().map(String::from).collect();
+        let description = self
+            .config
+            .profile(&self.selected_profile)
+            .and_then(|p| p.description.clone())
+            .unwrap_or_default();
+        let mut profile_content = column![row![
+            text("Profil:").size(16),
+            pick_list(profiles, Some(self.selected_profile.clone()), Message::ProfileSelected),
+            text(description).size(14),
+        ]
+        .spacing(10)
+        .align_items(iced::Alignment::Center)]
+        .spacing(10);
+        for run in &self.upcoming_runs {
+            profile_content = profile_content.push(
+                text(format!("Geplant: {} ({}), nächster Lauf: {}", run.schedule.profile, run.schedule.trigger, run.next)).size(14),
+            );
+        }
+        if let Some(error) = &self.config_error {
+            profile_content = profile_content
+                .push(text(format!("Konfiguration fehlerhaft, eingebaute Profile aktiv: {}", error)).size(14));
+        }
+        features = features.push(container(profile_content).width(Length::Fill).padding(10));
+
+        // Vorschau der gefundenen Dateien vor dem Löschen
+        if let Some(preview) = &self.preview {
+            let (selected, selected_size) = preview.tree.selection();
+            let dry_run = preview.profile.options().dry_run;
+            let confirm = button(text(if dry_run { "Probelauf starten" } else { "Ausgewählte bereinigen" }))
+                .padding(10)
+                .style(iced::theme::Button::Destructive)
+                .on_press_maybe((selected > 0).then_some(Message::ConfirmCleaning));
+            let discard = button(text("Verwerfen")).padding(10).style(iced::theme::Button::Secondary).on_press(Message::DiscardPreview);
+
+            let preview_content = column![
+                text(format!(
+                    "Vorschau: {} von {} Dateien ausgewählt ({} von {})",
+                    selected,
+                    preview.plan.file_count(),
+                    format_bytes(selected_size),
+                    format_bytes(preview.plan.total_size())
+                ))
+                .size(18),
+                text("Abgewählte Dateien, Verzeichnisse und Orte werden nicht angefasst.").size(14),
+                preview.tree.view(),
+                row![confirm, discard].spacing(10),
+            ]
+            .spacing(10);
+
+            features = features.push(
+                container(preview_content)
+                    .style(iced::theme::Container::Box)
+                    .width(Length::Fill)
+                    .padding(20),
+            );
+        }
+
+        // Fortschritt der laufenden Bereinigung
+        if let Some(run) = &self.cleaning_run {
+            let cancelling = run.cancel.is_cancelled();
+            let mut progress_content = column![].spacing(10);
+
+            match &run.progress {
+                Some(progress) => {
+                    let phase = match progress.phase {
+                        CleaningPhase::Scanning => "Durchsuche",
+                        CleaningPhase::Deleting => "Lösche",
+                        CleaningPhase::Finished => "Abgeschlossen",
+                    };
+                    progress_content = progress_content
+                        .push(text(format!("{}: {}", phase, progress.location)).size(18))
+                        .push(progress_bar(0.0..=1.0, progress.fraction()))
+                        .push(text(progress.current_path.display().to_string()).size(12))
+                        .push(
+                            text(format!(
+                                "{} Dateien geprüft, {} von {} gelöscht ({})",
+                                progress.files_scanned,
+                                progress.files_deleted,
+                                progress.files_planned,
+                                format_bytes(progress.bytes_freed)
+                            ))
+                            .size(14),
+                        );
+                }
+                None => {
+                    progress_content = progress_content.push(text("Bereinigung wird gestartet...").size(18));
+                }
+            }
+
+            let cancel_button = button(text(if cancelling { "Wird abgebrochen..." } else { "Abbrechen" }))
+                .padding(10)
+                .style(iced::theme::Button::Destructive)
+                .on_press_maybe((!cancelling).then_some(Message::CancelCleaning));
+
+            features = features.push(
+                container(progress_content.push(cancel_button))
+                    .style(iced::theme::Container::Box)
+                    .width(Length::Fill)
+                    .padding(20),
+            );
+        }
+
         // Bereinigungsergebnisse anzeigen, wenn vorhanden
         if let Some(result) = &self.cleaning_result {
             let result_content = match result {
                 Ok(summary) => {
                     let mut content = column![
                         text(format!(
-                            "Bereinigt: {} Dateien ({})",
+                            "{}: {} Dateien ({})",
+                            if summary.cancelled { "Abgebrochen, bis dahin bereinigt" } else { "Bereinigt" },
                             summary.deleted_files,
                             summary.formatted_size()
                         ))
@@ -176,6 +620,19 @@ impl Application for RustyCleanApp {
                         content = content.push(errors_list);
                     }
 
+                    content = content.push(button(text("Bericht speichern")).padding(10).on_press(Message::SaveReport));
+                    match &self.report_saved {
+                        Some(Ok(paths)) => {
+                            for path in paths {
+                                content = content.push(text(format!("Gespeichert: {}", path.display())).size(14));
+                            }
+                        }
+                        Some(Err(error)) => {
+                            content = content.push(text(format!("Bericht konnte nicht gespeichert werden: {}", error)).size(14));
+                        }
+                        None => {}
+                    }
+
                     content
                 }
                 Err(error) => column![text(format!("Fehler: {}", error)).size(18)],
@@ -189,14 +646,73 @@ impl Application for RustyCleanApp {
             features = features.push(result_card);
         }
 
+        // Duplikatsuche
+        features = features.push(widgets::feature_card(
+            "Duplikate",
+            "Findet inhaltsgleiche Dateien, z.B. doppelte Downloads oder Build-Artefakte",
+            if self.duplicates_visible { "Duplikatsuche ausblenden" } else { "Duplikate finden" },
+            Message::ToggleDuplicates,
+            false,
+        ));
+
+        if self.duplicates_visible {
+            features = features.push(
+                container(self.duplicates.view())
+                    .style(iced::theme::Container::Box)
+                    .width(Length::Fill)
+                    .padding(20),
+            );
+        }
+
+        // Speicherplatzanalyse
+        features = features.push(widgets::feature_card(
+            "Speicherplatzanalyse",
+            "Zeigt, welche Ordner und Dateien den meisten Platz belegen",
+            if self.disk_usage_visible { "Analyse ausblenden" } else { "Speicherplatz analysieren" },
+            Message::ToggleDiskUsage,
+            false,
+        ));
+
+        if self.disk_usage_visible {
+            features = features.push(
+                container(self.disk_usage.view())
+                    .style(iced::theme::Container::Box)
+                    .width(Length::Fill)
+                    .padding(20),
+            );
+        }
+
+        // Verlauf der bisherigen Bereinigungen
+        features = features.push(widgets::feature_card(
+            "Verlauf",
+            "Freigegebener Speicher pro Woche und pro Ort",
+            if self.history_visible { "Verlauf ausblenden" } else { "Verlauf anzeigen" },
+            Message::ToggleHistory,
+            false,
+        ));
+
+        if self.history_visible {
+            let history_content: Element<'_, Message> = match &self.history {
+                Some(Ok(entries)) => history::view(entries, self.history_range),
+                Some(Err(error)) => text(format!("Verlauf konnte nicht geladen werden: {}", error)).size(16).into(),
+                None => text("Lade Verlauf...").size(16).into(),
+            };
+            features = features.push(
+                container(history_content)
+                    .style(iced::theme::Container::Box)
+                    .width(Length::Fill)
+                    .padding(20),
+            );
+        }
+
         // Systemüberwachung-Kachel
         let monitoring_card = widgets::feature_card(
             "Systemüberwachung",
             "Überwacht CPU, Speicher und Festplattennutzung in Echtzeit",
-            if self.monitoring_active {
-                "Überwachung stoppen"
+            if self.monitoring_visible {
+                "Überwachung ausblenden"
             } else {
-                "Überwachung starten"
+                "Überwachung anzeigen"
             },
             Message::ToggleMonitoring,
             false,
@@ -204,90 +720,9 @@ impl Application for RustyCleanApp {
 
         features = features.push(monitoring_card);
 
-        // Systemstatus anzeigen, wenn die Überwachung aktiv ist
-        if self.monitoring_active {
-            let status_content = if let Some(status) = &self.system_status {
-                // CPU-Auslastung
-                let cpu_usage = column![
-                    text("CPU-Auslastung").size(18),
-                    text(system_info::format_percentage(status.cpu_usage)).size(24)
-                ]
-                .spacing(5)
-                .padding(10)
-                .width(Length::Fill)
-                .align_items(iced::Alignment::Center);
-
-                // Speichernutzung
-                let mem_percentage = (status.memory_used as f64 / status.memory_total as f64 * 100.0) as f32;
-                let memory_usage = column![
-                    text("Speichernutzung").size(18),
-                    text(format!(
-                        "{} / {} ({})",
-                        system_info::format_bytes(status.memory_used),
-                        system_info::format_bytes(status.memory_total),
-                        system_info::format_percentage(mem_percentage)
-                    ))
-                    .size(16)
-                ]
-                .spacing(5)
-                .padding(10)
-                .width(Length::Fill)
-                .align_items(iced::Alignment::Center);
-
-                // Festplattennutzung
-                let disk_percentage = if status.disk_total > 0 {
-                    (status.disk_used as f64 / status.disk_total as f64 * 100.0) as f32
-                } else {
-                    0.0
-                };
-                let disk_usage = column![
-                    text("Festplattennutzung").size(18),
-                    text(format!(
-                        "{} / {} ({})",
-                        system_info::format_bytes(status.disk_used),
-                        system_info::format_bytes(status.disk_total),
-                        system_info::format_percentage(disk_percentage)
-                    ))
-                    .size(16)
-                ]
-                .spacing(5)
-                .padding(10)
-                .width(Length::Fill)
-                .align_items(iced::Alignment::Center);
-
-                // Hauptlayout der Systemstatistiken
-                let system_metrics = row![cpu_usage, memory_usage, disk_usage]
-                    .spacing(20)
-                    .padding(10)
-                    .width(Length::Fill);
-
-                // Top-Prozesse
-                let mut process_list = column![text("Top-Prozesse nach CPU-Nutzung:").size(18)]
-                    .spacing(10)
-                    .padding(10);
-
-                for proc in &status.top_processes {
-                    process_list = process_list.push(
-                        row![
-                            text(&proc.name).size(14).width(Length::FillPortion(6)),
-                            text(system_info::format_percentage(proc.cpu_usage))
-                                .size(14)
-                                .width(Length::FillPortion(2)),
-                            text(system_info::format_bytes(proc.memory_usage))
-                                .size(14)
-                                .width(Length::FillPortion(3)),
-                        ]
-                        .spacing(10)
-                        .padding(5)
-                    );
-                }
-
-                column![system_metrics, process_list]
-            } else {
-                column![text("Lade Systemdaten...").size(18)]
-                    .padding(20)
-                    .align_items(iced::Alignment::Center)
-            };
+        // Systemstatus anzeigen, wenn die Überwachung eingeblendet ist
+        if self.monitoring_visible {
+            let status_content = self.monitor.view();
 
             let status_card = container(status_content)
                 .style(iced::theme::Container::Box)
@@ -0,0 +1,148 @@
+use iced::mouse;
+use iced::widget::canvas::{self, Cache, Canvas, Geometry, Path, Stroke, Text};
+use iced::widget::{column, row, text};
+use iced::{Color, Element, Length, Point, Rectangle, Renderer, Size, Theme};
+
+use crate::monitoring::{format_percentage, Metric, SampleHistory};
+
+use super::app::Message;
+
+/// Höhe eines Diagramms
+const CHART_HEIGHT: f32 = 110.0;
+
+/// Platz links für die Beschriftung der Prozentachse
+const AXIS_WIDTH: f32 = 40.0;
+
+fn color(metric: Metric) -> Color {
+    match metric {
+        Metric::Cpu => Color::from_rgb(0.27, 0.51, 0.71),
+        Metric::Memory => Color::from_rgb(0.40, 0.65, 0.45),
+        Metric::Disk => Color::from_rgb(0.85, 0.55, 0.25),
+    }
+}
+
+/// Verlaufsdiagramme für CPU, Arbeitsspeicher und Festplatte
+#[derive(Default)]
+pub struct HistoryCharts {
+    caches: [Cache; 3],
+}
+
+impl HistoryCharts {
+    /// Nach jeder neuen Messung aufrufen, damit neu gezeichnet wird
+    pub fn clear(&self) {
+        for cache in &self.caches {
+            cache.clear();
+        }
+    }
+
+    pub fn view<'a>(&'a self, history: &'a SampleHistory) -> Element<'a, Message> {
+        let minutes = history.window().as_secs() / 60;
+        let mut content = column![text(format!("Verlauf der letzten {} Minuten", minutes)).size(18)].spacing(10);
+
+        for (metric, cache) in Metric::ALL.into_iter().zip(&self.caches) {
+            let stats = match history.stats(metric) {
+                Some(stats) => format!(
+                    "Min {}  ·  Ø {}  ·  Max {}",
+                    format_percentage(stats.min),
+                    format_percentage(stats.avg),
+                    format_percentage(stats.max)
+                ),
+                None => String::from("Noch keine Messwerte"),
+            };
+            content = content.push(
+                column![
+                    row![text(metric.label()).size(16).width(Length::Fill), text(stats).size(14)].spacing(10),
+                    Canvas::new(LineChart { history, metric, cache })
+                        .width(Length::Fill)
+                        .height(Length::Fixed(CHART_HEIGHT)),
+                ]
+                .spacing(4),
+            );
+        }
+        content.into()
+    }
+}
+
+/// Liniendiagramm einer Größe; die rechte Kante ist die neueste Messung
+struct LineChart<'a> {
+    history: &'a SampleHistory,
+    metric: Metric,
+    cache: &'a Cache,
+}
+
+impl canvas::Program<Message> for LineChart<'_> {
+    type State = ();
+
+    fn draw(&self, _state: &(), renderer: &Renderer, _theme: &Theme, bounds: Rectangle, _cursor: mouse::Cursor) -> Vec<Geometry> {
+        let chart = self.cache.draw(renderer, bounds.size(), |frame| {
+            let plot = Rectangle::new(Point::new(AXIS_WIDTH, 4.0), Size::new(frame.width() - AXIS_WIDTH - 4.0, frame.height() - 8.0));
+            frame.fill_rectangle(plot.position(), plot.size(), Color::from_rgb(0.97, 0.97, 0.97));
+
+            for percent in [0.0, 50.0, 100.0] {
+                let y = plot.y + plot.height * (1.0 - percent / 100.0);
+                frame.stroke(
+                    &Path::line(Point::new(plot.x, y), Point::new(plot.x + plot.width, y)),
+                    Stroke::default().with_color(Color::from_rgb(0.85, 0.85, 0.85)).with_width(1.0),
+                );
+                frame.fill_text(Text {
+                    content: format!("{:.0}%", percent),
+                    position: Point::new(2.0, (y - 7.0).clamp(0.0, frame.height() - 14.0)),
+                    color: Color::from_rgb(0.4, 0.4, 0.4),
+                    size: 12.0,
+                    ..Text::default()
+                });
+            }
+
+            let points = points(self.history, self.metric, plot);
+            if points.len() > 1 {
+                let line = Path::new(|builder| {
+                    builder.move_to(points[0]);
+                    for point in &points[1..] {
+                        builder.line_to(*point);
+                    }
+                });
+                frame.stroke(&line, Stroke::default().with_color(color(self.metric)).with_width(2.0));
+            }
+        });
+        vec![chart]
+    }
+}
+
+/// Position der Messungen im Diagrammbereich: Zeit auf der x-Achse, 0–100 % auf der y-Achse
+fn points(history: &SampleHistory, metric: Metric, plot: Rectangle) -> Vec<Point> {
+    let Some(newest) = history.latest().map(|sample| sample.time) else {
+        return Vec::new();
+    };
+    let window = history.window().as_secs_f32().max(1.0);
+    history
+        .iter()
+        .map(|sample| {
+            let age = newest.duration_since(sample.time).as_secs_f32();
+            let value = sample.value(metric).clamp(0.0, 100.0);
+            Point::new(
+                plot.x + plot.width * (1.0 - age / window),
+                plot.y + plot.height * (1.0 - value / 100.0),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitoring::Sample;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_points_span_window() {
+        let start = Instant::now();
+        let mut history = SampleHistory::new(Duration::from_secs(60));
+        for (second, cpu_usage) in [(0, 0.0), (30, 50.0), (60, 100.0)] {
+            history.push(Sample { time: start + Duration::from_secs(second), cpu_usage, memory_usage: 0.0, disk_usage: 0.0 });
+        }
+
+        let plot = Rectangle::new(Point::new(40.0, 0.0), Size::new(120.0, 100.0));
+        let points = points(&history, Metric::Cpu, plot);
+        assert_eq!(points, vec![Point::new(40.0, 100.0), Point::new(100.0, 50.0), Point::new(160.0, 0.0)]);
+    }
+}
@@ -0,0 +1,181 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use iced::widget::canvas::Cache;
+use iced::widget::{button, column, row, text, text_input};
+use iced::{Alignment, Element, Length};
+
+use crate::cleaning::disk_usage::analyze_with_monitor;
+use crate::cleaning::{format_bytes, targets, CancellationToken, CleaningMonitor, DiskUsage, UsageNode, UsageOptions};
+
+use super::app::Message;
+use super::tasks;
+use super::treemap::Treemap;
+
+/// Zustand der Speicherplatzanalyse in der Oberfläche
+#[derive(Default)]
+pub struct DiskAnalyzer {
+    /// Zu analysierendes Verzeichnis
+    pub root: String,
+    /// Laufende Analyse
+    analysis: Option<CancellationToken>,
+    usage: Option<Result<Arc<DiskUsage>, String>>,
+    /// Angezeigtes Verzeichnis innerhalb der Analyse
+    current: PathBuf,
+    treemap: Cache,
+}
+
+impl DiskAnalyzer {
+    /// Vorbelegt mit dem Home-Verzeichnis
+    pub fn new() -> Self {
+        Self {
+            root: targets::home_dir().map(|home| home.display().to_string()).unwrap_or_default(),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.analysis.is_some()
+    }
+
+    /// Startet die Analyse im Hintergrund
+    pub fn analyze(&mut self) -> impl std::future::Future<Output = Result<Arc<DiskUsage>, String>> {
+        let cancel = CancellationToken::new();
+        self.analysis = Some(cancel.clone());
+        self.usage = None;
+
+        let root = PathBuf::from(self.root.trim());
+        tasks::blocking(move || Arc::new(analyze_with_monitor(&root, &UsageOptions::default(), &CleaningMonitor::new(cancel))))
+    }
+
+    pub fn cancel(&self) {
+        if let Some(cancel) = &self.analysis {
+            cancel.cancel();
+        }
+    }
+
+    pub fn analyzed(&mut self, usage: Result<Arc<DiskUsage>, String>) {
+        self.analysis = None;
+        if let Ok(usage) = &usage {
+            self.current = usage.root.path.clone();
+        }
+        self.usage = Some(usage);
+        self.treemap.clear();
+    }
+
+    /// Wechselt in ein Verzeichnis der Analyse
+    pub fn open(&mut self, path: PathBuf) {
+        if let Some(Ok(usage)) = &self.usage {
+            if usage.root.find(&path).is_some() {
+                self.current = path;
+                self.treemap.clear();
+            }
+        }
+    }
+
+    /// Wechselt in das übergeordnete Verzeichnis, höchstens bis zur Wurzel der Analyse
+    pub fn up(&mut self) {
+        if let Some(Ok(usage)) = &self.usage {
+            if self.current != usage.root.path {
+                if let Some(parent) = self.current.parent() {
+                    self.current = parent.to_path_buf();
+                    self.treemap.clear();
+                }
+            }
+        }
+    }
+
+    fn current_node<'a>(&self, usage: &'a DiskUsage) -> &'a UsageNode {
+        usage.root.find(&self.current).unwrap_or(&usage.root)
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let analyze_button = if self.analysis.is_some() {
+            button(text("Abbrechen")).padding(10).style(iced::theme::Button::Destructive).on_press(Message::CancelUsageAnalysis)
+        } else {
+            button(text("Analysieren"))
+                .padding(10)
+                .on_press_maybe((!self.root.trim().is_empty()).then_some(Message::AnalyzeUsage))
+        };
+
+        let mut content = column![row![
+            text("Verzeichnis:").size(16),
+            text_input("z.B. /home/benutzer oder C:\\", &self.root).on_input(Message::UsageRootChanged),
+            analyze_button,
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center)]
+        .spacing(10);
+
+        match &self.usage {
+            None if self.analysis.is_some() => content = content.push(text("Analyse läuft...").size(16)),
+            None => {}
+            Some(Err(error)) => content = content.push(text(format!("Fehler: {}", error)).size(16)),
+            Some(Ok(usage)) => content = content.push(self.usage_view(usage)),
+        }
+
+        content.into()
+    }
+
+    fn usage_view<'a>(&'a self, usage: &'a DiskUsage) -> Element<'a, Message> {
+        let node = self.current_node(usage);
+        let mut summary = format!(
+            "{}: {} in {} Dateien",
+            node.path.display(),
+            format_bytes(node.size),
+            node.file_count
+        );
+        if usage.cancelled {
+            summary.push_str(" (abgebrochen, unvollständig)");
+        }
+
+        let mut content = column![
+            text(summary).size(18),
+            row![
+                button(text("Nach oben"))
+                    .padding(8)
+                    .style(iced::theme::Button::Secondary)
+                    .on_press_maybe((node.path != usage.root.path).then_some(Message::UsageNavigateUp)),
+                button(text("Als Bereinigungsziel übernehmen"))
+                    .padding(8)
+                    .style(iced::theme::Button::Destructive)
+                    .on_press(Message::CleanPath(node.path.clone())),
+            ]
+            .spacing(10),
+            Treemap::new(node, &self.treemap).view(360.0),
+        ]
+        .spacing(10);
+
+        let mut files = column![text(format!("Größte Dateien (Top {})", usage.top_n)).size(16)].spacing(4);
+        for file in &usage.largest_files {
+            files = files.push(size_row(&file.path, file.size, None));
+        }
+        let mut dirs = column![text("Ordner mit den meisten eigenen Dateien").size(16)].spacing(4);
+        for dir in usage.largest_dirs() {
+            dirs = dirs.push(size_row(&dir.path, dir.file_size, Some(Message::UsageDrillDown(dir.path.clone()))));
+        }
+        content = content.push(row![files.width(Length::FillPortion(1)), dirs.width(Length::FillPortion(1))].spacing(20));
+
+        if !usage.errors.is_empty() {
+            content = content.push(text(format!("{} Pfade konnten nicht gelesen werden", usage.errors.len())).size(14));
+        }
+        content.into()
+    }
+}
+
+fn size_row<'a>(path: &Path, size: u64, open: Option<Message>) -> Element<'a, Message> {
+    let label: Element<'a, Message> = match open {
+        Some(message) => button(text(path.display().to_string()).size(13))
+            .padding(0)
+            .style(iced::theme::Button::Text)
+            .on_press(message)
+            .into(),
+        None => text(path.display().to_string()).size(13).into(),
+    };
+    row![
+        text(format_bytes(size)).size(13).width(Length::Fixed(80.0)),
+        label,
+    ]
+    .spacing(10)
+    .into()
+}
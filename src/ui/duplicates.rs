@@ -0,0 +1,209 @@
+use std::path::PathBuf;
+
+use iced::widget::{button, column, row, text, text_input};
+use iced::{Alignment, Element, Length};
+
+use crate::cleaning::{
+    clean_duplicates, find_duplicates_with_monitor, format_bytes, targets, CancellationToken, CleaningMonitor, CleaningOptions,
+    CleaningSummary, DeletionMode, DuplicateOptions, DuplicateReport, HistoryEntry, HistoryStore, KeeperRule,
+};
+
+use super::app::Message;
+use super::tasks;
+
+/// Höchstzahl der angezeigten Gruppen
+const MAX_GROUPS_SHOWN: usize = 20;
+
+/// Zustand der Duplikatsuche in der Oberfläche
+#[derive(Debug, Default)]
+pub struct DuplicateFinder {
+    /// Zu durchsuchende Ordner, durch `;` getrennt
+    pub roots: String,
+    /// Ordner, dessen Kopien bevorzugt behalten werden (leer = neueste behalten)
+    pub preferred: String,
+    /// Laufende Suche
+    search: Option<CancellationToken>,
+    report: Option<Result<DuplicateReport, String>>,
+    cleaning: bool,
+    result: Option<Result<CleaningSummary, String>>,
+}
+
+impl DuplicateFinder {
+    /// Vorbelegt mit dem Download-Ordner des Benutzers
+    pub fn new() -> Self {
+        let roots = targets::home_dir()
+            .map(|home| home.join("Downloads"))
+            .filter(|downloads| downloads.is_dir())
+            .map(|downloads| downloads.display().to_string())
+            .unwrap_or_default();
+        Self { roots, ..Self::default() }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.search.is_some() || self.cleaning
+    }
+
+    /// Startet die Suche im Hintergrund
+    pub fn search(&mut self) -> impl std::future::Future<Output = Result<DuplicateReport, String>> {
+        let cancel = CancellationToken::new();
+        self.search = Some(cancel.clone());
+        self.report = None;
+        self.result = None;
+
+        let options = DuplicateOptions {
+            roots: split_paths(&self.roots),
+            keeper: match split_paths(&self.preferred) {
+                preferred if preferred.is_empty() => KeeperRule::Newest,
+                preferred => KeeperRule::PreferPaths(preferred),
+            },
+            ..Default::default()
+        };
+        tasks::blocking(move || find_duplicates_with_monitor(&options, &CleaningMonitor::new(cancel)))
+    }
+
+    pub fn cancel_search(&self) {
+        if let Some(cancel) = &self.search {
+            cancel.cancel();
+        }
+    }
+
+    pub fn search_finished(&mut self, report: Result<DuplicateReport, String>) {
+        self.search = None;
+        self.report = Some(report);
+    }
+
+    /// Verschiebt die überzähligen Kopien in die Quarantäne, damit sie wiederherstellbar bleiben
+    pub fn clean(&mut self) -> Option<impl std::future::Future<Output = Result<CleaningSummary, String>>> {
+        let Some(Ok(report)) = &self.report else {
+            return None;
+        };
+        let report = report.clone();
+        self.cleaning = true;
+
+        Some(tasks::blocking(move || {
+            let options = CleaningOptions {
+                deletion_mode: DeletionMode::Quarantine,
+                remove_empty_dirs: false,
+                ..Default::default()
+            };
+            let summary = clean_duplicates(&report, &options, &CleaningMonitor::default());
+            let entry = HistoryEntry::from_summary(&summary, None, chrono::Utc::now());
+            if let Err(e) = HistoryStore::open_default().and_then(|store| store.append(&entry)) {
+                eprintln!("Verlauf konnte nicht gespeichert werden: {}", e);
+            }
+            summary
+        }))
+    }
+
+    pub fn clean_finished(&mut self, result: Result<CleaningSummary, String>) {
+        self.cleaning = false;
+        self.report = None;
+        self.result = Some(result);
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let search_button = if self.search.is_some() {
+            button(text("Abbrechen")).padding(10).style(iced::theme::Button::Destructive).on_press(Message::CancelDuplicateSearch)
+        } else {
+            button(text("Duplikate suchen"))
+                .padding(10)
+                .on_press_maybe((!self.is_busy() && !self.roots.trim().is_empty()).then_some(Message::FindDuplicates))
+        };
+
+        let mut content = column![
+            row![
+                text("Ordner:").size(16).width(Length::Fixed(150.0)),
+                text_input("z.B. ~/Downloads; ~/Projekte (durch ; getrennt)", &self.roots).on_input(Message::DuplicateRootsChanged),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            row![
+                text("Bevorzugt behalten:").size(16).width(Length::Fixed(150.0)),
+                text_input("Ordner (leer = neueste Kopie behalten)", &self.preferred).on_input(Message::DuplicatePreferredChanged),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            search_button,
+        ]
+        .spacing(10);
+
+        if self.search.is_some() {
+            content = content.push(text("Suche läuft...").size(16));
+        }
+
+        match &self.report {
+            Some(Ok(report)) if report.cancelled => content = content.push(text("Suche abgebrochen").size(16)),
+            Some(Ok(report)) => content = content.push(report_view(report, self.cleaning)),
+            Some(Err(error)) => content = content.push(text(format!("Fehler: {}", error)).size(16)),
+            None => {}
+        }
+
+        match &self.result {
+            Some(Ok(summary)) => {
+                let mut line = format!("{} Kopien ({}) in die Quarantäne verschoben", summary.quarantined_files, summary.formatted_size());
+                if let Some(run_id) = &summary.quarantine_run_id {
+                    line.push_str(&format!(", Lauf {}", run_id));
+                }
+                content = content.push(text(line).size(16));
+                if !summary.errors.is_empty() {
+                    content = content.push(text(format!("{} Fehler", summary.errors.len())).size(14));
+                }
+            }
+            Some(Err(error)) => content = content.push(text(format!("Fehler: {}", error)).size(16)),
+            None => {}
+        }
+
+        content.into()
+    }
+}
+
+fn report_view(report: &DuplicateReport, cleaning: bool) -> Element<'_, Message> {
+    let mut content = column![text(format!(
+        "{} Dateien geprüft: {} Gruppen, {} überzählige Kopien ({})",
+        report.files_scanned,
+        report.groups.len(),
+        report.redundant_files(),
+        format_bytes(report.wasted_bytes())
+    ))
+    .size(18)]
+    .spacing(8);
+
+    for group in report.groups.iter().take(MAX_GROUPS_SHOWN) {
+        let mut files = column![text(format!("{} × {}", group.files.len(), format_bytes(group.size))).size(15)].spacing(2);
+        for (index, file) in group.files.iter().enumerate() {
+            let marker = if index == group.keeper { "behalten" } else { "entfernen" };
+            files = files.push(text(format!("    [{}] {}", marker, file.path.display())).size(13));
+        }
+        content = content.push(files);
+    }
+    if report.groups.len() > MAX_GROUPS_SHOWN {
+        content = content.push(text(format!("... und {} weitere Gruppen", report.groups.len() - MAX_GROUPS_SHOWN)).size(14));
+    }
+    if !report.errors.is_empty() {
+        content = content.push(text(format!("{} Pfade übersprungen (geschützt oder nicht lesbar)", report.errors.len())).size(14));
+    }
+
+    if !report.groups.is_empty() {
+        content = content.push(
+            button(text(if cleaning { "Wird verschoben..." } else { "Überzählige Kopien in Quarantäne verschieben" }))
+                .padding(10)
+                .style(iced::theme::Button::Destructive)
+                .on_press_maybe((!cleaning).then_some(Message::CleanDuplicates)),
+        );
+    }
+
+    content.into()
+}
+
+/// Durch `;` getrennte Pfade; `~` steht für das Home-Verzeichnis
+fn split_paths(input: &str) -> Vec<PathBuf> {
+    input
+        .split(';')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| match (part.strip_prefix('~'), targets::home_dir()) {
+            (Some(rest), Some(home)) => home.join(rest.trim_start_matches(['/', '\\'])),
+            _ => PathBuf::from(part),
+        })
+        .collect()
+}
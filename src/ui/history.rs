@@ -0,0 +1,93 @@
+use chrono::{Datelike, Duration, Utc};
+use iced::widget::{column, pick_list, progress_bar, row, text};
+use iced::{Alignment, Element, Length};
+
+use crate::cleaning::history::{self, HistoryEntry, HistoryStore};
+use crate::cleaning::format_bytes;
+
+use super::app::Message;
+
+/// Zeitraum der Verlaufsansicht
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HistoryRange {
+    FourWeeks,
+    #[default]
+    TwelveWeeks,
+    Year,
+}
+
+impl HistoryRange {
+    pub const ALL: [HistoryRange; 3] = [HistoryRange::FourWeeks, HistoryRange::TwelveWeeks, HistoryRange::Year];
+
+    fn weeks(self) -> i64 {
+        match self {
+            HistoryRange::FourWeeks => 4,
+            HistoryRange::TwelveWeeks => 12,
+            HistoryRange::Year => 52,
+        }
+    }
+}
+
+impl std::fmt::Display for HistoryRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HistoryRange::Year => f.write_str("Letztes Jahr"),
+            range => write!(f, "Letzte {} Wochen", range.weeks()),
+        }
+    }
+}
+
+/// Lädt die Läufe des Zeitraums aus dem Standardverlauf
+pub async fn load(range: HistoryRange) -> Result<Vec<HistoryEntry>, String> {
+    let now = Utc::now();
+    HistoryStore::open_default()
+        .and_then(|store| store.query(now - Duration::weeks(range.weeks()), now))
+        .map_err(|e| e.to_string())
+}
+
+/// Summen je Woche und je Ort als Balken
+pub fn view<'a>(entries: &[HistoryEntry], range: HistoryRange) -> Element<'a, Message> {
+    let total: u64 = entries.iter().map(|entry| entry.bytes_freed).sum();
+    let mut content = column![
+        row![
+            text("Zeitraum:").size(16),
+            pick_list(&HistoryRange::ALL[..], Some(range), Message::HistoryRangeSelected),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+        text(format!("{} Läufe, insgesamt {} freigegeben", entries.len(), format_bytes(total))).size(18),
+    ]
+    .spacing(10);
+
+    if entries.is_empty() {
+        return content.push(text("Noch keine Bereinigungen in diesem Zeitraum").size(14)).into();
+    }
+
+    let weeks = history::weekly_totals(entries);
+    let week_max = weeks.iter().map(|(_, bytes)| *bytes).max().unwrap_or(0);
+    content = content.push(text("Pro Woche").size(16));
+    for (monday, bytes) in weeks {
+        let label = format!("KW {:02} ({})", monday.iso_week().week(), monday.format("%d.%m.%Y"));
+        content = content.push(bar(label, bytes, week_max));
+    }
+
+    let locations = history::location_totals(entries);
+    let location_max = locations.first().map(|(_, bytes)| *bytes).unwrap_or(0);
+    content = content.push(text("Pro Ort").size(16));
+    for (name, bytes) in locations {
+        content = content.push(bar(name, bytes, location_max));
+    }
+
+    content.into()
+}
+
+fn bar<'a>(label: String, bytes: u64, max: u64) -> Element<'a, Message> {
+    row![
+        text(label).size(14).width(Length::FillPortion(3)),
+        progress_bar(0.0..=max.max(1) as f32, bytes as f32).height(14).width(Length::FillPortion(5)),
+        text(format_bytes(bytes)).size(14).width(Length::FillPortion(2)),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center)
+    .into()
+}
@@ -1,5 +1,15 @@
 mod app;
+mod charts;
+mod disk_usage;
+mod duplicates;
+mod history;
+mod monitor;
+mod preview;
+// Farbpalette der Oberfläche – noch nicht alle Farben sind in Verwendung
+#[allow(dead_code)]
 mod style;
+mod tasks;
+mod treemap;
 mod widgets;
 
 pub use app::RustyCleanApp;
@@ -0,0 +1,546 @@
+use chrono::TimeZone;
+use iced::widget::{button, column, container, pick_list, progress_bar, row, text, text_input, tooltip, Column};
+use iced::{Alignment, Element, Length};
+
+use crate::config::MonitoringSettings;
+use crate::monitoring::system_info::{self, CoreInfo, DiskInfo, NetworkInfo, ProcessInfo, ProcessSort, SystemStatus};
+use crate::monitoring::process_control::{self, ProcessAction};
+use crate::monitoring::DeviceIo;
+use crate::monitoring::history::percentage;
+use crate::monitoring::{Sample, SampleHistory};
+
+use super::app::Message;
+use super::charts::HistoryCharts;
+use super::tasks;
+
+/// Kerne je Zeile in der Kernansicht
+const CORES_PER_ROW: usize = 2;
+
+/// Auswahl für die Anzahl angezeigter Prozesse
+const ROW_CHOICES: [usize; 5] = [10, 25, 50, 100, 250];
+
+/// Auswählbare Nice-Stufen
+const PRIORITY_CHOICES: [i32; 8] = [-20, -10, -5, 0, 5, 10, 15, 19];
+
+/// Aktuelle Systemdaten; bei einem Fehler leere Werte
+pub async fn refresh() -> SystemStatus {
+    system_info::get_system_status().unwrap_or_default()
+}
+
+/// Zustand der Systemüberwachung in der Oberfläche
+pub struct SystemMonitor {
+    status: Option<SystemStatus>,
+    /// Messungen laufen auch bei ausgeblendeter Überwachung weiter, damit der Verlauf vollständig ist
+    samples: SampleHistory,
+    charts: HistoryCharts,
+    sort: ProcessSort,
+    /// Suchtext für die Prozessliste
+    filter: String,
+    /// Höchstzahl angezeigter Prozesse
+    rows: usize,
+    /// Angeklickter Prozess, auf den sich die Aktionen beziehen
+    selected: Option<u32>,
+    priority: i32,
+    /// Aktion, die noch bestätigt werden muss
+    pending: Option<PendingAction>,
+    action_running: bool,
+    action_result: Option<Result<String, String>>,
+}
+
+/// Zu bestätigende Prozessaktion; der Name wird festgehalten, falls der Prozess verschwindet
+#[derive(Debug, Clone)]
+struct PendingAction {
+    pid: u32,
+    name: String,
+    action: ProcessAction,
+}
+
+impl SystemMonitor {
+    pub fn new(settings: &MonitoringSettings) -> Self {
+        Self {
+            status: None,
+            samples: SampleHistory::new(settings.history_window()),
+            charts: HistoryCharts::default(),
+            sort: ProcessSort::default(),
+            filter: String::new(),
+            rows: settings.process_rows.max(1),
+            selected: None,
+            priority: 10,
+            pending: None,
+            action_running: false,
+            action_result: None,
+        }
+    }
+
+    pub fn select(&mut self, pid: u32) {
+        if self.selected != Some(pid) {
+            self.selected = Some(pid);
+            self.pending = None;
+        }
+    }
+
+    pub fn set_priority(&mut self, priority: i32) {
+        self.priority = priority;
+    }
+
+    /// Merkt eine Aktion für den ausgewählten Prozess vor; ausgeführt wird erst nach Bestätigung
+    pub fn request(&mut self, action: ProcessAction) {
+        let process = self.selected_process();
+        self.pending = process.map(|process| PendingAction { pid: process.pid, name: process.name.clone(), action });
+        self.action_result = None;
+    }
+
+    pub fn cancel_action(&mut self) {
+        self.pending = None;
+    }
+
+    /// Führt die vorgemerkte Aktion im Hintergrund aus
+    pub fn confirm(&mut self) -> Option<impl std::future::Future<Output = Result<String, String>>> {
+        let PendingAction { pid, name, action } = self.pending.take()?;
+        self.action_running = true;
+
+        let work = tasks::blocking(move || {
+            process_control::perform(pid, action)
+                .map(|()| format!("Prozess \"{}\" ({}): {}", name, pid, done_label(action)))
+                .map_err(|error| error.to_string())
+        });
+        Some(async move { work.await.and_then(|result| result) })
+    }
+
+    pub fn action_finished(&mut self, result: Result<String, String>) {
+        self.action_running = false;
+        self.action_result = Some(result);
+    }
+
+    fn selected_process(&self) -> Option<&ProcessInfo> {
+        let pid = self.selected?;
+        self.status.as_ref()?.processes.iter().find(|process| process.pid == pid)
+    }
+
+    pub fn set_sort(&mut self, sort: ProcessSort) {
+        self.sort = sort;
+        if let Some(status) = &mut self.status {
+            system_info::sort_processes(&mut status.processes, sort);
+        }
+    }
+
+    pub fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+    }
+
+    pub fn set_rows(&mut self, rows: usize) {
+        self.rows = rows;
+    }
+
+    /// Übernimmt eine neue Messung
+    pub fn update(&mut self, status: SystemStatus) {
+        self.samples.push(Sample::from_status(&status, std::time::Instant::now()));
+        self.charts.clear();
+        let mut status = status;
+        // Einmal je Messung sortieren statt bei jedem Zeichnen
+        system_info::sort_processes(&mut status.processes, self.sort);
+        self.status = Some(status);
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let Some(status) = &self.status else {
+            return column![text("Lade Systemdaten...").size(18)]
+                .padding(20)
+                .align_items(Alignment::Center)
+                .into();
+        };
+
+        // CPU-Auslastung
+        let cpu_usage = column![
+            text("CPU-Auslastung").size(18),
+            text(system_info::format_percentage(status.cpu_usage)).size(24)
+        ];
+
+        // Speichernutzung
+        let memory_usage = column![
+            text("Speichernutzung").size(18),
+            text(usage_label(status.memory_used, status.memory_total)).size(16),
+            progress_bar(0.0..=100.0, percentage(status.memory_used, status.memory_total)).height(10),
+        ];
+
+        // Auslagerungsspeicher
+        let swap_usage = column![
+            text("Auslagerungsspeicher").size(18),
+            text(usage_label(status.swap_used, status.swap_total)).size(16),
+            progress_bar(0.0..=100.0, percentage(status.swap_used, status.swap_total)).height(10),
+        ];
+
+        // Hauptlaufwerk
+        let disk_title = match &status.primary_disk {
+            Some(mount_point) => format!("Hauptlaufwerk ({})", mount_point.display()),
+            None => String::from("Hauptlaufwerk"),
+        };
+        let disk_usage = column![
+            text(disk_title).size(18),
+            text(usage_label(status.disk_used, status.disk_total)).size(16),
+            progress_bar(0.0..=100.0, percentage(status.disk_used, status.disk_total)).height(10),
+        ];
+
+        // Hauptlayout der Systemstatistiken
+        let system_metrics = row(
+            [cpu_usage, memory_usage, swap_usage, disk_usage]
+                .into_iter()
+                .map(|metric| metric.spacing(5).padding(10).width(Length::Fill).align_items(Alignment::Center).into())
+                .collect(),
+        )
+        .spacing(20)
+        .padding(10)
+        .width(Length::Fill);
+
+        let load = status.load_average;
+        let system_line = text(format!(
+            "Laufzeit: {} (gestartet {})  ·  Last (1/5/15 Min): {:.2} / {:.2} / {:.2}",
+            system_info::format_uptime(status.uptime),
+            format_timestamp(status.boot_time),
+            load.one,
+            load.five,
+            load.fifteen
+        ))
+        .size(14);
+
+        column![
+            system_metrics,
+            system_line,
+            disks_view(status),
+            disk_io_view(&status.disk_io),
+            network_view(status),
+            cores_view(&status.cores),
+            self.processes_view(status),
+            self.charts.view(&self.samples),
+        ]
+        .spacing(10)
+        .padding([0, 10])
+        .into()
+    }
+
+    /// Gefilterte und sortierte Prozesstabelle
+    fn processes_view<'a>(&'a self, status: &'a SystemStatus) -> Element<'a, Message> {
+        let matching: Vec<&ProcessInfo> = status.processes.iter().filter(|process| process.matches(&self.filter)).collect();
+
+        let mut content = column![
+            row![
+                text(format!("Prozesse ({} von {})", matching.len().min(self.rows), matching.len())).size(18).width(Length::Fill),
+                text_input("Filtern nach Name, Befehl, Benutzer oder PID", &self.filter)
+                    .on_input(Message::ProcessFilterChanged)
+                    .width(Length::Fixed(320.0)),
+                text("Zeilen:").size(14),
+                pick_list(&ROW_CHOICES[..], Some(self.rows), Message::ProcessRowsSelected),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+            row![
+                self.sort_header("PID", ProcessSort::Pid, 1),
+                self.sort_header("Name", ProcessSort::Name, 4),
+                text("Benutzer").size(14).width(Length::FillPortion(2)),
+                text("Zustand").size(14).width(Length::FillPortion(2)),
+                self.sort_header("CPU", ProcessSort::Cpu, 2),
+                self.sort_header("Speicher", ProcessSort::Memory, 2),
+                self.sort_header("Lesen", ProcessSort::DiskRead, 2),
+                self.sort_header("Schreiben", ProcessSort::DiskWrite, 2),
+            ]
+            .spacing(10)
+            .padding([0, 5]),
+        ]
+        .spacing(6)
+        .padding(10);
+
+        if let Some(actions) = self.actions_view() {
+            content = content.push(actions);
+        }
+
+        for process in matching.into_iter().take(self.rows) {
+            content = content.push(process_row(process, self.selected == Some(process.pid)));
+        }
+        content.into()
+    }
+
+    /// Aktionen für den ausgewählten Prozess, Bestätigung und Ergebnis der letzten Aktion
+    fn actions_view(&self) -> Option<Element<'_, Message>> {
+        let mut content = column![].spacing(8);
+
+        if let Some(pending) = &self.pending {
+            content = content.push(
+                row![
+                    text(format!("Prozess \"{}\" ({}) wirklich {}?", pending.name, pending.pid, pending.action)).size(16),
+                    button(text("Bestätigen"))
+                        .padding(8)
+                        .style(iced::theme::Button::Destructive)
+                        .on_press(Message::ConfirmProcessAction),
+                    button(text("Abbrechen"))
+                        .padding(8)
+                        .style(iced::theme::Button::Secondary)
+                        .on_press(Message::CancelProcessAction),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            );
+        } else if let Some(process) = self.selected_process() {
+            let protected = process_control::is_protected(process.pid);
+            let enabled = |action: ProcessAction| (!protected && !self.action_running).then_some(Message::ProcessActionRequested(action));
+            let mut line = row![
+                text(format!("Ausgewählt: {} ({})", process.name, process.pid)).size(16),
+                button(text("Beenden")).padding(8).on_press_maybe(enabled(ProcessAction::Terminate)),
+                button(text("Sofort beenden"))
+                    .padding(8)
+                    .style(iced::theme::Button::Destructive)
+                    .on_press_maybe(enabled(ProcessAction::Kill)),
+                pick_list(&PRIORITY_CHOICES[..], Some(self.priority), Message::ProcessPriorityChosen),
+                button(text("Priorität setzen"))
+                    .padding(8)
+                    .style(iced::theme::Button::Secondary)
+                    .on_press_maybe(enabled(ProcessAction::SetPriority(self.priority))),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center);
+            if protected {
+                line = line.push(text("(geschützt)").size(14));
+            }
+            content = content.push(line);
+        }
+
+        if self.action_running {
+            content = content.push(text("Aktion läuft...").size(14));
+        }
+        match &self.action_result {
+            Some(Ok(message)) => content = content.push(text(message).size(14)),
+            Some(Err(error)) => content = content.push(text(format!("Fehler: {}", error)).size(14)),
+            None => {}
+        }
+
+        let empty = self.pending.is_none() && self.selected_process().is_none() && !self.action_running && self.action_result.is_none();
+        (!empty).then(|| container(content).style(iced::theme::Container::Box).padding(10).width(Length::Fill).into())
+    }
+
+    /// Spaltenkopf, der die Prozessliste nach dieser Spalte sortiert
+    fn sort_header(&self, label: &str, sort: ProcessSort, portion: u16) -> Element<'_, Message> {
+        let arrow = match sort {
+            ProcessSort::Pid | ProcessSort::Name => "▲",
+            _ => "▼",
+        };
+        let label = if self.sort == sort { format!("{} {}", label, arrow) } else { label.to_string() };
+        button(text(label).size(14))
+            .padding(0)
+            .style(iced::theme::Button::Text)
+            .on_press(Message::ProcessSortChanged(sort))
+            .width(Length::FillPortion(portion))
+            .into()
+    }
+}
+
+/// Eine Zeile der Prozesstabelle; Befehlszeile, Elternprozess und Startzeit als Tooltip
+fn process_row(process: &ProcessInfo, selected: bool) -> Element<'_, Message> {
+    let mut details = Vec::new();
+    if !process.command.is_empty() {
+        details.push(process.command.clone());
+    }
+    if let Some(parent) = process.parent_pid {
+        details.push(format!("Elternprozess: {}", parent));
+    }
+    details.push(format!("Gestartet: {}", format_timestamp(process.start_time)));
+
+    let name = tooltip(
+        text(&process.name).size(14),
+        details.join("\n"),
+        tooltip::Position::Bottom,
+    )
+    .style(iced::theme::Container::Box);
+
+    let columns = row![
+        text(process.pid).size(14).width(Length::FillPortion(1)),
+        container(name).width(Length::FillPortion(4)),
+        text(process.user.as_deref().unwrap_or("-")).size(14).width(Length::FillPortion(2)),
+        text(&process.status).size(14).width(Length::FillPortion(2)),
+        text(system_info::format_percentage(process.cpu_usage)).size(14).width(Length::FillPortion(2)),
+        text(system_info::format_bytes(process.memory_usage)).size(14).width(Length::FillPortion(2)),
+        text(format_rate(process.disk_read_per_sec)).size(14).width(Length::FillPortion(2)),
+        text(format_rate(process.disk_written_per_sec)).size(14).width(Length::FillPortion(2)),
+    ]
+    .spacing(10);
+
+    // Ein Klick wählt den Prozess für die Aktionen aus
+    button(columns)
+        .padding([2, 5])
+        .width(Length::Fill)
+        .style(if selected { iced::theme::Button::Secondary } else { iced::theme::Button::Text })
+        .on_press(Message::ProcessSelected(process.pid))
+        .into()
+}
+
+/// Erfolgsmeldung einer Prozessaktion
+fn done_label(action: ProcessAction) -> String {
+    match action {
+        ProcessAction::Terminate => String::from("zum Beenden aufgefordert"),
+        ProcessAction::Kill => String::from("beendet"),
+        ProcessAction::SetPriority(nice) => format!("Priorität auf {} gesetzt", nice),
+    }
+}
+
+/// Balken je Kern mit Auslastung und Takt
+fn cores_view(cores: &[CoreInfo]) -> Element<'_, Message> {
+    let mut content = column![text(format!("Kerne ({})", cores.len())).size(18)].spacing(4).padding(10);
+    for chunk in cores.chunks(CORES_PER_ROW) {
+        let mut line = row![].spacing(20);
+        for core in chunk {
+            line = line.push(
+                row![
+                    text(&core.name).size(13).width(Length::Fixed(60.0)),
+                    progress_bar(0.0..=100.0, core.usage).height(12).width(Length::Fill),
+                    text(system_info::format_percentage(core.usage)).size(13).width(Length::Fixed(50.0)),
+                    text(format!("{} MHz", core.frequency)).size(13).width(Length::Fixed(75.0)),
+                ]
+                .spacing(8)
+                .align_items(Alignment::Center)
+                .width(Length::FillPortion(1)),
+            );
+        }
+        // Letzte Zeile auffüllen, damit die Balken gleich breit bleiben
+        for _ in chunk.len()..CORES_PER_ROW {
+            line = line.push(Column::new().width(Length::FillPortion(1)));
+        }
+        content = content.push(line);
+    }
+    content.into()
+}
+
+/// Tabelle aller Laufwerke; das Hauptlaufwerk ist markiert
+fn disks_view(status: &SystemStatus) -> Element<'_, Message> {
+    let mut content = column![text(format!("Laufwerke ({})", status.disks.len())).size(18)].spacing(4).padding(10);
+    content = content.push(disk_row(
+        ["Einhängepunkt", "Gerät", "Dateisystem", "Belegt", "Frei", ""].map(String::from),
+        None,
+        14,
+    ));
+    for disk in &status.disks {
+        let primary = status.primary_disk.as_ref() == Some(&disk.mount_point);
+        content = content.push(disk_row(disk_columns(disk, primary), Some(disk), 13));
+    }
+    content.into()
+}
+
+fn disk_columns(disk: &DiskInfo, primary: bool) -> [String; 6] {
+    let mut flags = Vec::new();
+    if primary {
+        flags.push("Hauptlaufwerk");
+    }
+    if disk.removable {
+        flags.push("Wechselmedium");
+    }
+    [
+        disk.mount_point.display().to_string(),
+        disk.name.clone(),
+        disk.file_system.clone(),
+        usage_label(disk.used(), disk.total),
+        system_info::format_bytes(disk.available),
+        flags.join(", "),
+    ]
+}
+
+fn disk_row<'a>(columns: [String; 6], disk: Option<&DiskInfo>, size: u16) -> Element<'a, Message> {
+    let [mount_point, name, file_system, used, available, flags] = columns;
+    let gauge: Element<'a, Message> = match disk {
+        Some(disk) => progress_bar(0.0..=100.0, percentage(disk.used(), disk.total)).height(10).into(),
+        None => text("").into(),
+    };
+    row![
+        text(mount_point).size(size).width(Length::FillPortion(3)),
+        text(name).size(size).width(Length::FillPortion(3)),
+        text(file_system).size(size).width(Length::FillPortion(2)),
+        text(used).size(size).width(Length::FillPortion(4)),
+        container(gauge).width(Length::FillPortion(2)),
+        text(available).size(size).width(Length::FillPortion(2)),
+        text(flags).size(size).width(Length::FillPortion(3)),
+    ]
+    .spacing(10)
+    .align_items(Alignment::Center)
+    .into()
+}
+
+/// Lese- und Schreibrate je Blockgerät; ohne Daten (außerhalb von Linux) leer
+fn disk_io_view(devices: &[DeviceIo]) -> Element<'_, Message> {
+    if devices.is_empty() {
+        return column![].into();
+    }
+    let mut content = column![text("Datenträger-Durchsatz").size(18)].spacing(4).padding(10);
+    let header = ["Gerät", "Lesen", "Schreiben", "Gelesen gesamt", "Geschrieben gesamt"];
+    content = content.push(row(header.into_iter().map(|label| text(label).size(14).width(Length::FillPortion(2)).into()).collect()).spacing(10));
+    for device in devices {
+        let columns = [
+            device.name.clone(),
+            format_rate(device.read_per_sec),
+            format_rate(device.written_per_sec),
+            system_info::format_bytes(device.total_read),
+            system_info::format_bytes(device.total_written),
+        ];
+        content = content.push(row(columns.into_iter().map(|column| text(column).size(13).width(Length::FillPortion(2)).into()).collect()).spacing(10));
+    }
+    content.into()
+}
+
+/// Schnittstellen mit Datenverkehr: aktuelle Raten, Summen und Fehler
+fn network_view(status: &SystemStatus) -> Element<'_, Message> {
+    let (received, transmitted) = status.network_rates();
+    let mut content = column![text(format!(
+        "Netzwerk  ·  ↓ {}  ↑ {}",
+        format_rate(received),
+        format_rate(transmitted)
+    ))
+    .size(18)]
+    .spacing(4)
+    .padding(10);
+
+    let header = ["Schnittstelle", "Empfang", "Senden", "Empfangen gesamt", "Gesendet gesamt", "Fehler"];
+    content = content.push(network_row(header.map(String::from), 14));
+
+    let active = status.networks.iter().filter(|network| network.total_received + network.total_transmitted > 0);
+    for network in active {
+        content = content.push(network_row(network_columns(network), 13));
+    }
+    content.into()
+}
+
+fn network_columns(network: &NetworkInfo) -> [String; 6] {
+    [
+        network.name.clone(),
+        format_rate(network.received_per_sec),
+        format_rate(network.transmitted_per_sec),
+        system_info::format_bytes(network.total_received),
+        system_info::format_bytes(network.total_transmitted),
+        format!("{} / {}", network.receive_errors, network.transmit_errors),
+    ]
+}
+
+fn network_row<'a>(columns: [String; 6], size: u16) -> Element<'a, Message> {
+    row(columns
+        .into_iter()
+        .map(|column| text(column).size(size).width(Length::FillPortion(2)).into())
+        .collect())
+    .spacing(10)
+    .into()
+}
+
+fn format_rate(bytes_per_sec: u64) -> String {
+    format!("{}/s", system_info::format_bytes(bytes_per_sec))
+}
+
+fn usage_label(used: u64, total: u64) -> String {
+    format!(
+        "{} / {} ({})",
+        system_info::format_bytes(used),
+        system_info::format_bytes(total),
+        system_info::format_percentage(percentage(used, total))
+    )
+}
+
+/// Zeitpunkt in Sekunden seit 1970 als lokales Datum mit Uhrzeit
+fn format_timestamp(seconds: u64) -> String {
+    chrono::Local
+        .timestamp_opt(seconds as i64, 0)
+        .single()
+        .map(|time| time.format("%d.%m.%Y %H:%M").to_string())
+        .unwrap_or_else(|| String::from("unbekannt"))
+}
@@ -0,0 +1,273 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use iced::widget::{button, checkbox, column, row, scrollable, text};
+use iced::{Alignment, Element, Length};
+
+use crate::cleaning::{format_bytes, CleaningPlan};
+
+use super::app::Message;
+
+/// Höchstzahl der Dateien, die unter einem aufgeklappten Verzeichnis einzeln angezeigt werden
+const MAX_FILES_SHOWN: usize = 200;
+
+/// Knoten im Vorschaubaum (Indizes in Orte, Verzeichnisse und Dateien)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeId {
+    Location(usize),
+    Directory(usize, usize),
+    File(usize, usize, usize),
+}
+
+#[derive(Debug)]
+struct FileNode {
+    path: PathBuf,
+    size: u64,
+    selected: bool,
+}
+
+#[derive(Debug)]
+struct DirectoryNode {
+    path: PathBuf,
+    expanded: bool,
+    files: Vec<FileNode>,
+}
+
+#[derive(Debug)]
+struct LocationNode {
+    name: String,
+    expanded: bool,
+    directories: Vec<DirectoryNode>,
+}
+
+/// Auswahlbaum Ort → Verzeichnis → Datei über einem Bereinigungsplan
+///
+/// Anfangs ist alles ausgewählt und nur die Orte sind sichtbar. Die Auswahl eines Orts oder
+/// Verzeichnisses setzt alle enthaltenen Dateien; angehakt ist er, wenn alle Dateien ausgewählt sind.
+#[derive(Debug, Default)]
+pub struct PreviewTree {
+    locations: Vec<LocationNode>,
+}
+
+impl PreviewTree {
+    pub fn new(plan: &CleaningPlan) -> Self {
+        let locations = plan
+            .locations
+            .iter()
+            .filter_map(|location| {
+                let mut directories: BTreeMap<&Path, Vec<FileNode>> = BTreeMap::new();
+                for entry in plan.entries_for(&location.name) {
+                    directories.entry(entry.path.parent().unwrap_or(Path::new(""))).or_default().push(FileNode {
+                        path: entry.path.clone(),
+                        size: entry.size,
+                        selected: true,
+                    });
+                }
+                if directories.is_empty() {
+                    return None;
+                }
+
+                Some(LocationNode {
+                    name: location.name.clone(),
+                    expanded: false,
+                    directories: directories
+                        .into_iter()
+                        .map(|(path, mut files)| {
+                            files.sort_by(|a, b| a.path.cmp(&b.path));
+                            DirectoryNode { path: path.to_path_buf(), expanded: false, files }
+                        })
+                        .collect(),
+                })
+            })
+            .collect();
+
+        Self { locations }
+    }
+
+    /// Klappt einen Ort oder ein Verzeichnis auf bzw. zu
+    pub fn toggle_expanded(&mut self, node: NodeId) {
+        match node {
+            NodeId::Location(l) => {
+                if let Some(location) = self.locations.get_mut(l) {
+                    location.expanded = !location.expanded;
+                }
+            }
+            NodeId::Directory(l, d) => {
+                if let Some(directory) = self.locations.get_mut(l).and_then(|loc| loc.directories.get_mut(d)) {
+                    directory.expanded = !directory.expanded;
+                }
+            }
+            NodeId::File(..) => {}
+        }
+    }
+
+    /// Wählt einen Knoten samt allen enthaltenen Dateien aus bzw. ab
+    pub fn set_selected(&mut self, node: NodeId, selected: bool) {
+        let files: Vec<&mut FileNode> = match node {
+            NodeId::Location(l) => self
+                .locations
+                .get_mut(l)
+                .map(|location| location.directories.iter_mut().flat_map(|dir| dir.files.iter_mut()).collect())
+                .unwrap_or_default(),
+            NodeId::Directory(l, d) => self
+                .locations
+                .get_mut(l)
+                .and_then(|location| location.directories.get_mut(d))
+                .map(|dir| dir.files.iter_mut().collect())
+                .unwrap_or_default(),
+            NodeId::File(l, d, f) => self
+                .locations
+                .get_mut(l)
+                .and_then(|location| location.directories.get_mut(d))
+                .and_then(|dir| dir.files.get_mut(f))
+                .into_iter()
+                .collect(),
+        };
+        for file in files {
+            file.selected = selected;
+        }
+    }
+
+    fn files(&self) -> impl Iterator<Item = &FileNode> {
+        self.locations.iter().flat_map(|location| location.directories.iter()).flat_map(|dir| dir.files.iter())
+    }
+
+    /// Anzahl und Größe der ausgewählten Dateien
+    pub fn selection(&self) -> (usize, u64) {
+        totals(self.files())
+    }
+
+    /// Entfernt alle abgewählten Dateien aus dem Plan, damit die Ausführung sie nicht anfasst
+    pub fn apply(&self, plan: &mut CleaningPlan) {
+        let deselected: HashSet<&Path> = self.files().filter(|file| !file.selected).map(|file| file.path.as_path()).collect();
+        plan.retain(|entry| !deselected.contains(entry.path.as_path()));
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let mut tree = column![].spacing(4);
+
+        for (l, location) in self.locations.iter().enumerate() {
+            let files = location.directories.iter().flat_map(|dir| dir.files.iter());
+            tree = tree.push(node_row(0, Some(location.expanded), &location.name, files, NodeId::Location(l)));
+            if !location.expanded {
+                continue;
+            }
+
+            for (d, directory) in location.directories.iter().enumerate() {
+                let label = directory.path.display().to_string();
+                tree = tree.push(node_row(1, Some(directory.expanded), &label, directory.files.iter(), NodeId::Directory(l, d)));
+                if !directory.expanded {
+                    continue;
+                }
+
+                for (f, file) in directory.files.iter().take(MAX_FILES_SHOWN).enumerate() {
+                    let name = file.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                    tree = tree.push(node_row(2, None, &name, std::iter::once(file), NodeId::File(l, d, f)));
+                }
+                if directory.files.len() > MAX_FILES_SHOWN {
+                    tree = tree.push(
+                        row![
+                            indent(2),
+                            text(format!("... und {} weitere (über das Verzeichnis auswählbar)", directory.files.len() - MAX_FILES_SHOWN)).size(13),
+                        ]
+                    );
+                }
+            }
+        }
+
+        scrollable(tree).height(Length::Fixed(320.0)).into()
+    }
+}
+
+/// Zeile eines Knotens: Aufklapp-Schalter, Auswahl, Name und Größe
+fn node_row<'a, 'f>(
+    depth: u16,
+    expanded: Option<bool>,
+    label: &str,
+    files: impl Iterator<Item = &'f FileNode> + Clone,
+    node: NodeId,
+) -> Element<'a, Message> {
+    let (selected, selected_size) = totals(files.clone());
+    let (count, size) = files.fold((0, 0), |(n, s), file| (n + 1, s + file.size));
+
+    let toggle: Element<'a, Message> = match expanded {
+        Some(expanded) => button(text(if expanded { "▾" } else { "▸" }).size(14))
+            .padding([0, 6])
+            .style(iced::theme::Button::Text)
+            .on_press(Message::PreviewExpandToggled(node))
+            .into(),
+        None => indent(1),
+    };
+    let info = if count == 1 {
+        format_bytes(size)
+    } else if selected == count {
+        format!("{} Dateien, {}", count, format_bytes(size))
+    } else {
+        format!("{} von {} Dateien, {} von {}", selected, count, format_bytes(selected_size), format_bytes(size))
+    };
+
+    row![
+        indent(depth),
+        toggle,
+        checkbox(label, selected == count, move |checked| Message::PreviewSelectionChanged(node, checked))
+            .size(16)
+            .text_size(14)
+            .width(Length::Fill),
+        text(info).size(13),
+    ]
+    .spacing(6)
+    .align_items(Alignment::Center)
+    .into()
+}
+
+fn indent<'a>(depth: u16) -> Element<'a, Message> {
+    iced::widget::horizontal_space(Length::Fixed(f32::from(depth) * 20.0)).into()
+}
+
+fn totals<'f>(files: impl Iterator<Item = &'f FileNode>) -> (usize, u64) {
+    files.filter(|file| file.selected).fold((0, 0), |(n, s), file| (n + 1, s + file.size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cleaning::{MatchReason, PlannedFile};
+
+    fn planned(location: &str, path: &str, size: u64) -> PlannedFile {
+        PlannedFile {
+            location: location.to_string(),
+            path: PathBuf::from(path),
+            size,
+            modified: None,
+            reason: MatchReason::Extension(String::from("tmp")),
+            companion: None,
+        }
+    }
+
+    #[test]
+    fn test_deselected_nodes_are_removed_from_plan() {
+        let mut plan = CleaningPlan::new();
+        for (location, path) in [
+            ("Temp", "/tmp/a/1.tmp"),
+            ("Temp", "/tmp/a/2.tmp"),
+            ("Temp", "/tmp/b/3.tmp"),
+            ("Cache", "/cache/4.tmp"),
+        ] {
+            plan.location_mut(location);
+            plan.push(planned(location, path, 10));
+        }
+
+        let mut tree = PreviewTree::new(&plan);
+        assert_eq!(tree.selection(), (4, 40));
+
+        // Ganzen Ort abwählen, dann eine einzelne Datei wieder auswählen
+        tree.set_selected(NodeId::Location(1), false);
+        tree.set_selected(NodeId::Directory(0, 0), false);
+        tree.set_selected(NodeId::File(0, 0, 1), true);
+        assert_eq!(tree.selection(), (2, 20));
+
+        tree.apply(&mut plan);
+        let remaining: Vec<_> = plan.entries.iter().map(|e| e.path.to_str().unwrap()).collect();
+        assert_eq!(remaining, vec!["/tmp/a/2.tmp", "/tmp/b/3.tmp"]);
+    }
+}
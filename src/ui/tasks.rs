@@ -0,0 +1,104 @@
+use iced::futures::channel::{mpsc, oneshot};
+use iced::futures::{SinkExt, StreamExt};
+use iced::subscription::{self, Subscription};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+
+use crate::cleaning::{
+    execute_with_monitor, scan_targets_with_monitor, CancellationToken, CleaningMonitor, CleaningPlan, CleaningProgress, HistoryEntry,
+    HistoryStore, PathTarget, TargetRegistry,
+};
+use crate::config::Profile;
+
+use super::app::Message;
+
+/// Durchsucht die Ziele des Profils in einem eigenen Thread, ohne etwas zu löschen
+///
+/// Mit `adhoc` wird statt der Ziele des Profils nur dieses Verzeichnis durchsucht; die
+/// Optionen stammen weiterhin aus dem Profil. Liefert Fortschritt und zum Schluss den Plan
+/// für die Vorschau als Nachrichten. Die Subscription lebt, solange die App sie mit
+/// derselben `run_id` anfordert.
+pub fn scan_temp_files(run_id: u64, cancel: CancellationToken, profile: Profile, adhoc: Option<PathBuf>) -> Subscription<Message> {
+    worker(("temp-scan", run_id), move |progress| {
+        let monitor = CleaningMonitor::new(cancel).with_callback(progress);
+        let options = profile.options();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| match adhoc {
+            Some(root) => scan_targets_with_monitor(&[&PathTarget::new(root)], Some(&options), &monitor),
+            None => {
+                let registry = TargetRegistry::with_builtin_targets();
+                scan_targets_with_monitor(&profile.select(&registry), Some(&options), &monitor)
+            }
+        }))
+            .map_err(|_| String::from("Suche unerwartet abgebrochen"));
+        Message::ScanCompleted(result)
+    })
+}
+
+/// Führt einen (in der Vorschau bestätigten) Plan mit den Optionen des Profils aus
+///
+/// Gelöscht wird genau, was im Plan steht. Abgeschlossene Läufe (außer Dry Runs)
+/// werden im Verlauf festgehalten, Läufe mit Ad-hoc-Ziel ohne Profilnamen.
+pub fn clean_temp_files(
+    run_id: u64,
+    cancel: CancellationToken,
+    profile_name: Option<String>,
+    profile: Profile,
+    plan: Arc<CleaningPlan>,
+) -> Subscription<Message> {
+    worker(("temp-cleaning", run_id), move |progress| {
+        let monitor = CleaningMonitor::new(cancel).with_callback(progress);
+        let options = profile.options();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| execute_with_monitor(&plan, &options, &monitor)))
+            .map_err(|_| String::from("Bereinigung unerwartet abgebrochen"));
+
+        if let (Ok(summary), false) = (&result, options.dry_run) {
+            let entry = HistoryEntry::from_summary(summary, profile_name.as_deref(), chrono::Utc::now());
+            if let Err(e) = HistoryStore::open_default().and_then(|store| store.append(&entry)) {
+                eprintln!("Verlauf konnte nicht gespeichert werden: {}", e);
+            }
+        }
+        Message::CleaningCompleted(result)
+    })
+}
+
+/// Führt blockierende Arbeit in einem eigenen Thread aus, ohne den Executor aufzuhalten
+pub async fn blocking<T, F>(work: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let _ = sender.send(panic::catch_unwind(AssertUnwindSafe(work)));
+    });
+    match receiver.await {
+        Ok(Ok(value)) => Ok(value),
+        _ => Err(String::from("Vorgang unerwartet abgebrochen")),
+    }
+}
+
+/// Startet `work` in einem eigenen Thread und leitet Fortschritt und Ergebnis weiter
+fn worker<F>(id: (&'static str, u64), work: F) -> Subscription<Message>
+where
+    F: FnOnce(Box<dyn Fn(CleaningProgress) + Send + Sync>) -> Message + Send + 'static,
+{
+    subscription::channel(id, 100, move |mut output| async move {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let progress_sender = sender.clone();
+
+        thread::spawn(move || {
+            let message = work(Box::new(move |progress| {
+                let _ = progress_sender.unbounded_send(Message::CleaningProgressed(progress));
+            }));
+            let _ = sender.unbounded_send(message);
+        });
+
+        while let Some(message) = receiver.next().await {
+            let _ = output.send(message).await;
+        }
+
+        iced::futures::future::pending().await
+    })
+}
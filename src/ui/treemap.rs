@@ -0,0 +1,286 @@
+use iced::mouse;
+use iced::widget::canvas::{self, event, Cache, Canvas, Event, Frame, Geometry, Path, Stroke, Text};
+use iced::{Color, Element, Length, Point, Rectangle, Renderer, Size, Theme};
+
+use crate::cleaning::{format_bytes, UsageNode};
+
+use super::app::Message;
+
+/// Farben der Kacheln der obersten Ebene, der Reihe nach vergeben
+const PALETTE: [Color; 6] = [
+    Color::from_rgb(0.27, 0.51, 0.71),
+    Color::from_rgb(0.40, 0.65, 0.45),
+    Color::from_rgb(0.85, 0.55, 0.25),
+    Color::from_rgb(0.60, 0.45, 0.70),
+    Color::from_rgb(0.80, 0.40, 0.40),
+    Color::from_rgb(0.35, 0.65, 0.70),
+];
+
+/// Kacheln, die kleiner sind, bekommen keine Beschriftung
+const MIN_LABEL_SIZE: Size = Size::new(70.0, 32.0);
+
+/// Höhe der Beschriftungszeile über verschachtelten Kacheln
+const HEADER_HEIGHT: f32 = 18.0;
+
+/// Treemap eines Verzeichnisses: Unterverzeichnisse als Kacheln proportional zu ihrer Größe,
+/// darin jeweils deren Unterverzeichnisse; ein Klick auf eine Kachel öffnet das Verzeichnis
+pub struct Treemap<'a> {
+    node: &'a UsageNode,
+    cache: &'a Cache,
+}
+
+impl<'a> Treemap<'a> {
+    pub fn new(node: &'a UsageNode, cache: &'a Cache) -> Self {
+        Self { node, cache }
+    }
+
+    pub fn view(self, height: f32) -> Element<'a, Message> {
+        Canvas::new(self).width(Length::Fill).height(Length::Fixed(height)).into()
+    }
+
+    /// Kachel unter dem Mauszeiger
+    fn tile_at(&self, bounds: Rectangle, cursor: mouse::Cursor) -> Option<Tile<'a>> {
+        let position = cursor.position_in(bounds)?;
+        tiles(self.node, Rectangle::new(Point::ORIGIN, bounds.size()))
+            .into_iter()
+            .find(|tile| tile.rect.contains(position))
+    }
+}
+
+/// Eine Kachel; `node` ist `None` für die Dateien direkt im Verzeichnis
+struct Tile<'a> {
+    rect: Rectangle,
+    node: Option<&'a UsageNode>,
+    size: u64,
+}
+
+impl Tile<'_> {
+    fn label(&self) -> String {
+        match self.node {
+            Some(node) => node.name.clone(),
+            None => String::from("(Dateien)"),
+        }
+    }
+}
+
+/// Kacheln für die Unterverzeichnisse und die direkt enthaltenen Dateien eines Knotens
+fn tiles(node: &UsageNode, bounds: Rectangle) -> Vec<Tile<'_>> {
+    let mut items: Vec<(Option<&UsageNode>, u64)> =
+        node.children.iter().filter(|child| child.size > 0).map(|child| (Some(child), child.size)).collect();
+    if node.file_size > 0 {
+        items.push((None, node.file_size));
+    }
+    items.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+
+    let sizes: Vec<u64> = items.iter().map(|(_, size)| *size).collect();
+    squarify(&sizes, bounds)
+        .into_iter()
+        .zip(items)
+        .map(|(rect, (node, size))| Tile { rect, node, size })
+        .collect()
+}
+
+impl canvas::Program<Message> for Treemap<'_> {
+    type State = ();
+
+    fn update(&self, _state: &mut (), event: Event, bounds: Rectangle, cursor: mouse::Cursor) -> (event::Status, Option<Message>) {
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
+            if let Some(node) = self.tile_at(bounds, cursor).and_then(|tile| tile.node) {
+                return (event::Status::Captured, Some(Message::UsageDrillDown(node.path.clone())));
+            }
+        }
+        (event::Status::Ignored, None)
+    }
+
+    fn draw(&self, _state: &(), renderer: &Renderer, _theme: &Theme, bounds: Rectangle, cursor: mouse::Cursor) -> Vec<Geometry> {
+        let map = self.cache.draw(renderer, bounds.size(), |frame| {
+            let area = Rectangle::new(Point::ORIGIN, frame.size());
+            for (index, tile) in tiles(self.node, area).iter().enumerate() {
+                let color = match tile.node {
+                    Some(_) => PALETTE[index % PALETTE.len()],
+                    None => Color::from_rgb(0.6, 0.6, 0.6),
+                };
+                draw_tile(frame, tile.rect, color);
+
+                // Zweite Ebene angedeutet, damit die Struktur ohne Klick erkennbar ist
+                if let Some(node) = tile.node {
+                    let inner = Rectangle {
+                        x: tile.rect.x + 3.0,
+                        y: tile.rect.y + HEADER_HEIGHT,
+                        width: tile.rect.width - 6.0,
+                        height: tile.rect.height - HEADER_HEIGHT - 3.0,
+                    };
+                    if inner.width > 20.0 && inner.height > 20.0 {
+                        for child in tiles(node, inner) {
+                            draw_tile(frame, child.rect, lighten(color, 0.25));
+                        }
+                    }
+                }
+
+                if tile.rect.width >= MIN_LABEL_SIZE.width && tile.rect.height >= MIN_LABEL_SIZE.height {
+                    frame.fill_text(Text {
+                        content: fit(&format!("{} ({})", tile.label(), format_bytes(tile.size)), tile.rect.width),
+                        position: Point::new(tile.rect.x + 4.0, tile.rect.y + 2.0),
+                        color: Color::WHITE,
+                        size: 13.0,
+                        ..Text::default()
+                    });
+                }
+            }
+        });
+
+        let mut overlay = Frame::new(renderer, bounds.size());
+        if let Some(tile) = self.tile_at(bounds, cursor) {
+            overlay.stroke(
+                &Path::rectangle(tile.rect.position(), tile.rect.size()),
+                Stroke::default().with_color(Color::BLACK).with_width(2.0),
+            );
+            // Name und Größe auch für zu kleine Kacheln
+            let hint = format!("{} ({})", tile.label(), format_bytes(tile.size));
+            let position = Point::new(4.0, bounds.height - 18.0);
+            overlay.fill_rectangle(
+                Point::new(0.0, bounds.height - 20.0),
+                Size::new((hint.chars().count() as f32 * 7.5 + 8.0).min(bounds.width), 20.0),
+                Color::from_rgba(1.0, 1.0, 1.0, 0.9),
+            );
+            overlay.fill_text(Text {
+                content: hint,
+                position,
+                color: Color::BLACK,
+                size: 13.0,
+                ..Text::default()
+            });
+        }
+
+        vec![map, overlay.into_geometry()]
+    }
+
+    fn mouse_interaction(&self, _state: &(), bounds: Rectangle, cursor: mouse::Cursor) -> mouse::Interaction {
+        match self.tile_at(bounds, cursor) {
+            Some(Tile { node: Some(_), .. }) => mouse::Interaction::Pointer,
+            _ => mouse::Interaction::default(),
+        }
+    }
+}
+
+fn draw_tile(frame: &mut Frame, rect: Rectangle, color: Color) {
+    frame.fill_rectangle(rect.position(), rect.size(), color);
+    frame.stroke(
+        &Path::rectangle(rect.position(), rect.size()),
+        Stroke::default().with_color(Color::WHITE).with_width(1.0),
+    );
+}
+
+fn lighten(color: Color, amount: f32) -> Color {
+    Color::from_rgb(
+        color.r + (1.0 - color.r) * amount,
+        color.g + (1.0 - color.g) * amount,
+        color.b + (1.0 - color.b) * amount,
+    )
+}
+
+/// Kürzt eine Beschriftung grob auf die Breite der Kachel
+fn fit(label: &str, width: f32) -> String {
+    let max_chars = ((width - 8.0) / 7.5).max(1.0) as usize;
+    if label.chars().count() <= max_chars {
+        label.to_string()
+    } else {
+        let mut short: String = label.chars().take(max_chars.saturating_sub(1)).collect();
+        short.push('…');
+        short
+    }
+}
+
+/// Teilt `bounds` in Rechtecke mit Flächen proportional zu `sizes` auf (Squarified Treemap)
+///
+/// `sizes` muss absteigend sortiert sein; das Ergebnis hat dieselbe Reihenfolge. Reihen werden
+/// entlang der kürzeren Seite so lange verlängert, wie sich das ungünstigste Seitenverhältnis verbessert.
+pub fn squarify(sizes: &[u64], bounds: Rectangle) -> Vec<Rectangle> {
+    let total: u64 = sizes.iter().sum();
+    if total == 0 || bounds.width <= 0.0 || bounds.height <= 0.0 {
+        return vec![Rectangle::new(bounds.position(), Size::ZERO); sizes.len()];
+    }
+
+    let scale = f64::from(bounds.width) * f64::from(bounds.height) / total as f64;
+    let areas: Vec<f64> = sizes.iter().map(|&size| size as f64 * scale).collect();
+    let (mut x, mut y) = (f64::from(bounds.x), f64::from(bounds.y));
+    let (mut width, mut height) = (f64::from(bounds.width), f64::from(bounds.height));
+    let mut result = Vec::with_capacity(sizes.len());
+
+    let mut start = 0;
+    while start < areas.len() {
+        let side = width.min(height);
+        let mut end = start + 1;
+        let mut best = worst_ratio(&areas[start..end], side);
+        while end < areas.len() {
+            let next = worst_ratio(&areas[start..=end], side);
+            if next > best {
+                break;
+            }
+            best = next;
+            end += 1;
+        }
+
+        let row_area: f64 = areas[start..end].iter().sum();
+        let thickness = if side > 0.0 { row_area / side } else { 0.0 };
+        let mut offset = 0.0;
+        for &area in &areas[start..end] {
+            let length = if thickness > 0.0 { area / thickness } else { 0.0 };
+            let rect = if width >= height {
+                Rectangle { x: x as f32, y: (y + offset) as f32, width: thickness as f32, height: length as f32 }
+            } else {
+                Rectangle { x: (x + offset) as f32, y: y as f32, width: length as f32, height: thickness as f32 }
+            };
+            result.push(rect);
+            offset += length;
+        }
+
+        if width >= height {
+            x += thickness;
+            width -= thickness;
+        } else {
+            y += thickness;
+            height -= thickness;
+        }
+        start = end;
+    }
+    result
+}
+
+/// Ungünstigstes Seitenverhältnis einer Reihe entlang einer Seite der Länge `side`
+fn worst_ratio(row: &[f64], side: f64) -> f64 {
+    let sum: f64 = row.iter().sum();
+    let max = row.iter().cloned().fold(f64::MIN, f64::max);
+    let min = row.iter().cloned().fold(f64::MAX, f64::min);
+    let (side2, sum2) = (side * side, sum * sum);
+    (side2 * max / sum2).max(sum2 / (side2 * min))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_squarify_fills_bounds_proportionally() {
+        let bounds = Rectangle::new(Point::new(10.0, 20.0), Size::new(600.0, 400.0));
+        let sizes = [6, 6, 4, 3, 2, 2, 1];
+        let rects = squarify(&sizes, bounds);
+
+        assert_eq!(rects.len(), sizes.len());
+        let total: u64 = sizes.iter().sum();
+        for (rect, size) in rects.iter().zip(sizes) {
+            let expected = 600.0 * 400.0 * size as f32 / total as f32;
+            assert!((rect.width * rect.height - expected).abs() < 1.0);
+            assert!(rect.x >= 10.0 - 0.01 && rect.x + rect.width <= 610.01);
+            assert!(rect.y >= 20.0 - 0.01 && rect.y + rect.height <= 420.01);
+        }
+        // Keine Überlappungen
+        for (i, a) in rects.iter().enumerate() {
+            for b in &rects[i + 1..] {
+                let overlap_x = (a.x + a.width).min(b.x + b.width) - a.x.max(b.x);
+                let overlap_y = (a.y + a.height).min(b.y + b.height) - a.y.max(b.y);
+                assert!(overlap_x <= 0.01 || overlap_y <= 0.01);
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use iced::widget::{button, container, text};
-use iced::{Color, Length, Theme};
+use iced::Length;
 
 use super::style;
 